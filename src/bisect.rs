@@ -1,6 +1,8 @@
 //! 二分探索(Binary Search)
 //!
 //! - [`Bisect`] : 整数・浮動小数点数の範囲上の二分探索
+//! - [`TernarySearch`] : 整数の範囲上の単峰関数・凸関数の最適化
+//! - [`TernarySearchF64`] : 浮動小数点数の範囲上の単峰関数の最適化
 //! - [`SliceBoundsExt`] : ソート済みスライス上の lower_bound / upper_bound
 //!
 //! # Examples
@@ -15,6 +17,29 @@
 //! assert_eq!(result, 6); // 6*6 = 36 >= 30
 //! ```
 //!
+//! ## [`TernarySearch`]
+//!
+//! ```
+//! use reprol::bisect::TernarySearch;
+//!
+//! // (x - 3)^2 を最小化する x
+//! assert_eq!((0..100).ternary_search_min(|&x| (x - 3) * (x - 3)), 3);
+//!
+//! // 最小値が平坦に続く場合は，その左端と値を返す
+//! let f = |&x: &i32| (x - 10).max(0) + (5 - x).max(0);
+//! assert_eq!((0..20).argmin_convex(f), (5, 0));
+//! ```
+//!
+//! ## [`TernarySearchF64`]
+//!
+//! ```
+//! use reprol::bisect::TernarySearchF64;
+//!
+//! // 黄金分割探索を100回繰り返す
+//! let x = (0.0..10.0).ternary_search_min(100, |&x| (x - 2.5) * (x - 2.5));
+//! assert!((x - 2.5).abs() < 1e-9);
+//! ```
+//!
 //! ## [`SliceBoundsExt`]
 //!
 //! ```
@@ -118,6 +143,207 @@ impl Bisect<f64> for Range<f64> {
     }
 }
 
+/// 範囲上の単峰関数の最適化を行うトレイト．
+pub trait TernarySearch<T> {
+    /// 範囲上で`f`を最小化する`x`を返す．
+    ///
+    /// フィボナッチ探索(離散版の黄金分割探索)により，O(log n) 回の`f`の評価で求める．
+    /// 最小値をとる`x`が複数ある場合はそのいずれかを返す．
+    ///
+    /// # 制約
+    /// - `f`は下に凸，または狭義単峰(最小値の両側で狭義単調)
+    ///
+    /// # Panics
+    ///
+    /// 範囲が空の場合にパニックする．
+    fn ternary_search_min<V: PartialOrd>(self, f: impl FnMut(&T) -> V) -> T;
+
+    /// 範囲上で`f`を最大化する`x`を返す．
+    ///
+    /// # 制約
+    /// - `f`は上に凸，または狭義単峰(最大値の両側で狭義単調)
+    ///
+    /// # Panics
+    ///
+    /// 範囲が空の場合にパニックする．
+    fn ternary_search_max<V: PartialOrd>(self, f: impl FnMut(&T) -> V) -> T;
+
+    /// 下に凸な`f`を最小化する最小の`x`と，その値`f(x)`を返す．
+    ///
+    /// `f(x) <= f(x + 1)`となる最小の`x`を二分探索で求める．
+    /// 最小値が平坦に続く場合も，その左端を返す．
+    ///
+    /// # 制約
+    /// - `f`は下に凸
+    ///
+    /// # Panics
+    ///
+    /// 範囲が空の場合にパニックする．
+    fn argmin_convex<V: PartialOrd>(self, f: impl FnMut(&T) -> V) -> (T, V);
+}
+
+impl<T, B> TernarySearch<T> for B
+where
+    T: BisectInteger,
+    B: RangeBounds<T>,
+{
+    fn ternary_search_min<V: PartialOrd>(self, mut f: impl FnMut(&T) -> V) -> T {
+        let Range { start, end } = normalize(self, T::MIN, T::SUP);
+        assert!(start < end);
+        let n = T::distance(&start, &end);
+        let i = fibonacci_search(n, |i| f(&start.offset(i)), |lhs, rhs| lhs <= rhs);
+        start.offset(i)
+    }
+
+    fn ternary_search_max<V: PartialOrd>(self, mut f: impl FnMut(&T) -> V) -> T {
+        let Range { start, end } = normalize(self, T::MIN, T::SUP);
+        assert!(start < end);
+        let n = T::distance(&start, &end);
+        let i = fibonacci_search(n, |i| f(&start.offset(i)), |lhs, rhs| lhs >= rhs);
+        start.offset(i)
+    }
+
+    fn argmin_convex<V: PartialOrd>(self, mut f: impl FnMut(&T) -> V) -> (T, V) {
+        let Range { start, end } = normalize(self, T::MIN, T::SUP);
+        assert!(start < end);
+        let last = start.offset(T::distance(&start, &end) - 1);
+        let x = if start < last {
+            (start..last).bisect(|x| f(x) > f(&x.offset(1)))
+        } else {
+            start
+        };
+        let value = f(&x);
+        (x, value)
+    }
+}
+
+/// 長さ`n`の範囲`[0, n)`上でフィボナッチ探索を行い，最適な位置を返す．
+///
+/// `is_better_or_equal(a, b)`は`a`が`b`以上に良い値かを返す．
+/// 範囲外(`n`以上)の位置は最も悪い値として扱う．
+fn fibonacci_search<V>(
+    n: u128,
+    mut f: impl FnMut(u128) -> V,
+    mut is_better_or_equal: impl FnMut(&V, &V) -> bool,
+) -> u128 {
+    // fib[k] - 1 >= n となるまでフィボナッチ数を求める
+    let mut fib = vec![1u128, 2];
+    while fib[fib.len() - 1] - 1 < n && fib[fib.len() - 1] < u128::MAX {
+        let next = fib[fib.len() - 1].saturating_add(fib[fib.len() - 2]);
+        fib.push(next);
+    }
+
+    let mut eval = |i: u128| (i < n).then(|| f(i));
+    let mut cmp = |lhs: &Option<V>, rhs: &Option<V>| match (lhs, rhs) {
+        (Some(lhs), Some(rhs)) => is_better_or_equal(lhs, rhs),
+        (_, None) => true,
+        (None, Some(_)) => false,
+    };
+
+    // 探索区間は[lo, lo + fib[k] - 1)で，c = lo - 1 + fib[k - 2]，d = lo - 1 + fib[k - 1]
+    let mut k = fib.len() - 1;
+    let mut lo = 0;
+    if k < 2 {
+        return lo;
+    }
+    let mut c = lo + (fib[k - 2] - 1);
+    let mut d = lo + (fib[k - 1] - 1);
+    let mut fc = eval(c);
+    let mut fd = eval(d);
+
+    loop {
+        k -= 1;
+        if cmp(&fc, &fd) {
+            // 最適な位置は[lo, d)にある
+            d = c;
+            fd = fc;
+            if k < 2 {
+                break;
+            }
+            c = lo + (fib[k - 2] - 1);
+            fc = eval(c);
+        } else {
+            // 最適な位置は(c, lo + fib[k + 1] - 1)にある
+            lo = c + 1;
+            c = d;
+            fc = fd;
+            if k < 2 {
+                break;
+            }
+            d = lo + (fib[k - 1] - 1);
+            fd = eval(d);
+        }
+    }
+
+    lo
+}
+
+/// 浮動小数点数の範囲上の単峰関数の最適化を行うトレイト．
+pub trait TernarySearchF64 {
+    /// 範囲上で`f`を最小化する`x`を，黄金分割探索を`iterations`回繰り返して求める．
+    ///
+    /// 1回の反復で探索区間の幅は約0.618倍になる．
+    ///
+    /// # 制約
+    /// - `f`は狭義単峰
+    fn ternary_search_min<V: PartialOrd>(self, iterations: usize, f: impl FnMut(&f64) -> V) -> f64;
+
+    /// 範囲上で`f`を最大化する`x`を，黄金分割探索を`iterations`回繰り返して求める．
+    ///
+    /// # 制約
+    /// - `f`は狭義単峰
+    fn ternary_search_max<V: PartialOrd>(self, iterations: usize, f: impl FnMut(&f64) -> V) -> f64;
+}
+
+impl TernarySearchF64 for Range<f64> {
+    fn ternary_search_min<V: PartialOrd>(self, iterations: usize, f: impl FnMut(&f64) -> V) -> f64 {
+        golden_section_search(self, iterations, f, |lhs, rhs| lhs <= rhs)
+    }
+
+    fn ternary_search_max<V: PartialOrd>(self, iterations: usize, f: impl FnMut(&f64) -> V) -> f64 {
+        golden_section_search(self, iterations, f, |lhs, rhs| lhs >= rhs)
+    }
+}
+
+/// 黄金分割探索．`is_better_or_equal(a, b)`は`a`が`b`以上に良い値かを返す．
+fn golden_section_search<V>(
+    range: Range<f64>,
+    iterations: usize,
+    mut f: impl FnMut(&f64) -> V,
+    mut is_better_or_equal: impl FnMut(&V, &V) -> bool,
+) -> f64 {
+    let Range {
+        start: mut a,
+        end: mut b,
+    } = range;
+    assert!(a <= b);
+
+    // 1 / φ
+    let r = (5f64.sqrt() - 1.0) / 2.0;
+    let mut c = b - r * (b - a);
+    let mut d = a + r * (b - a);
+    let mut fc = f(&c);
+    let mut fd = f(&d);
+
+    for _ in 0..iterations {
+        if is_better_or_equal(&fc, &fd) {
+            b = d;
+            d = c;
+            fd = fc;
+            c = b - r * (b - a);
+            fc = f(&c);
+        } else {
+            a = c;
+            c = d;
+            fc = fd;
+            d = a + r * (b - a);
+            fd = f(&d);
+        }
+    }
+
+    (a + b) / 2.0
+}
+
 /// ソート済みスライスに対する境界探索の拡張トレイト．
 pub trait SliceBoundsExt {
     /// 要素の型．
@@ -184,6 +410,12 @@ trait BisectInteger: Discrete {
 
     /// `start` と `end` の中間値を返す．隣接している場合は `None`．
     fn midpoint(start: &Self, end: &Self) -> Option<Self>;
+
+    /// `end - start` を返す．
+    fn distance(start: &Self, end: &Self) -> u128;
+
+    /// `self + d` を返す．
+    fn offset(&self, d: u128) -> Self;
 }
 
 macro_rules! impl_bisect_integer_inner {
@@ -195,6 +427,14 @@ macro_rules! impl_bisect_integer_inner {
             fn midpoint(start: &Self, end: &Self) -> Option<Self> {
                 (end - start > 1).then(|| start + (end - start) / 2)
             }
+
+            fn distance(start: &Self, end: &Self) -> u128 {
+                (*end as u128).wrapping_sub(*start as u128)
+            }
+
+            fn offset(&self, d: u128) -> Self {
+                (*self as u128).wrapping_add(d) as Self
+            }
         }
     };
 }
@@ -443,6 +683,82 @@ mod tests {
         (f64::NAN..f64::NAN).bisect(|&x: &f64| x < 0.5);
     }
 
+    // ========== TernarySearch 単峰関数の最適化 ==========
+
+    #[test]
+    fn test_ternary_search_basic() {
+        let f = |&x: &i64| (x - 7) * (x - 7);
+        assert_eq!((0..100).ternary_search_min(f), 7);
+        assert_eq!((-100..=0).ternary_search_min(f), 0);
+        assert_eq!((8..9).ternary_search_min(f), 8);
+        assert_eq!((..).ternary_search_min(|&x: &i32| (x as i64 - 5).abs()), 5);
+        assert_eq!(
+            (0u8..).ternary_search_max(|&x| -(x as i32 - 200).abs()),
+            200
+        );
+        assert_eq!((-50..50).ternary_search_max(|&x| -f(&x)), 7);
+        assert_eq!((0..100).argmin_convex(f), (7, 0));
+        assert_eq!((10..=10).argmin_convex(f), (10, 9));
+    }
+
+    #[test]
+    fn test_ternary_search_randomized() {
+        let mut rng = get_test_rng();
+
+        for _ in 0..1000 {
+            // 平坦な部分を含む下に凸な関数
+            let a = rng.random_range(-30..=30);
+            let b = rng.random_range(a..=30);
+            let s = rng.random_range(0..=3);
+            let t = rng.random_range(0..=3);
+            let f = |&x: &i64| (a - x).max(0) * s + (x - b).max(0) * t;
+
+            let l = rng.random_range(-40..=40);
+            let r = rng.random_range(l + 1..=41);
+            let min = (l..r).map(|x| f(&x)).min().unwrap();
+            let argmin = (l..r).find(|x| f(x) == min).unwrap();
+
+            let x = (l..r).ternary_search_min(f);
+            assert!((l..r).contains(&x));
+            assert_eq!(f(&x), min);
+
+            let x = (l..r).ternary_search_max(|x| -f(x));
+            assert!((l..r).contains(&x));
+            assert_eq!(f(&x), min);
+
+            assert_eq!((l..r).argmin_convex(f), (argmin, min));
+        }
+    }
+
+    #[test]
+    fn test_ternary_search_evaluations() {
+        let mut count = 0;
+        let x = (0..1_000_000_000i64).ternary_search_min(|&x| {
+            count += 1;
+            (x - 123_456_789).abs()
+        });
+        assert_eq!(x, 123_456_789);
+        assert!(count <= 50);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_ternary_search_empty_range_panics() {
+        (3..3).ternary_search_min(|&x: &i32| x);
+    }
+
+    #[test]
+    fn test_ternary_search_f64() {
+        let x = (-10.0..10.0).ternary_search_min(100, |&x| (x - 1.3) * (x - 1.3));
+        assert!((x - 1.3).abs() < 1e-9);
+
+        let x = (0.0..std::f64::consts::PI).ternary_search_max(100, |&x| x.sin());
+        assert!((x - std::f64::consts::FRAC_PI_2).abs() < 1e-6);
+
+        let x = (2.0..2.0).ternary_search_min(10, |&x| x);
+        assert_eq!(x, 2.0);
+    }
+
     // ========== SliceBoundsExt スライス境界探索 ==========
 
     #[test]
@@ -0,0 +1,214 @@
+//! 座標圧縮(coordinate compression)
+//!
+//! 値の集合をソートして重複を除き，各値をその順位(0-indexed)に対応させる．
+//! 値と添字の相互変換に加えて，任意の値に対するlower bound・upper boundや，
+//! 値の範囲を添字の半開区間に変換する機能を提供する．
+//!
+//! # 使用例
+//! ```
+//! use reprol::compress::CoordinateCompressor;
+//!
+//! let cc = CoordinateCompressor::new([100, 5, 30, 5, 70]);
+//! assert_eq!(cc.len(), 4);
+//! assert_eq!(cc.index_of(&30), 1);
+//! assert_eq!(cc.value_at(3), &100);
+//! assert_eq!(cc.lower_bound(&31), 2);
+//! assert_eq!(cc.upper_bound(&70), 3);
+//! assert_eq!(cc.range(10..=70), 1..3); // 30, 70
+//! assert_eq!(cc.range(..30), 0..1); // 5
+//! assert_eq!(cc.compress(&[70, 5]), vec![2, 0]);
+//! ```
+//!
+//! # 問題例
+//! - [ABC036 C](https://atcoder.jp/contests/abc036/tasks/abc036_c)
+
+use std::{
+    iter::FromIterator,
+    ops::{Bound, Index, Range, RangeBounds},
+};
+
+use crate::bisect::SliceBoundsExt;
+
+/// 座標圧縮
+#[derive(Clone, Debug)]
+pub struct CoordinateCompressor<T> {
+    /// ソートされたuniqueな値のリスト
+    values: Vec<T>,
+}
+
+impl<T: Ord> CoordinateCompressor<T> {
+    /// 値の集合`values`から構築する．
+    pub fn new(values: impl IntoIterator<Item = T>) -> Self {
+        let mut values = values.into_iter().collect::<Vec<_>>();
+        values.sort_unstable();
+        values.dedup();
+        Self { values }
+    }
+
+    /// 異なる値の個数を返す．
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// 値が1つもないかを返す．
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// 値`x`が含まれるかを返す．
+    pub fn contains(&self, x: &T) -> bool {
+        self.values.binary_search(x).is_ok()
+    }
+
+    /// 値`x`の添字を返す．
+    ///
+    /// # Panics
+    ///
+    /// `x`が含まれない場合はパニックする．
+    pub fn index_of(&self, x: &T) -> usize {
+        self.values
+            .binary_search(x)
+            .expect("value not registered in CoordinateCompressor")
+    }
+
+    /// 添字`index`に対応する値を返す．
+    pub fn value_at(&self, index: usize) -> &T {
+        &self.values[index]
+    }
+
+    /// `x`以上の値の最小の添字を返す．該当なしの場合は`len()`を返す．
+    pub fn lower_bound(&self, x: &T) -> usize {
+        self.values.lower_bound(x)
+    }
+
+    /// `x`より大きい値の最小の添字を返す．該当なしの場合は`len()`を返す．
+    pub fn upper_bound(&self, x: &T) -> usize {
+        self.values.upper_bound(x)
+    }
+
+    /// 値が`range`に含まれる添字の半開区間を返す．
+    pub fn range(&self, range: impl RangeBounds<T>) -> Range<usize> {
+        let l = match range.start_bound() {
+            Bound::Included(x) => self.lower_bound(x),
+            Bound::Excluded(x) => self.upper_bound(x),
+            Bound::Unbounded => 0,
+        };
+        let r = match range.end_bound() {
+            Bound::Included(x) => self.upper_bound(x),
+            Bound::Excluded(x) => self.lower_bound(x),
+            Bound::Unbounded => self.len(),
+        };
+        l..r.max(l)
+    }
+
+    /// 各値を添字に変換した配列を返す．
+    ///
+    /// # Panics
+    ///
+    /// 含まれない値がある場合はパニックする．
+    pub fn compress(&self, v: &[T]) -> Vec<usize> {
+        v.iter().map(|x| self.index_of(x)).collect()
+    }
+
+    /// ソートされたuniqueな値のリストを返す．
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+}
+
+impl<T: Ord> FromIterator<T> for CoordinateCompressor<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::new(iter)
+    }
+}
+
+impl<T: Ord> From<Vec<T>> for CoordinateCompressor<T> {
+    fn from(v: Vec<T>) -> Self {
+        Self::new(v)
+    }
+}
+
+impl<T: Ord, const N: usize> From<[T; N]> for CoordinateCompressor<T> {
+    fn from(v: [T; N]) -> Self {
+        Self::new(v)
+    }
+}
+
+impl<T> Index<usize> for CoordinateCompressor<T> {
+    type Output = T;
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.values[index]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::*;
+    use crate::utils::test_utils::random::get_test_rng;
+
+    #[test]
+    fn test() {
+        let cc = CoordinateCompressor::from(vec![3, -1, 4, 1, 5, 9, 2, 6, 5, 3]);
+        assert_eq!(cc.values(), &[-1, 1, 2, 3, 4, 5, 6, 9]);
+        assert_eq!(cc.index_of(&-1), 0);
+        assert_eq!(cc.index_of(&9), 7);
+        assert_eq!(cc[2], 2);
+        assert!(cc.contains(&6));
+        assert!(!cc.contains(&7));
+        assert_eq!(cc.range(7..8), 7..7);
+        assert_eq!(cc.range((Bound::Included(8), Bound::Excluded(3))), 7..7);
+        assert_eq!(cc.range(..), 0..8);
+        assert_eq!(cc.range((Bound::Excluded(1), Bound::Included(5))), 2..6);
+
+        let cc = CoordinateCompressor::<i32>::from_iter([]);
+        assert!(cc.is_empty());
+        assert_eq!(cc.range(..), 0..0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_of_panics_when_value_is_not_registered() {
+        let cc = CoordinateCompressor::new([1, 3]);
+        cc.index_of(&2);
+    }
+
+    #[test]
+    fn test_randomized() {
+        let mut rng = get_test_rng();
+
+        for _ in 0..100 {
+            let n = rng.random_range(0..50);
+            let v = (0..n)
+                .map(|_| rng.random_range(-30..=30))
+                .collect::<Vec<i32>>();
+            let cc = CoordinateCompressor::new(v.clone());
+
+            for (i, x) in cc.compress(&v).into_iter().zip(&v) {
+                assert_eq!(cc.value_at(i), x);
+            }
+
+            for _ in 0..50 {
+                let l = rng.random_range(-40..=40);
+                let r = rng.random_range(-40..=40);
+                let expected = cc
+                    .values()
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, x)| (l..r).contains(*x))
+                    .map(|(i, _)| i)
+                    .collect::<Vec<_>>();
+                assert_eq!(cc.range(l..r).collect::<Vec<_>>(), expected);
+                assert_eq!(
+                    cc.lower_bound(&l),
+                    cc.values().iter().filter(|&&x| x < l).count()
+                );
+                assert_eq!(
+                    cc.upper_bound(&l),
+                    cc.values().iter().filter(|&&x| x <= l).count()
+                );
+            }
+        }
+    }
+}
@@ -26,10 +26,72 @@
 //! assert_eq!(dijkstra.cost(&3), None);
 //! assert_eq!(dijkstra.cost(&4), Some(&9));
 //! ```
+//!
+//! コストが符号なし整数の場合は，優先度付きキューとしてRadix Heapを用いる[`RadixDijkstra`]が使える．
+//!
+//! ```
+//! use reprol::dijkstra::RadixDijkstra;
+//! let graph = vec![vec![(1, 2u64)], vec![(2, 3)], vec![]];
+//! let dijkstra = RadixDijkstra::new(
+//!     graph.len(),
+//!     &0,
+//!     &0,
+//!     |&v| v,
+//!     |&v| graph[v].iter().cloned(),
+//! );
+//! assert_eq!(dijkstra.cost(&2), Some(&5));
+//! ```
 
-use std::{cmp::Reverse, collections::BinaryHeap, fmt::Debug, ops::Add};
+use std::{cmp::Reverse, collections::BinaryHeap, fmt::Debug, marker::PhantomData, ops::Add};
 
-use crate::path_tracker::{NoPath, PathTracker, WithPath};
+use crate::{
+    ds::radix_heap::{RadixHeap, RadixKey},
+    path_tracker::{NoPath, PathTracker, WithPath},
+};
+
+/// ダイクストラで使う優先度付きキュー．
+/// コスト`C`が最小の要素から取り出す．
+pub trait Queue<C, V> {
+    fn new() -> Self;
+    fn push(&mut self, cost: C, v: V);
+    fn pop(&mut self) -> Option<(C, V)>;
+}
+
+// 任意のコスト用
+impl<C: Ord, V: Ord> Queue<C, V> for BinaryHeap<(Reverse<C>, V)> {
+    #[inline(always)]
+    fn new() -> Self {
+        BinaryHeap::new()
+    }
+
+    #[inline(always)]
+    fn push(&mut self, cost: C, v: V) {
+        self.push((Reverse(cost), v));
+    }
+
+    #[inline(always)]
+    fn pop(&mut self) -> Option<(C, V)> {
+        self.pop().map(|(Reverse(cost), v)| (cost, v))
+    }
+}
+
+// 符号なし整数のコスト用
+impl<C: RadixKey, V> Queue<C, V> for RadixHeap<C, V> {
+    #[inline(always)]
+    fn new() -> Self {
+        RadixHeap::new()
+    }
+
+    #[inline(always)]
+    fn push(&mut self, cost: C, v: V) {
+        self.push(cost, v);
+    }
+
+    #[inline(always)]
+    fn pop(&mut self) -> Option<(C, V)> {
+        self.pop()
+    }
+}
 
 /// ダイクストラの本体．
 ///
@@ -37,19 +99,22 @@ use crate::path_tracker::{NoPath, PathTracker, WithPath};
 /// - `C`: コストの型
 /// - `I`: `V`をインデックス(`usize`)に変換する関数
 /// - `P`: 経路情報に関する構造体(`WithPath` または `NoPath`)
-pub struct DijkstraImpl<V, C, I, P> {
+/// - `Q`: 優先度付きキュー(`BinaryHeap` または `RadixHeap`)
+pub struct DijkstraImpl<V, C, I, P, Q> {
     start: V,
     to_index: I,
     costs: Vec<Option<C>>,
     path_tracker: P,
+    phantom: PhantomData<Q>,
 }
 
-impl<V, C, I, P> DijkstraImpl<V, C, I, P>
+impl<V, C, I, P, Q> DijkstraImpl<V, C, I, P, Q>
 where
     V: Clone + Ord,
     C: Clone + Ord + Add<Output = C>,
     I: Fn(&V) -> usize,
     P: PathTracker<V>,
+    Q: Queue<C, V>,
 {
     pub fn new<E>(
         n: usize,
@@ -64,12 +129,12 @@ where
         let mut costs = vec![None; n];
         let mut path_tracker = P::new(n);
 
-        let mut heap = BinaryHeap::new();
+        let mut heap = Q::new();
 
         costs[to_index(start)] = Some(zero.clone());
-        heap.push((Reverse(zero.clone()), start.clone()));
+        heap.push(zero.clone(), start.clone());
 
-        while let Some((Reverse(cost), v)) = heap.pop() {
+        while let Some((cost, v)) = heap.pop() {
             let index_v = to_index(&v);
             if costs[index_v].as_ref().is_some_and(|cost_v| cost_v < &cost) {
                 continue;
@@ -85,7 +150,7 @@ where
                 {
                     costs[index_nv] = Some(new_cost_nv.clone());
                     path_tracker.set_previous(index_nv, &v);
-                    heap.push((Reverse(new_cost_nv), nv));
+                    heap.push(new_cost_nv, nv);
                 }
             }
         }
@@ -95,6 +160,7 @@ where
             to_index,
             costs,
             path_tracker,
+            phantom: PhantomData,
         }
     }
 
@@ -109,11 +175,12 @@ where
     }
 }
 
-impl<V, C, I> DijkstraImpl<V, C, I, WithPath<V>>
+impl<V, C, I, Q> DijkstraImpl<V, C, I, WithPath<V>, Q>
 where
     V: Clone + Ord,
     C: Clone + Ord + Add<Output = C>,
     I: Fn(&V) -> usize,
+    Q: Queue<C, V>,
 {
     /// `v`の直前の頂点を返す．
     pub fn previous(&self, v: &V) -> Option<&V> {
@@ -127,7 +194,7 @@ where
     }
 }
 
-impl<V, C, I, P> Debug for DijkstraImpl<V, C, I, P>
+impl<V, C, I, P, Q> Debug for DijkstraImpl<V, C, I, P, Q>
 where
     V: Debug,
     C: Debug,
@@ -141,10 +208,17 @@ where
 }
 
 /// ダイクストラ(経路復元なし)
-pub type Dijkstra<V, C, I> = DijkstraImpl<V, C, I, NoPath>;
+pub type Dijkstra<V, C, I> = DijkstraImpl<V, C, I, NoPath, BinaryHeap<(Reverse<C>, V)>>;
 
 /// ダイクストラ(経路復元あり)
-pub type DijkstraWithPath<V, C, I> = DijkstraImpl<V, C, I, WithPath<V>>;
+pub type DijkstraWithPath<V, C, I> =
+    DijkstraImpl<V, C, I, WithPath<V>, BinaryHeap<(Reverse<C>, V)>>;
+
+/// Radix Heapを用いるダイクストラ(経路復元なし)
+pub type RadixDijkstra<V, C, I> = DijkstraImpl<V, C, I, NoPath, RadixHeap<C, V>>;
+
+/// Radix Heapを用いるダイクストラ(経路復元あり)
+pub type RadixDijkstraWithPath<V, C, I> = DijkstraImpl<V, C, I, WithPath<V>, RadixHeap<C, V>>;
 
 #[cfg(test)]
 mod tests {
@@ -326,4 +400,53 @@ mod tests {
             assert_eq!(dijkstra.path(&4), Some(vec![3, 0, 1, 2, 4]));
         }
     }
+
+    #[test]
+    fn test_radix() {
+        use rand::Rng;
+
+        use crate::utils::test_utils::random::get_test_rng;
+
+        let mut rng = get_test_rng();
+
+        for _ in 0..100 {
+            let n = rng.random_range(1..30);
+            let graph = (0..n)
+                .map(|_| {
+                    (0..rng.random_range(0..5))
+                        .map(|_| (rng.random_range(0..n), rng.random_range(0..100u64)))
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+            let start = rng.random_range(0..n);
+
+            let expected = Dijkstra::new(n, &start, &0, |&v| v, |&v| graph[v].iter().cloned());
+            let actual = RadixDijkstra::new(n, &start, &0, |&v| v, |&v| graph[v].iter().cloned());
+            let with_path =
+                RadixDijkstraWithPath::new(n, &start, &0, |&v| v, |&v| graph[v].iter().cloned());
+
+            for v in 0..n {
+                assert_eq!(actual.cost(&v), expected.cost(&v));
+
+                if let Some(path) = with_path.path(&v) {
+                    assert_eq!(path.first(), Some(&start));
+                    assert_eq!(path.last(), Some(&v));
+                    let cost = path
+                        .windows(2)
+                        .map(|w| {
+                            graph[w[0]]
+                                .iter()
+                                .filter(|&&(u, _)| u == w[1])
+                                .map(|&(_, c)| c)
+                                .min()
+                                .unwrap()
+                        })
+                        .sum::<u64>();
+                    assert_eq!(Some(&cost), expected.cost(&v));
+                } else {
+                    assert_eq!(expected.cost(&v), None);
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,332 @@
+//! 作用付きDisjoint Set Union(Action Union-Find)
+//!
+//! 各要素にモノイドの値を持ち，以下の操作を行うことができるDSU．
+//! - 連結成分全体への作用の一括適用．
+//! - 連結成分全体の要素の総積の取得．
+//! - 各要素の値の取得．
+//!
+//! 作用は連結成分ごとの履歴として遅延して保持する．
+//! 各要素は連結成分に加わった時点の履歴の位置を記録しておき，
+//! それ以降の作用の合成を適用することで値を復元する．
+//! 履歴の区間合成はダブリングにより O(log N) で求める．
+//! 統合時には小さい方の連結成分の要素の値を確定させて大きい方に移す(small-to-large)．
+//!
+//! 連結成分の総積は統合の順序に依存するため，演算は可換であることが望ましい．
+//!
+//! # 計算量
+//! - `find`, `connected`, `size`, `fold`: O(1)
+//! - `act`: O(log Q) ただし Q は作用の回数
+//! - `get`: O(log Q)
+//! - `merge`: 償却 O(log N log Q)
+//!
+//! # 使用例
+//! ```
+//! use reprol::{
+//!     ds::action_dsu::ActionDsu,
+//!     ops::{act_add::ActAdd, op_max::OpMax},
+//! };
+//!
+//! let mut dsu = ActionDsu::<OpMax<i64>, ActAdd<i64>>::from(vec![1, 5, 3, 2]);
+//! dsu.merge(0, 1);
+//! dsu.act(0, &10); // 要素0, 1に10を加算
+//! assert_eq!(dsu.get(0), 11);
+//! assert_eq!(dsu.get(1), 15);
+//! assert_eq!(dsu.fold(1), &15);
+//!
+//! dsu.merge(1, 2);
+//! dsu.act(2, &-1); // 要素0, 1, 2に-1を加算
+//! assert_eq!(dsu.get(0), 10);
+//! assert_eq!(dsu.get(2), 2);
+//! assert_eq!(dsu.get(3), 2);
+//! assert_eq!(dsu.fold(0), &14);
+//! ```
+//!
+//! # 問題例
+//! - [ABC314 F](https://atcoder.jp/contests/abc314/tasks/abc314_f)
+
+use std::mem::{replace, swap, take};
+
+use crate::ops::{action::Action, monoid::Monoid};
+
+/// 作用付きDSU
+pub struct ActionDsu<O: Monoid, A: Action<O>> {
+    /// 各要素の代表元
+    leaders: Vec<usize>,
+
+    /// 各代表元の連結成分に属する要素のリスト
+    members: Vec<Vec<usize>>,
+
+    /// 各要素の値(`stamps`以降の作用を適用する前の値)
+    values: Vec<O::Element>,
+
+    /// 各要素が連結成分に加わった時点の作用の履歴の長さ
+    stamps: Vec<usize>,
+
+    /// 各代表元の作用の履歴
+    /// `histories[r][i][j]`: `i`番目の作用で終わる長さ`2^j`の作用の合成
+    histories: Vec<Vec<Vec<A::Element>>>,
+
+    /// 各代表元の連結成分の総積
+    folds: Vec<O::Element>,
+
+    count_components: usize,
+
+    /// 演算(モノイド)
+    op: O,
+
+    /// モノイドに対する作用
+    action: A,
+}
+
+impl<O: Monoid, A: Action<O>> ActionDsu<O, A>
+where
+    O::Element: Clone,
+    A::Element: Clone,
+{
+    /// 要素数`n`で初期化する．
+    /// 各要素の値は単位元で初期化される．
+    pub fn new(n: usize) -> Self
+    where
+        O: Default,
+        A: Default,
+    {
+        Self::with_op(n, O::default(), A::default())
+    }
+
+    /// モノイド`op`と作用`action`を指定して要素数`n`で初期化する．
+    pub fn with_op(n: usize, op: O, action: A) -> Self {
+        let values = (0..n).map(|_| op.id()).collect();
+        Self::from((values, op, action))
+    }
+
+    /// 要素`v`が属する集合の代表元を返す．
+    pub fn find(&self, v: usize) -> usize {
+        self.leaders[v]
+    }
+
+    /// 要素`u`と`v`が属する集合を統合する．
+    /// 統合が行われた場合は`true`を返す．
+    pub fn merge(&mut self, u: usize, v: usize) -> bool {
+        let mut u = self.find(u);
+        let mut v = self.find(v);
+
+        if u == v {
+            return false;
+        }
+
+        if self.members[u].len() < self.members[v].len() {
+            swap(&mut u, &mut v);
+        }
+
+        let stamp = self.histories[u].len();
+        for x in take(&mut self.members[v]) {
+            self.values[x] = self.get(x);
+            self.stamps[x] = stamp;
+            self.leaders[x] = u;
+            self.members[u].push(x);
+        }
+
+        self.histories[v] = vec![];
+        let fold_v = replace(&mut self.folds[v], self.op.id());
+        self.folds[u] = self.op.op(&self.folds[u], &fold_v);
+        self.count_components -= 1;
+
+        true
+    }
+
+    /// 要素`u`と`v`が同じ集合に属するかを判定する．
+    pub fn connected(&self, u: usize, v: usize) -> bool {
+        self.find(u) == self.find(v)
+    }
+
+    /// 要素`v`が属する集合の要素数を返す．
+    pub fn size(&self, v: usize) -> usize {
+        self.members[self.find(v)].len()
+    }
+
+    /// 連結成分の個数を返す．
+    pub fn count_components(&self) -> usize {
+        self.count_components
+    }
+
+    /// 要素`v`が属する集合の要素すべてに作用`f`を適用する．
+    pub fn act(&mut self, v: usize, f: &A::Element) {
+        let r = self.find(v);
+        self.folds[r] = self.action.act(f, &self.folds[r]);
+
+        let history = &mut self.histories[r];
+        let i = history.len();
+        let mut composed = vec![f.clone()];
+        for j in 1..=(i + 1).ilog2() as usize {
+            let older = &history[i - (1 << (j - 1))][j - 1];
+            composed.push(self.action.op(&composed[j - 1], older));
+        }
+        history.push(composed);
+    }
+
+    /// 要素`v`の値を返す．
+    pub fn get(&self, v: usize) -> O::Element {
+        let history = &self.histories[self.find(v)];
+        let stamp = self.stamps[v];
+
+        // 作用の履歴[stamp, len)の合成(新しい作用ほど左)
+        let mut f = self.action.id();
+        let mut i = history.len();
+        while i > stamp {
+            let j = (i - stamp).ilog2() as usize;
+            f = self.action.op(&f, &history[i - 1][j]);
+            i -= 1 << j;
+        }
+
+        self.action.act(&f, &self.values[v])
+    }
+
+    /// 要素`v`が属する集合の要素の総積を返す．
+    pub fn fold(&self, v: usize) -> &O::Element {
+        &self.folds[self.find(v)]
+    }
+}
+
+impl<O: Monoid, A: Action<O>> From<(Vec<O::Element>, O, A)> for ActionDsu<O, A>
+where
+    O::Element: Clone,
+{
+    fn from((values, op, action): (Vec<O::Element>, O, A)) -> Self {
+        let n = values.len();
+        assert!(n > 0);
+        Self {
+            leaders: (0..n).collect(),
+            members: (0..n).map(|v| vec![v]).collect(),
+            folds: values.clone(),
+            values,
+            stamps: vec![0; n],
+            histories: (0..n).map(|_| vec![]).collect(),
+            count_components: n,
+            op,
+            action,
+        }
+    }
+}
+
+impl<O: Monoid + Default, A: Action<O> + Default> From<Vec<O::Element>> for ActionDsu<O, A>
+where
+    O::Element: Clone,
+{
+    fn from(values: Vec<O::Element>) -> Self {
+        Self::from((values, O::default(), A::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::*;
+    use crate::{
+        ds::dsu::Dsu,
+        ops::{
+            act_add::ActAdd,
+            act_affine::{ActAffine, ActAffineElement},
+            act_set::ActSet,
+            op_min::OpMin,
+            op_range_sum::{OpRangeSum, OpRangeSumElement},
+        },
+        utils::test_utils::random::get_test_rng,
+    };
+
+    #[test]
+    fn test_min_add() {
+        let mut dsu = ActionDsu::<OpMin<i64>, ActAdd<i64>>::from(vec![3, 1, 4, 1, 5]);
+        assert!(dsu.merge(0, 2));
+        assert!(!dsu.merge(2, 0));
+        assert_eq!(dsu.fold(0), &3);
+        dsu.act(2, &10);
+        assert_eq!(dsu.get(0), 13);
+        assert_eq!(dsu.get(1), 1);
+        assert_eq!(dsu.get(2), 14);
+
+        assert!(dsu.merge(1, 2));
+        assert_eq!(dsu.fold(0), &1);
+        assert_eq!(dsu.size(1), 3);
+        assert_eq!(dsu.count_components(), 3);
+        dsu.act(1, &-2);
+        assert_eq!(dsu.get(0), 11);
+        assert_eq!(dsu.get(1), -1);
+        assert_eq!(dsu.get(2), 12);
+        assert_eq!(dsu.fold(2), &-1);
+        assert_eq!(dsu.get(3), 1);
+    }
+
+    #[test]
+    fn test_set() {
+        let mut dsu = ActionDsu::<OpMin<i64>, ActSet<i64>>::from(vec![3, 1, 4]);
+        dsu.merge(0, 1);
+        dsu.act(0, &Some(7));
+        assert_eq!(dsu.get(0), 7);
+        assert_eq!(dsu.get(1), 7);
+        assert_eq!(dsu.fold(1), &7);
+        dsu.merge(2, 1);
+        assert_eq!(dsu.fold(1), &4);
+        assert_eq!(dsu.get(2), 4);
+    }
+
+    #[test]
+    fn test_affine_randomized() {
+        type Op = OpRangeSum<i64>;
+        type Act = ActAffine<i64>;
+
+        let mut rng = get_test_rng();
+
+        const T: usize = 50;
+        const N_MAX: usize = 30;
+        const Q: usize = 1000;
+
+        for _ in 0..T {
+            let n = rng.random_range(1..=N_MAX);
+            let mut naive = (0..n)
+                .map(|_| rng.random_range(-100..=100))
+                .collect::<Vec<i64>>();
+            let mut naive_dsu = Dsu::new(n);
+            let mut dsu = ActionDsu::<Op, Act>::from(
+                naive
+                    .iter()
+                    .map(|&x| OpRangeSumElement::leaf(x))
+                    .collect::<Vec<_>>(),
+            );
+
+            for _ in 0..Q {
+                let u = rng.random_range(0..n);
+                match rng.random_range(0..4) {
+                    0 => {
+                        let v = rng.random_range(0..n);
+                        assert_eq!(dsu.merge(u, v), !naive_dsu.connected(u, v));
+                        naive_dsu.merge(u, v);
+                    }
+                    1 => {
+                        let f = ActAffineElement {
+                            a: rng.random_range(-3..=3),
+                            b: rng.random_range(-100..=100),
+                        };
+                        dsu.act(u, &f);
+                        for (v, x) in naive.iter_mut().enumerate() {
+                            if naive_dsu.connected(u, v) {
+                                *x = x.wrapping_mul(f.a).wrapping_add(f.b);
+                            }
+                        }
+                    }
+                    2 => {
+                        assert_eq!(dsu.get(u).value(), naive[u]);
+                    }
+                    _ => {
+                        let expected = (0..n)
+                            .filter(|&v| naive_dsu.connected(u, v))
+                            .fold(0i64, |acc, v| acc.wrapping_add(naive[v]));
+                        assert_eq!(dsu.fold(u).value(), expected);
+                        assert_eq!(dsu.fold(u).len(), naive_dsu.size(u) as i64);
+                        assert_eq!(dsu.size(u), naive_dsu.size(u));
+                    }
+                }
+            }
+        }
+    }
+}
@@ -8,19 +8,19 @@ use std::{
     ptr::NonNull,
 };
 
-struct Node<T> {
-    value: T,
-    len: usize,
-    height: i32,
-    left: Link<T>,
-    right: Link<T>,
+pub(crate) struct Node<T> {
+    pub(crate) value: T,
+    pub(crate) len: usize,
+    pub(crate) height: i32,
+    pub(crate) left: Link<T>,
+    pub(crate) right: Link<T>,
 }
 
-type NodePtr<T> = NonNull<Node<T>>;
-type Link<T> = Option<NodePtr<T>>;
+pub(crate) type NodePtr<T> = NonNull<Node<T>>;
+pub(crate) type Link<T> = Option<NodePtr<T>>;
 
 impl<T> Node<T> {
-    fn new(value: T) -> NodePtr<T> {
+    pub(crate) fn new(value: T) -> NodePtr<T> {
         let node = Self {
             value,
             len: 1,
@@ -30,21 +30,44 @@ impl<T> Node<T> {
         };
         NonNull::from(Box::leak(Box::new(node)))
     }
+}
 
+/// ノードに付随する情報(部分木の総積や遅延評価など)を管理するフック．
+///
+/// 平衡化・分割・併合の際に，子を参照・付け替えるノードについて呼び出される．
+pub(crate) trait Augment<T> {
+    /// ノードに遅延している情報を子に伝播する．
+    /// 子を参照・付け替える前に呼び出される．
     #[inline]
-    fn fetch(&mut self) {
-        self.len = len(self.left) + len(self.right) + 1;
-        self.height = height(self.left).max(height(self.right)) + 1;
-    }
+    fn push(&self, _node: NodePtr<T>) {}
+
+    /// 子の情報からノードに付随する情報を更新する．
+    /// `len`と`height`の更新後に呼び出される．
+    #[inline]
+    fn update(&self, _node: NodePtr<T>) {}
+}
+
+/// 付随する情報を持たないAVL木
+pub(crate) struct Plain;
+
+impl<T> Augment<T> for Plain {}
+
+/// 子の情報からノードの情報を更新する．
+#[inline]
+pub(crate) fn fetch<T>(mut node: NodePtr<T>, aug: &impl Augment<T>) {
+    let raw = unsafe { node.as_mut() };
+    raw.len = len(raw.left) + len(raw.right) + 1;
+    raw.height = height(raw.left).max(height(raw.right)) + 1;
+    aug.update(node);
 }
 
 #[inline]
-fn free<T>(node: NodePtr<T>) {
+pub(crate) fn free<T>(node: NodePtr<T>) {
     unsafe { drop(Box::from_raw(node.as_ptr())) };
 }
 
 #[inline]
-fn len<T>(node: Link<T>) -> usize {
+pub(crate) fn len<T>(node: Link<T>) -> usize {
     node.map_or(0, |node| unsafe { node.as_ref() }.len)
 }
 
@@ -65,39 +88,42 @@ fn diff_height<T>(node: Link<T>) -> i32 {
 }
 
 /// 木を平衡して新たなrootを返す
-fn balance<T>(mut root: Link<T>) -> Link<T> {
+/// (rootへの伝播が済んでいる場合のみ呼び出す)
+fn balance<T>(mut root: Link<T>, aug: &impl Augment<T>) -> Link<T> {
     /// rootを根とした部分木を右回転させる
-    /// (左の子が存在している場合のみ呼び出す)
-    fn rotate_right<T>(root: &mut Link<T>) {
+    /// (rootへの伝播が済んでいて，左の子が存在している場合のみ呼び出す)
+    fn rotate_right<T>(root: &mut Link<T>, aug: &impl Augment<T>) {
         *root = {
             let mut root = root.unwrap();
             let raw_root = unsafe { root.as_mut() };
 
             let mut left = raw_root.left.unwrap();
+            aug.push(left);
             let raw_left = unsafe { left.as_mut() };
 
             raw_root.left = raw_left.right;
-            raw_root.fetch();
+            fetch(root, aug);
             raw_left.right = Some(root);
-            raw_left.fetch();
+            fetch(left, aug);
             Some(left)
         };
     }
 
     /// rootを根とした部分木を左回転させる
-    /// (右の子が存在する場合のみ呼び出す)
-    fn rotate_left<T>(root: &mut Link<T>) {
+    /// (rootへの伝播が済んでいて，右の子が存在する場合のみ呼び出す)
+    fn rotate_left<T>(root: &mut Link<T>, aug: &impl Augment<T>) {
         *root = {
             let mut root = root.unwrap();
             let raw_root = unsafe { root.as_mut() };
 
             let mut right = raw_root.right.unwrap();
+            aug.push(right);
             let raw_right = unsafe { right.as_mut() };
 
             raw_root.right = raw_right.left;
-            raw_root.fetch();
+            fetch(root, aug);
             raw_right.left = Some(root);
-            raw_right.fetch();
+            fetch(right, aug);
             Some(right)
         };
     }
@@ -114,55 +140,66 @@ fn balance<T>(mut root: Link<T>) -> Link<T> {
         // 左部分木が高い場合
 
         let left = &mut raw_root.left;
+        aug.push(left.unwrap());
         if diff_height(*left) < 0 {
-            rotate_left(left);
+            rotate_left(left, aug);
         }
 
-        rotate_right(&mut root);
+        rotate_right(&mut root, aug);
     } else if d < -1 {
         // 右部分木が高い場合
 
         let right = &mut raw_root.right;
+        aug.push(right.unwrap());
         if diff_height(*right) > 0 {
-            rotate_right(right);
+            rotate_right(right, aug);
         }
 
-        rotate_left(&mut root);
+        rotate_left(&mut root, aug);
     } else {
-        raw_root.fetch();
+        fetch(root.unwrap(), aug);
     }
 
     root
 }
 
-fn merge_with_root<T>(left: Link<T>, root: Link<T>, right: Link<T>) -> Link<T> {
+/// (rootは伝播が済んでいるノードで，その子は上書きされる)
+pub(crate) fn merge_with_root<T>(
+    left: Link<T>,
+    root: Link<T>,
+    right: Link<T>,
+    aug: &impl Augment<T>,
+) -> Link<T> {
     let d = height(left) - height(right);
 
     if d > 1 {
+        aug.push(left.unwrap());
         let raw_left = unsafe { left.unwrap().as_mut() };
-        raw_left.right = merge_with_root(raw_left.right, root, right);
-        balance(left)
+        raw_left.right = merge_with_root(raw_left.right, root, right, aug);
+        balance(left, aug)
     } else if d < -1 {
+        aug.push(right.unwrap());
         let raw_right = unsafe { right.unwrap().as_mut() };
-        raw_right.left = merge_with_root(left, root, raw_right.left);
-        balance(right)
+        raw_right.left = merge_with_root(left, root, raw_right.left, aug);
+        balance(right, aug)
     } else {
         let raw_root = unsafe { root.unwrap().as_mut() };
         raw_root.left = left;
         raw_root.right = right;
-        balance(root)
+        balance(root, aug)
     }
 }
 
 /// 2つの木をマージして新たなrootを返す
-fn merge<T>(left: Link<T>, right: Link<T>) -> Link<T> {
+pub(crate) fn merge<T>(left: Link<T>, right: Link<T>, aug: &impl Augment<T>) -> Link<T> {
     /// nodeの部分木のうち最も右のノードを削除して新たなrootと削除されたノードを返す
-    fn remove_max<T>(mut node: Link<T>) -> (Link<T>, Link<T>) {
+    fn remove_max<T>(mut node: Link<T>, aug: &impl Augment<T>) -> (Link<T>, Link<T>) {
+        aug.push(node.unwrap());
         let raw_node = unsafe { node.unwrap().as_mut() };
         if raw_node.right.is_some() {
-            let (tmp, removed) = remove_max(raw_node.right);
+            let (tmp, removed) = remove_max(raw_node.right, aug);
             raw_node.right = tmp;
-            node = balance(node);
+            node = balance(node, aug);
             (node, removed)
         } else {
             let removed = node;
@@ -176,17 +213,19 @@ fn merge<T>(left: Link<T>, right: Link<T>) -> Link<T> {
     } else if right.is_none() {
         left
     } else {
-        let (left, removed) = remove_max(left);
-        merge_with_root(left, removed, right)
+        let (left, removed) = remove_max(left, aug);
+        merge_with_root(left, removed, right, aug)
     }
 }
 
 /// [0, index)の部分木と[index, n)の部分木に分割する
-fn split<T>(root: Link<T>, index: usize) -> (Link<T>, Link<T>) {
+pub(crate) fn split<T>(root: Link<T>, index: usize, aug: &impl Augment<T>) -> (Link<T>, Link<T>) {
     if root.is_none() {
         return (None, None);
     }
 
+    aug.push(root.unwrap());
+
     let (left, right) = {
         let raw_root = unsafe { root.unwrap().as_mut() };
         let left = raw_root.left;
@@ -200,20 +239,21 @@ fn split<T>(root: Link<T>, index: usize) -> (Link<T>, Link<T>) {
 
     match index.cmp(&left_len) {
         Ordering::Less => {
-            let tmp = split(left, index);
-            (tmp.0, merge_with_root(tmp.1, root, right))
+            let tmp = split(left, index, aug);
+            (tmp.0, merge_with_root(tmp.1, root, right, aug))
         }
         Ordering::Greater => {
-            let tmp = split(right, index - left_len - 1);
-            (merge_with_root(left, root, tmp.0), tmp.1)
+            let tmp = split(right, index - left_len - 1, aug);
+            (merge_with_root(left, root, tmp.0, aug), tmp.1)
         }
-        Ordering::Equal => (left, merge_with_root(None, root, right)),
+        Ordering::Equal => (left, merge_with_root(None, root, right, aug)),
     }
 }
 
 /// index番目のノードを取得する
-fn get<T>(root: Link<T>, index: usize) -> Link<T> {
-    let raw_root = unsafe { root?.as_mut() };
+/// (遅延している情報を持たない木に対してのみ呼び出す)
+pub(crate) fn get<T>(root: Link<T>, index: usize) -> Link<T> {
+    let raw_root = unsafe { root?.as_ref() };
     let left = raw_root.left;
     let right = raw_root.right;
     let left_len = len(left);
@@ -226,7 +266,8 @@ fn get<T>(root: Link<T>, index: usize) -> Link<T> {
 
 /// はじめてfがfalseとなるindexを返す
 /// すべての要素がtrueの場合はnを返す
-fn bisect<T>(root: Link<T>, mut f: impl FnMut(&T) -> bool) -> usize {
+/// (遅延している情報を持たない木に対してのみ呼び出す)
+pub(crate) fn bisect<T>(root: Link<T>, mut f: impl FnMut(&T) -> bool) -> usize {
     root.map(|node| unsafe { node.as_ref() }).map_or(0, |node| {
         let left = node.left;
         let right = node.right;
@@ -240,7 +281,7 @@ fn bisect<T>(root: Link<T>, mut f: impl FnMut(&T) -> bool) -> usize {
 }
 
 #[allow(unused)]
-fn traverse<T>(
+pub(crate) fn traverse<T>(
     node: Link<T>,
     mut preorder_f: impl FnMut(NodePtr<T>),
     mut inorder_f: impl FnMut(NodePtr<T>),
@@ -268,19 +309,19 @@ fn traverse<T>(
 
 #[allow(unused)]
 #[inline]
-fn traverse_preorder<T>(node: Link<T>, f: impl FnMut(NodePtr<T>)) {
+pub(crate) fn traverse_preorder<T>(node: Link<T>, f: impl FnMut(NodePtr<T>)) {
     traverse(node, f, |_| {}, |_| {});
 }
 
 #[allow(unused)]
 #[inline]
-fn traverse_inorder<T>(node: Link<T>, f: impl FnMut(NodePtr<T>)) {
+pub(crate) fn traverse_inorder<T>(node: Link<T>, f: impl FnMut(NodePtr<T>)) {
     traverse(node, |_| {}, f, |_| {});
 }
 
 #[allow(unused)]
 #[inline]
-fn traverse_postorder<T>(node: Link<T>, f: impl FnMut(NodePtr<T>)) {
+pub(crate) fn traverse_postorder<T>(node: Link<T>, f: impl FnMut(NodePtr<T>)) {
     traverse(node, |_| {}, |_| {}, f);
 }
 
@@ -354,26 +395,26 @@ impl<T> AvlTreeVec<T> {
     pub fn insert(&mut self, index: usize, value: T) {
         assert!(index <= self.len());
         let new_node = Some(Node::new(value));
-        let (left, right) = split(self.root.take(), index);
-        self.root = merge_with_root(left, new_node, right);
+        let (left, right) = split(self.root.take(), index, &Plain);
+        self.root = merge_with_root(left, new_node, right, &Plain);
     }
 
     pub fn remove(&mut self, index: usize) -> Option<T> {
         (index < self.len()).then(|| {
-            let (left, right) = split(self.root.take(), index);
-            let (removed, right) = split(right, 1);
-            self.root = merge(left, right);
+            let (left, right) = split(self.root.take(), index, &Plain);
+            let (removed, right) = split(right, 1, &Plain);
+            self.root = merge(left, right, &Plain);
             unsafe { Box::from_raw(removed.unwrap().as_ptr()) }.value
         })
     }
 
     pub fn append(&mut self, other: &mut Self) {
-        self.root = merge(self.root.take(), other.root.take())
+        self.root = merge(self.root.take(), other.root.take(), &Plain)
     }
 
     pub fn split_off(&mut self, index: usize) -> Self {
         assert!(index <= self.len());
-        let (left, right) = split(self.root.take(), index);
+        let (left, right) = split(self.root.take(), index, &Plain);
         self.root = left;
         Self { root: right }
     }
@@ -593,7 +634,7 @@ impl<'a, T> IterBase<'a, T> {
 pub struct Iter<'a, T>(IterBase<'a, T>);
 
 impl<'a, T: 'a> Iter<'a, T> {
-    fn new(root: Link<T>) -> Self {
+    pub(crate) fn new(root: Link<T>) -> Self {
         Self(IterBase::new(root))
     }
 }
@@ -0,0 +1,483 @@
+//! Binary Trie
+//!
+//! `BITS`ビットの非負整数の多重集合を，上位ビットから順に辿るトライ木で管理するデータ構造．
+//! 以下の操作をいずれも O(BITS) で処理できる．
+//! - 要素の挿入・削除．
+//! - 全要素へのxorの一括適用(遅延して保持する)．
+//! - `x`とのxorの最小値・最大値の取得．
+//! - k番目に小さい要素の取得．
+//! - `x`未満の要素の個数の取得．
+//!
+//! [`BinaryTrie::persistent`]で構築すると，挿入・削除のたびに経路をコピーする永続トライとなり，
+//! [`BinaryTrie::version`]で取得した過去の状態に[`BinaryTrie::restore`]で戻すことができる．
+//!
+//! # 使用例
+//! ```
+//! use reprol::ds::binary_trie::BinaryTrie;
+//!
+//! let mut trie = BinaryTrie::<30>::new();
+//! trie.insert(5);
+//! trie.insert(2);
+//! trie.insert(2);
+//! trie.insert(9);
+//! assert_eq!(trie.count(2), 2);
+//! assert_eq!(trie.min_xor(7), Some(2)); // 5 ^ 7 = 2
+//! assert_eq!(trie.max_xor(7), Some(14)); // 9 ^ 7 = 14
+//! assert_eq!(trie.kth_smallest(2), Some(5));
+//! assert_eq!(trie.count_less(6), 3);
+//!
+//! // 全要素にxor 3を適用: {6, 1, 1, 10}
+//! trie.xor_all(3);
+//! assert_eq!(trie.kth_smallest(0), Some(1));
+//! assert_eq!(trie.kth_smallest(2), Some(6));
+//! assert!(trie.erase(6));
+//! assert!(!trie.erase(5));
+//! assert_eq!(trie.len(), 3);
+//! ```
+//!
+//! ## 永続
+//! ```
+//! use reprol::ds::binary_trie::BinaryTrie;
+//!
+//! let mut trie = BinaryTrie::<30>::persistent();
+//! trie.insert(3);
+//! let v = trie.version();
+//! trie.insert(1);
+//! trie.erase(3);
+//! assert_eq!(trie.kth_smallest(0), Some(1));
+//!
+//! trie.restore(v);
+//! assert_eq!(trie.kth_smallest(0), Some(3));
+//! assert_eq!(trie.len(), 1);
+//! ```
+//!
+//! # 問題例
+//! - [Set Xor-Min](https://judge.yosupo.jp/problem/set_xor_min)
+//! - [ABC281 F](https://atcoder.jp/contests/abc281/tasks/abc281_f)
+
+/// 空のノード(子を持たず，個数0)
+const EMPTY: usize = 0;
+
+#[derive(Clone, Copy)]
+struct Node {
+    children: [usize; 2],
+
+    /// 部分木に含まれる要素の個数
+    count: usize,
+}
+
+/// `BITS`ビットの非負整数の多重集合を管理するBinary Trie
+#[derive(Clone)]
+pub struct BinaryTrie<const BITS: usize> {
+    /// `nodes[EMPTY]`は空のノード
+    nodes: Vec<Node>,
+
+    root: usize,
+
+    /// 全要素に適用するxor
+    mask: u64,
+
+    persistent: bool,
+}
+
+impl<const BITS: usize> BinaryTrie<BITS> {
+    /// 空のトライを生成する．
+    pub fn new() -> Self {
+        Self::build(false)
+    }
+
+    /// 挿入・削除のたびに経路をコピーする空の永続トライを生成する．
+    pub fn persistent() -> Self {
+        Self::build(true)
+    }
+
+    fn build(persistent: bool) -> Self {
+        assert!(BITS <= 64);
+        let empty = Node {
+            children: [EMPTY; 2],
+            count: 0,
+        };
+        Self {
+            nodes: vec![empty],
+            root: EMPTY,
+            mask: 0,
+            persistent,
+        }
+    }
+
+    /// 要素数を返す．
+    /// 重複する要素はその個数だけ数える．
+    pub fn len(&self) -> usize {
+        self.nodes[self.root].count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 現在の状態を表すバージョンを返す．
+    ///
+    /// # Panics
+    ///
+    /// 永続トライでない場合はパニックする．
+    pub fn version(&self) -> usize {
+        assert!(self.persistent, "BinaryTrie is not persistent");
+        self.root
+    }
+
+    /// バージョン`version`の状態に戻す．
+    /// 全要素に適用するxorは変化しない．
+    ///
+    /// # Panics
+    ///
+    /// 永続トライでない場合はパニックする．
+    pub fn restore(&mut self, version: usize) {
+        assert!(self.persistent, "BinaryTrie is not persistent");
+        assert!(version < self.nodes.len());
+        self.root = version;
+    }
+
+    /// `x`を1個挿入する．
+    pub fn insert(&mut self, x: u64) {
+        self.add(x, true);
+    }
+
+    /// `x`を1個削除する．
+    /// `x`が含まれていない場合は`false`を返す．
+    pub fn erase(&mut self, x: u64) -> bool {
+        let found = self.count(x) > 0;
+        if found {
+            self.add(x, false);
+        }
+        found
+    }
+
+    /// `x`の個数を返す．
+    pub fn count(&self, x: u64) -> usize {
+        Self::check(x);
+        let x = x ^ self.mask;
+        let mut v = self.root;
+        for b in (0..BITS).rev() {
+            v = self.nodes[v].children[(x >> b & 1) as usize];
+        }
+        self.nodes[v].count
+    }
+
+    /// `x`が1個以上含まれているかを判定する．
+    pub fn contains(&self, x: u64) -> bool {
+        self.count(x) > 0
+    }
+
+    /// 全要素に`x`をxorする．
+    pub fn xor_all(&mut self, x: u64) {
+        Self::check(x);
+        self.mask ^= x;
+    }
+
+    /// 要素`v`についての`v ^ x`の最小値を返す．
+    pub fn min_xor(&self, x: u64) -> Option<u64> {
+        Self::check(x);
+        self.greedy_xor(x ^ self.mask, 0).map(|y| y ^ self.mask ^ x)
+    }
+
+    /// 要素`v`についての`v ^ x`の最大値を返す．
+    pub fn max_xor(&self, x: u64) -> Option<u64> {
+        Self::check(x);
+        self.greedy_xor(x ^ self.mask, 1).map(|y| y ^ self.mask ^ x)
+    }
+
+    /// 最小の要素を返す．
+    pub fn min(&self) -> Option<u64> {
+        self.min_xor(0)
+    }
+
+    /// 最大の要素を返す．
+    pub fn max(&self) -> Option<u64> {
+        self.max_xor(0)
+    }
+
+    /// 小さい方から`k`番目(0-indexed)の要素を返す．
+    pub fn kth_smallest(&self, mut k: usize) -> Option<u64> {
+        if k >= self.len() {
+            return None;
+        }
+        let mut v = self.root;
+        let mut res = 0;
+        for b in (0..BITS).rev() {
+            let zero = (self.mask >> b & 1) as usize;
+            let child = self.nodes[v].children[zero];
+            if k < self.nodes[child].count {
+                v = child;
+            } else {
+                k -= self.nodes[child].count;
+                res |= 1 << b;
+                v = self.nodes[v].children[zero ^ 1];
+            }
+        }
+        Some(res)
+    }
+
+    /// `x`未満の要素の個数を返す．
+    pub fn count_less(&self, x: u64) -> usize {
+        Self::check(x);
+        let mut v = self.root;
+        let mut res = 0;
+        for b in (0..BITS).rev() {
+            let zero = (self.mask >> b & 1) as usize;
+            if x >> b & 1 == 1 {
+                res += self.nodes[self.nodes[v].children[zero]].count;
+                v = self.nodes[v].children[zero ^ 1];
+            } else {
+                v = self.nodes[v].children[zero];
+            }
+            if v == EMPTY {
+                break;
+            }
+        }
+        res
+    }
+
+    /// 要素の昇順に(値, 個数)を返すイテレータを返す．
+    pub fn iter(&self) -> impl Iterator<Item = (u64, usize)> + '_ {
+        let mut stack = vec![(self.root, BITS, 0)];
+        std::iter::from_fn(move || {
+            while let Some((v, depth, value)) = stack.pop() {
+                if self.nodes[v].count == 0 {
+                    continue;
+                }
+                if depth == 0 {
+                    return Some((value, self.nodes[v].count));
+                }
+                let b = depth - 1;
+                let zero = (self.mask >> b & 1) as usize;
+                stack.push((self.nodes[v].children[zero ^ 1], b, value | 1 << b));
+                stack.push((self.nodes[v].children[zero], b, value));
+            }
+            None
+        })
+    }
+
+    /// 格納されている値(xorを適用する前)`y`について，
+    /// `y ^ target`の各ビットを上位から`prefer`に近づけるように辿り，見つけた`y`を返す．
+    fn greedy_xor(&self, target: u64, prefer: u64) -> Option<u64> {
+        if self.is_empty() {
+            return None;
+        }
+        let mut v = self.root;
+        let mut res = 0;
+        for b in (0..BITS).rev() {
+            let mut c = ((target >> b & 1) ^ prefer) as usize;
+            if self.nodes[self.nodes[v].children[c]].count == 0 {
+                c ^= 1;
+            }
+            res |= (c as u64) << b;
+            v = self.nodes[v].children[c];
+        }
+        Some(res)
+    }
+
+    /// `x`の個数を1増やす(`insert = true`)または1減らす．
+    fn add(&mut self, x: u64, insert: bool) {
+        Self::check(x);
+        let x = x ^ self.mask;
+
+        let root = self.copy(self.root);
+        self.root = root;
+
+        let mut v = root;
+        for b in (0..BITS).rev() {
+            self.update_count(v, insert);
+            let c = (x >> b & 1) as usize;
+            let child = self.copy(self.nodes[v].children[c]);
+            self.nodes[v].children[c] = child;
+            v = child;
+        }
+        self.update_count(v, insert);
+    }
+
+    fn update_count(&mut self, v: usize, insert: bool) {
+        if insert {
+            self.nodes[v].count += 1;
+        } else {
+            self.nodes[v].count -= 1;
+        }
+    }
+
+    /// 書き換えてよいノードを返す．
+    /// 永続トライの場合や`v`が空のノードの場合は，`v`をコピーした新しいノードを返す．
+    fn copy(&mut self, v: usize) -> usize {
+        if self.persistent || v == EMPTY {
+            self.nodes.push(self.nodes[v]);
+            self.nodes.len() - 1
+        } else {
+            v
+        }
+    }
+
+    #[inline]
+    fn check(x: u64) {
+        assert!(BITS == 64 || x >> BITS == 0, "x must be less than 2^BITS");
+    }
+}
+
+impl<const BITS: usize> Default for BinaryTrie<BITS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const BITS: usize> Extend<u64> for BinaryTrie<BITS> {
+    fn extend<I: IntoIterator<Item = u64>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|x| self.insert(x));
+    }
+}
+
+impl<const BITS: usize> FromIterator<u64> for BinaryTrie<BITS> {
+    fn from_iter<I: IntoIterator<Item = u64>>(iter: I) -> Self {
+        let mut res = Self::new();
+        res.extend(iter);
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::*;
+    use crate::utils::test_utils::random::get_test_rng;
+
+    #[test]
+    fn test() {
+        let mut trie = BinaryTrie::<3>::from_iter([1, 6, 6, 3]);
+        assert!(trie.iter().eq([(1, 1), (3, 1), (6, 2)]));
+        assert_eq!(trie.min(), Some(1));
+        assert_eq!(trie.max(), Some(6));
+        assert_eq!(trie.count_less(7), 4);
+        assert_eq!(trie.count_less(0), 0);
+        trie.xor_all(5);
+        assert!(trie.iter().eq([(3, 2), (4, 1), (6, 1)]));
+        assert!(trie.contains(3));
+        assert!(!trie.contains(1));
+        assert_eq!(trie.kth_smallest(4), None);
+
+        trie.erase(3);
+        trie.erase(3);
+        trie.erase(4);
+        trie.erase(6);
+        assert!(trie.is_empty());
+        assert_eq!(trie.min_xor(3), None);
+        assert_eq!(trie.max(), None);
+    }
+
+    #[test]
+    fn test_full_bits() {
+        let mut trie = BinaryTrie::<64>::new();
+        trie.insert(u64::MAX);
+        trie.insert(0);
+        assert_eq!(trie.max_xor(1), Some(u64::MAX - 1));
+        assert_eq!(trie.min_xor(u64::MAX - 1), Some(1));
+        assert_eq!(trie.count_less(u64::MAX), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_out_of_range() {
+        let mut trie = BinaryTrie::<3>::new();
+        trie.insert(8);
+    }
+
+    #[test]
+    fn test_randomized() {
+        let mut rng = get_test_rng();
+
+        const T: usize = 50;
+        const Q: usize = 1000;
+        const BITS: usize = 5;
+
+        for _ in 0..T {
+            let mut trie = BinaryTrie::<BITS>::new();
+            let mut naive: Vec<u64> = vec![];
+
+            for _ in 0..Q {
+                let x = rng.random_range(0..1 << BITS);
+                match rng.random_range(0..6) {
+                    0 => {
+                        trie.insert(x);
+                        naive.push(x);
+                    }
+                    1 => {
+                        let i = naive.iter().position(|&y| y == x);
+                        assert_eq!(trie.erase(x), i.is_some());
+                        if let Some(i) = i {
+                            naive.swap_remove(i);
+                        }
+                    }
+                    2 => {
+                        trie.xor_all(x);
+                        naive.iter_mut().for_each(|y| *y ^= x);
+                    }
+                    3 => {
+                        assert_eq!(trie.min_xor(x), naive.iter().map(|&y| y ^ x).min());
+                        assert_eq!(trie.max_xor(x), naive.iter().map(|&y| y ^ x).max());
+                    }
+                    4 => {
+                        let mut sorted = naive.clone();
+                        sorted.sort_unstable();
+                        let k = rng.random_range(0..=naive.len());
+                        assert_eq!(trie.kth_smallest(k), sorted.get(k).copied());
+                    }
+                    _ => {
+                        assert_eq!(trie.count_less(x), naive.iter().filter(|&&y| y < x).count());
+                        assert_eq!(trie.count(x), naive.iter().filter(|&&y| y == x).count());
+                    }
+                }
+                assert_eq!(trie.len(), naive.len());
+            }
+        }
+    }
+
+    #[test]
+    fn test_persistent_randomized() {
+        let mut rng = get_test_rng();
+
+        const T: usize = 50;
+        const Q: usize = 500;
+        const BITS: usize = 5;
+
+        for _ in 0..T {
+            let mut trie = BinaryTrie::<BITS>::persistent();
+            let mut versions = vec![(trie.version(), vec![])];
+            let mut naive: Vec<u64> = vec![];
+
+            for _ in 0..Q {
+                let x = rng.random_range(0..1 << BITS);
+                match rng.random_range(0..4) {
+                    0 => {
+                        trie.insert(x);
+                        naive.push(x);
+                    }
+                    1 => {
+                        if let Some(i) = naive.iter().position(|&y| y == x) {
+                            naive.swap_remove(i);
+                            assert!(trie.erase(x));
+                        }
+                    }
+                    2 => {
+                        versions.push((trie.version(), naive.clone()));
+                    }
+                    _ => {
+                        let (v, s) = &versions[rng.random_range(0..versions.len())];
+                        trie.restore(*v);
+                        naive = s.clone();
+                    }
+                }
+                let mut sorted = naive.clone();
+                sorted.sort_unstable();
+                assert_eq!(trie.len(), sorted.len());
+                assert_eq!(trie.min_xor(x), naive.iter().map(|&y| y ^ x).min());
+                assert_eq!(trie.count_less(x), sorted.partition_point(|&y| y < x));
+            }
+        }
+    }
+}
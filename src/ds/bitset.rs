@@ -1,11 +1,14 @@
 //! BitSet
 //!
 //! 固定長のビット集合を`u64`配列で管理するデータ構造．
-//! 単一ビットの取得・設定・リセットと，辞書順(数値的)比較を提供する．
+//! 単一ビットの取得・設定・リセットと，辞書順(数値的)比較に加えて，
+//! ビット演算(`&`, `|`, `^`, `!`)，シフト(`<<`, `>>`)，立っているビットの個数や位置の取得を提供する．
+//!
+//! ビット長が実行時にしか分からない場合は，ヒープ上に確保する[`DynBitSet`]を用いる．
 //!
 //! # 使用例
 //! ```
-//! use reprol::{bitset, ds::bitset::BitSet};
+//! use reprol::{bitset, ds::bitset::{BitSet, DynBitSet}};
 //!
 //! let mut bs = bitset!(130);
 //! bs.set(5);
@@ -16,9 +19,33 @@
 //!
 //! let high: BitSet<3> = BitSet::new();
 //! assert_eq!(high, BitSet::<3>::ZERO);
+//!
+//! // 部分和DP
+//! let mut dp = BitSet::<2>::new();
+//! dp.set(0);
+//! for w in [3, 5, 7] {
+//!     dp |= &dp << w;
+//! }
+//! assert_eq!(dp.iter().collect::<Vec<_>>(), vec![0, 3, 5, 7, 8, 10, 12, 15]);
+//! assert_eq!(dp.count_ones(), 8);
+//! assert_eq!(dp.find_next(8), Some(10));
+//!
+//! let mut dp = DynBitSet::new(16);
+//! dp.set(0);
+//! for w in [3, 5, 7] {
+//!     dp |= &dp << w;
+//! }
+//! assert_eq!(dp.iter().collect::<Vec<_>>(), vec![0, 3, 5, 7, 8, 10, 12, 15]);
 //! ```
 
-use std::{cmp::Ordering, fmt::Debug};
+use std::{
+    cmp::Ordering,
+    fmt::Debug,
+    ops::{
+        BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not, Shl, ShlAssign, Shr,
+        ShrAssign,
+    },
+};
 
 /// 固定長のビット集合を`u64`の配列で保持するデータ構造．
 #[derive(Clone, PartialEq, Eq, Hash)]
@@ -71,8 +98,299 @@ impl<const WORDS: usize> BitSet<WORDS> {
         assert!(w < WORDS);
         self.bit[w] &= !m;
     }
+
+    /// 立っているビットの個数を返す．
+    pub fn count_ones(&self) -> usize {
+        count_ones(&self.bit)
+    }
+
+    /// 立っているビットが存在するかを返す．
+    pub fn any(&self) -> bool {
+        self.bit.iter().any(|&w| w != 0)
+    }
+
+    /// すべてのビットが0かを返す．
+    pub fn none(&self) -> bool {
+        !self.any()
+    }
+
+    /// 立っているビットのうち，最小の位置を返す．
+    pub fn find_first(&self) -> Option<usize> {
+        find_from(&self.bit, 0)
+    }
+
+    /// 立っているビットのうち，`i`より大きい最小の位置を返す．
+    pub fn find_next(&self, i: usize) -> Option<usize> {
+        find_from(&self.bit, i.checked_add(1)?)
+    }
+
+    /// 立っているビットの位置を昇順に返すイテレータを返す．
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        std::iter::successors(self.find_first(), |&i| self.find_next(i))
+    }
+
+    // 以下は演算子を実装するマクロ(`impl_bit_op`など)が`DynBitSet`と共通で呼び出す内部用の補助関数．
+    // 固定長では範囲外のビットが立つことも長さが食い違うこともないため，何もしない．
+
+    #[inline]
+    fn truncate(&mut self) {}
+
+    #[inline]
+    fn check_same_len(&self, _rhs: &Self) {}
+}
+
+/// ヒープ上に確保する可変長のビット集合．
+///
+/// ビット長は生成時に指定し，範囲外のビットは常に0に保たれる．
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct DynBitSet {
+    bit: Vec<u64>,
+    len: usize,
+}
+
+impl DynBitSet {
+    /// ビット長が`len`で，すべてのビットが0の`DynBitSet`を生成する．
+    pub fn new(len: usize) -> Self {
+        Self {
+            bit: vec![0; len.div_ceil(64)],
+            len,
+        }
+    }
+
+    /// ビット長を返す．
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// ビット長が0かを返す．
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// 指定したビットが立っているかを返す．
+    ///
+    /// # パニック
+    /// - `i >= len` の場合
+    pub fn get(&self, i: usize) -> bool {
+        assert!(i < self.len);
+        (self.bit[i >> 6] >> (i & 63)) & 1 == 1
+    }
+
+    /// 指定したビットを1に更新する．
+    ///
+    /// # パニック
+    /// - `i >= len` の場合
+    pub fn set(&mut self, i: usize) {
+        assert!(i < self.len);
+        self.bit[i >> 6] |= 1 << (i & 63);
+    }
+
+    /// 指定したビットを0に更新する．
+    ///
+    /// # パニック
+    /// - `i >= len` の場合
+    pub fn reset(&mut self, i: usize) {
+        assert!(i < self.len);
+        self.bit[i >> 6] &= !(1 << (i & 63));
+    }
+
+    /// 立っているビットの個数を返す．
+    pub fn count_ones(&self) -> usize {
+        count_ones(&self.bit)
+    }
+
+    /// 立っているビットが存在するかを返す．
+    pub fn any(&self) -> bool {
+        self.bit.iter().any(|&w| w != 0)
+    }
+
+    /// すべてのビットが0かを返す．
+    pub fn none(&self) -> bool {
+        !self.any()
+    }
+
+    /// 立っているビットのうち，最小の位置を返す．
+    pub fn find_first(&self) -> Option<usize> {
+        find_from(&self.bit, 0)
+    }
+
+    /// 立っているビットのうち，`i`より大きい最小の位置を返す．
+    pub fn find_next(&self, i: usize) -> Option<usize> {
+        find_from(&self.bit, i.checked_add(1)?)
+    }
+
+    /// 立っているビットの位置を昇順に返すイテレータを返す．
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        std::iter::successors(self.find_first(), |&i| self.find_next(i))
+    }
+
+    /// 範囲外のビットを0に戻す．
+    fn truncate(&mut self) {
+        if self.len & 63 != 0
+            && let Some(last) = self.bit.last_mut()
+        {
+            *last &= (1 << (self.len & 63)) - 1;
+        }
+    }
+
+    /// 2つのビット集合のビット長が等しいことを確かめる．
+    fn check_same_len(&self, rhs: &Self) {
+        assert_eq!(self.len, rhs.len, "bit lengths differ");
+    }
+}
+
+fn count_ones(bit: &[u64]) -> usize {
+    bit.iter().map(|w| w.count_ones() as usize).sum()
+}
+
+/// 立っているビットのうち，`start`以上の最小の位置を返す．
+fn find_from(bit: &[u64], start: usize) -> Option<usize> {
+    let w = start >> 6;
+    let first = bit.get(w)? & (!0 << (start & 63));
+    if first != 0 {
+        return Some((w << 6) | first.trailing_zeros() as usize);
+    }
+    bit.iter()
+        .enumerate()
+        .skip(w + 1)
+        .find(|&(_, &x)| x != 0)
+        .map(|(i, x)| (i << 6) | x.trailing_zeros() as usize)
+}
+
+/// 全体を上位方向に`n`ビットシフトする．あふれたビットは捨てられる．
+fn shl_words(bit: &mut [u64], n: usize) {
+    let (w, b) = (n >> 6, n & 63);
+    for i in (0..bit.len()).rev() {
+        bit[i] = if i < w {
+            0
+        } else if b == 0 || i == w {
+            bit[i - w] << b
+        } else {
+            (bit[i - w] << b) | (bit[i - w - 1] >> (64 - b))
+        };
+    }
 }
 
+/// 全体を下位方向に`n`ビットシフトする．
+fn shr_words(bit: &mut [u64], n: usize) {
+    let (w, b) = (n >> 6, n & 63);
+    let len = bit.len();
+    for i in 0..len {
+        bit[i] = match i.checked_add(w) {
+            Some(j) if j < len => {
+                if b == 0 || j + 1 == len {
+                    bit[j] >> b
+                } else {
+                    (bit[j] >> b) | (bit[j + 1] << (64 - b))
+                }
+            }
+            _ => 0,
+        };
+    }
+}
+
+macro_rules! impl_bit_op {
+    ([$($g:tt)*], $t:ty, $trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident, $op:tt) => {
+        impl<$($g)*> $assign_trait<&$t> for $t {
+            fn $assign_method(&mut self, rhs: &$t) {
+                self.check_same_len(rhs);
+                self.bit
+                    .iter_mut()
+                    .zip(rhs.bit.iter())
+                    .for_each(|(x, &y)| *x $op y);
+            }
+        }
+
+        impl<$($g)*> $assign_trait for $t {
+            fn $assign_method(&mut self, rhs: $t) {
+                self.$assign_method(&rhs);
+            }
+        }
+
+        impl<$($g)*> $trait<&$t> for &$t {
+            type Output = $t;
+            fn $method(self, rhs: &$t) -> $t {
+                let mut res = self.clone();
+                res.$assign_method(rhs);
+                res
+            }
+        }
+
+        impl<$($g)*> $trait<&$t> for $t {
+            type Output = $t;
+            fn $method(mut self, rhs: &$t) -> $t {
+                self.$assign_method(rhs);
+                self
+            }
+        }
+
+        impl<$($g)*> $trait for $t {
+            type Output = $t;
+            fn $method(mut self, rhs: $t) -> $t {
+                self.$assign_method(&rhs);
+                self
+            }
+        }
+    };
+}
+
+macro_rules! impl_shift_op {
+    ([$($g:tt)*], $t:ty, $trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident, $f:ident) => {
+        impl<$($g)*> $assign_trait<usize> for $t {
+            fn $assign_method(&mut self, n: usize) {
+                $f(&mut self.bit, n);
+                self.truncate();
+            }
+        }
+
+        impl<$($g)*> $trait<usize> for &$t {
+            type Output = $t;
+            fn $method(self, n: usize) -> $t {
+                let mut res = self.clone();
+                res.$assign_method(n);
+                res
+            }
+        }
+
+        impl<$($g)*> $trait<usize> for $t {
+            type Output = $t;
+            fn $method(mut self, n: usize) -> $t {
+                self.$assign_method(n);
+                self
+            }
+        }
+    };
+}
+
+macro_rules! impl_ops {
+    ([$($g:tt)*], $t:ty) => {
+        impl_bit_op!([$($g)*], $t, BitAnd, bitand, BitAndAssign, bitand_assign, &=);
+        impl_bit_op!([$($g)*], $t, BitOr, bitor, BitOrAssign, bitor_assign, |=);
+        impl_bit_op!([$($g)*], $t, BitXor, bitxor, BitXorAssign, bitxor_assign, ^=);
+        impl_shift_op!([$($g)*], $t, Shl, shl, ShlAssign, shl_assign, shl_words);
+        impl_shift_op!([$($g)*], $t, Shr, shr, ShrAssign, shr_assign, shr_words);
+
+        impl<$($g)*> Not for &$t {
+            type Output = $t;
+            fn not(self) -> $t {
+                !self.clone()
+            }
+        }
+
+        impl<$($g)*> Not for $t {
+            type Output = $t;
+            fn not(mut self) -> $t {
+                self.bit.iter_mut().for_each(|x| *x = !*x);
+                self.truncate();
+                self
+            }
+        }
+    };
+}
+
+impl_ops!([const WORDS: usize], BitSet<WORDS>);
+impl_ops!([], DynBitSet);
+
 impl<const WORDS: usize> Default for BitSet<WORDS> {
     fn default() -> Self {
         Self::new()
@@ -93,26 +411,54 @@ impl<const WORDS: usize> PartialOrd for BitSet<WORDS> {
 
 impl<const WORDS: usize> Debug for BitSet<WORDS> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut started = false;
+        fmt_words(&self.bit, f)
+    }
+}
 
-        for &w in self.bit.iter().rev() {
-            if !started {
-                if w == 0 {
-                    continue;
-                }
-                started = true;
-                write!(f, "{:b}", w)?;
-            } else {
-                write!(f, "{:064b}", w)?;
-            }
-        }
+impl Ord for DynBitSet {
+    /// 数値として比較し，等しい場合はビット長で比較する．
+    fn cmp(&self, other: &Self) -> Ordering {
+        let n = self.bit.len().max(other.bit.len());
+        (0..n)
+            .rev()
+            .map(|i| self.bit.get(i).unwrap_or(&0))
+            .cmp((0..n).rev().map(|i| other.bit.get(i).unwrap_or(&0)))
+            .then(self.len.cmp(&other.len))
+    }
+}
 
+impl PartialOrd for DynBitSet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Debug for DynBitSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt_words(&self.bit, f)
+    }
+}
+
+fn fmt_words(bit: &[u64], f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let mut started = false;
+
+    for &w in bit.iter().rev() {
         if !started {
-            write!(f, "0")?;
+            if w == 0 {
+                continue;
+            }
+            started = true;
+            write!(f, "{:b}", w)?;
+        } else {
+            write!(f, "{:064b}", w)?;
         }
+    }
 
-        Ok(())
+    if !started {
+        write!(f, "0")?;
     }
+
+    Ok(())
 }
 
 /// 指定したビット長に対応する`BitSet`を生成する．
@@ -331,4 +677,136 @@ mod tests {
         test::<2>(&mut rng);
         test::<10>(&mut rng);
     }
+
+    #[test]
+    fn test_ops() {
+        let mut a = BitSet::<2>::new();
+        let mut b = BitSet::<2>::new();
+        a.set(1);
+        a.set(70);
+        b.set(70);
+        b.set(127);
+
+        assert_eq!((&a & &b).iter().collect::<Vec<_>>(), vec![70]);
+        assert_eq!((&a | &b).iter().collect::<Vec<_>>(), vec![1, 70, 127]);
+        assert_eq!((&a ^ &b).iter().collect::<Vec<_>>(), vec![1, 127]);
+        assert_eq!((!&a).count_ones(), 126);
+        assert_eq!((&a << 63).iter().collect::<Vec<_>>(), vec![64]);
+        assert_eq!((&b >> 70).iter().collect::<Vec<_>>(), vec![0, 57]);
+        assert!((&a << 128).none());
+        assert!(a.any());
+
+        assert_eq!(a.find_first(), Some(1));
+        assert_eq!(a.find_next(1), Some(70));
+        assert_eq!(a.find_next(70), None);
+        assert_eq!(a.find_next(usize::MAX), None);
+    }
+
+    #[test]
+    fn test_dyn_ops() {
+        let mut a = DynBitSet::new(100);
+        a.set(0);
+        a.set(99);
+        assert_eq!(a.len(), 100);
+        assert_eq!((!&a).count_ones(), 98);
+        assert_eq!((&a << 1).iter().collect::<Vec<_>>(), vec![1]);
+        assert_eq!((&a >> 99).iter().collect::<Vec<_>>(), vec![0]);
+
+        let empty = DynBitSet::new(0);
+        assert!(empty.is_empty());
+        assert!((!empty).none());
+    }
+
+    #[test]
+    #[should_panic(expected = "bit lengths differ")]
+    fn dyn_ops_panics_when_len_differs() {
+        let _ = DynBitSet::new(10) & DynBitSet::new(11);
+    }
+
+    #[test]
+    fn test_ops_random() {
+        fn to_vec(naive: &[bool]) -> Vec<usize> {
+            (0..naive.len()).filter(|&i| naive[i]).collect()
+        }
+
+        fn random_naive(rng: &mut impl Rng, n: usize) -> Vec<bool> {
+            (0..n).map(|_| rng.random_ratio(1, 4)).collect()
+        }
+
+        fn test<const WORDS: usize>(rng: &mut impl Rng) {
+            let n = WORDS * 64;
+            for _ in 0..100 {
+                let x = random_naive(rng, n);
+                let y = random_naive(rng, n);
+                let mut a = BitSet::<WORDS>::new();
+                let mut b = BitSet::<WORDS>::new();
+                let mut c = DynBitSet::new(n);
+                let mut d = DynBitSet::new(n);
+                for i in 0..n {
+                    if x[i] {
+                        a.set(i);
+                        c.set(i);
+                    }
+                    if y[i] {
+                        b.set(i);
+                        d.set(i);
+                    }
+                }
+
+                let and = (0..n).map(|i| x[i] & y[i]).collect::<Vec<_>>();
+                let or = (0..n).map(|i| x[i] | y[i]).collect::<Vec<_>>();
+                let xor = (0..n).map(|i| x[i] ^ y[i]).collect::<Vec<_>>();
+                let not = (0..n).map(|i| !x[i]).collect::<Vec<_>>();
+                assert!((&a & &b).iter().eq(to_vec(&and)));
+                assert!((&a | &b).iter().eq(to_vec(&or)));
+                assert!((&a ^ &b).iter().eq(to_vec(&xor)));
+                assert!((!&a).iter().eq(to_vec(&not)));
+                assert!((&c & &d).iter().eq(to_vec(&and)));
+                assert!((&c | &d).iter().eq(to_vec(&or)));
+                assert!((&c ^ &d).iter().eq(to_vec(&xor)));
+                assert!((!&c).iter().eq(to_vec(&not)));
+
+                let s = rng.random_range(0..=n + 10);
+                let shl = (0..n).map(|i| i >= s && x[i - s]).collect::<Vec<_>>();
+                let shr = (0..n).map(|i| i + s < n && x[i + s]).collect::<Vec<_>>();
+                assert!((&a << s).iter().eq(to_vec(&shl)));
+                assert!((&a >> s).iter().eq(to_vec(&shr)));
+                assert!((&c << s).iter().eq(to_vec(&shl)));
+                assert!((&c >> s).iter().eq(to_vec(&shr)));
+
+                let count = x.iter().filter(|&&v| v).count();
+                assert_eq!(a.count_ones(), count);
+                assert_eq!(c.count_ones(), count);
+                assert_eq!(a.any(), count > 0);
+                assert_eq!(c.none(), count == 0);
+
+                let i = rng.random_range(0..n);
+                let next = (i + 1..n).find(|&j| x[j]);
+                assert_eq!(a.find_next(i), next);
+                assert_eq!(c.find_next(i), next);
+            }
+        }
+
+        fn test_dyn(rng: &mut impl Rng) {
+            for _ in 0..100 {
+                let n = rng.random_range(1..300);
+                let x = random_naive(rng, n);
+                let mut c = DynBitSet::new(n);
+                (0..n).filter(|&i| x[i]).for_each(|i| c.set(i));
+
+                let s = rng.random_range(0..=n);
+                let shl = (0..n).map(|i| i >= s && x[i - s]).collect::<Vec<_>>();
+                let not = (0..n).map(|i| !x[i]).collect::<Vec<_>>();
+                assert!((&c << s).iter().eq(to_vec(&shl)));
+                assert!((!&c).iter().eq(to_vec(&not)));
+                assert_eq!(c.find_first(), (0..n).find(|&i| x[i]));
+            }
+        }
+
+        let mut rng = get_test_rng();
+        test::<1>(&mut rng);
+        test::<2>(&mut rng);
+        test::<5>(&mut rng);
+        test_dyn(&mut rng);
+    }
 }
@@ -0,0 +1,291 @@
+//! 座標圧縮2次元Fenwick Tree(Compressed 2D Fenwick Tree)
+//!
+//! 2次元平面上の点集合を管理するデータ構造．
+//! 更新する点は事前に登録し，登録した点に対して以下の操作を行うことができる．
+//! - 要素の1点更新．
+//! - 任意の長方形区間に含まれる点の要素の総積(和，xorなど)の取得．
+//!
+//! x方向のFenwick Treeの各ノードが，担当する点のy座標をソートして持ち，
+//! y方向のFenwick Treeを保持する．
+//! 演算は可換群である必要がある．
+//!
+//! # 計算量
+//! - 構築: `O(N log N)` ただし N は点の数
+//! - 点の更新: `O(log^2 N)`
+//! - 長方形区間クエリ: `O(log^2 N)`
+//! - メモリ: `O(N log N)`
+//!
+//! # 使用例
+//! ```
+//! use reprol::{ds::compressed_fenwick_tree_2d::CompressedFenwickTree2d, ops::op_add::OpAdd};
+//!
+//! let points = vec![(10, 3), (10, 8), (20, 3), (30, 100)];
+//! let mut ft = CompressedFenwickTree2d::<i64, OpAdd<i64>>::new(points);
+//! ft.op((10, 3), &5);
+//! ft.op((20, 3), &7);
+//! ft.op((10, 3), &1);
+//! assert_eq!(ft.fold(10..30, 0..10), 13);
+//! assert_eq!(ft.get((10, 3)), 6);
+//! ft.set((30, 100), 4);
+//! assert_eq!(ft.fold(0..50, 3..200), 17);
+//! ```
+
+use std::ops::Range;
+
+use crate::{bisect::SliceBoundsExt, ds::fenwick_tree::FenwickTree, ops::group::Group};
+
+/// 座標圧縮2次元Fenwick Tree
+pub struct CompressedFenwickTree2d<K, O: Group> {
+    /// ソートされたuniqueなx座標のリスト
+    xs: Vec<K>,
+
+    /// 各ノードのソートされたuniqueなy座標のリスト
+    ys: Vec<Vec<K>>,
+
+    /// 各ノードのy方向のFenwick Tree
+    y_fts: Vec<FenwickTree<O>>,
+
+    /// 演算(群)
+    op: O,
+}
+
+impl<K: Ord + Clone, O: Group> CompressedFenwickTree2d<K, O> {
+    /// 更新する点の集合`points`から構築する．
+    pub fn new(points: impl IntoIterator<Item = (K, K)>) -> Self
+    where
+        O: Default + Clone,
+    {
+        Self::with_op(points, O::default())
+    }
+
+    /// 演算`op`を指定して，更新する点の集合`points`から構築する．
+    pub fn with_op(points: impl IntoIterator<Item = (K, K)>, op: O) -> Self
+    where
+        O: Clone,
+    {
+        let points = points.into_iter().collect::<Vec<_>>();
+
+        let mut xs = points.iter().map(|(x, _)| x.clone()).collect::<Vec<_>>();
+        xs.sort_unstable();
+        xs.dedup();
+
+        let nx = xs.len();
+
+        let mut ys = vec![vec![]; nx];
+        for (x, y) in points {
+            let mut i = xs.lower_bound(&x) + 1;
+            while i <= nx {
+                ys[i - 1].push(y.clone());
+                i += i & i.wrapping_neg();
+            }
+        }
+
+        let mut y_fts = Vec::with_capacity(nx);
+
+        for ysi in &mut ys {
+            ysi.sort_unstable();
+            ysi.dedup();
+            y_fts.push(FenwickTree::with_op(ysi.len(), op.clone()));
+        }
+
+        Self { xs, ys, y_fts, op }
+    }
+
+    /// 点`point`の要素に`rhs`を作用させる．
+    ///
+    /// # Panics
+    ///
+    /// `point` が事前に登録されていない点である場合はパニックする．
+    pub fn op(&mut self, point: (K, K), rhs: &O::Element) {
+        let (x, y) = point;
+        let xi = self.xs.lower_bound(&x);
+
+        assert!(
+            xi < self.xs.len() && self.xs[xi] == x,
+            "point not registered in CompressedFenwickTree2d"
+        );
+
+        let mut i = xi + 1;
+        while i <= self.xs.len() {
+            let ys = &self.ys[i - 1];
+            let yi = ys.lower_bound(&y);
+            assert!(
+                yi < ys.len() && ys[yi] == y,
+                "point not registered in CompressedFenwickTree2d"
+            );
+            self.y_fts[i - 1].op(yi, rhs);
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    /// 点`point`の要素を`value`に更新する．
+    ///
+    /// # Panics
+    ///
+    /// `point` が事前に登録されていない点である場合はパニックする．
+    pub fn set(&mut self, point: (K, K), value: O::Element) {
+        let diff = self.op.op(&value, &self.op.inv(&self.get(point.clone())));
+        self.op(point, &diff);
+    }
+
+    /// 点`point`の要素を返す．
+    /// 登録されていない点の場合は単位元を返す．
+    pub fn get(&self, point: (K, K)) -> O::Element {
+        let (x, y) = point;
+        let l = self.xs.lower_bound(&x);
+        let r = self.xs.upper_bound(&x);
+        let y_range = |ys: &[K]| ys.lower_bound(&y)..ys.upper_bound(&y);
+        self.fold_index(l, r, y_range)
+    }
+
+    /// 長方形区間`[x_range] × [y_range]`に含まれる点の要素の総積を返す．
+    ///
+    /// # Panics
+    ///
+    /// `x_range`または`y_range`の始点が終点より大きい場合はパニックする．
+    pub fn fold(&self, x_range: Range<K>, y_range: Range<K>) -> O::Element {
+        assert!(x_range.start <= x_range.end);
+        assert!(y_range.start <= y_range.end);
+        let l = self.xs.lower_bound(&x_range.start);
+        let r = self.xs.lower_bound(&x_range.end);
+        let y_range = |ys: &[K]| ys.lower_bound(&y_range.start)..ys.lower_bound(&y_range.end);
+        self.fold_index(l, r, y_range)
+    }
+
+    /// x座標のインデックス区間`[l, r)`について，
+    /// 各ノードで`y_range`が返すインデックス区間の総積を返す．
+    fn fold_index(&self, l: usize, r: usize, y_range: impl Fn(&[K]) -> Range<usize>) -> O::Element {
+        let prefix_l = self.prefix(l, &y_range);
+        let prefix_r = self.prefix(r, &y_range);
+        self.op.op(&self.op.inv(&prefix_l), &prefix_r)
+    }
+
+    /// x座標のインデックス区間`[0, r)`について，
+    /// 各ノードで`y_range`が返すインデックス区間の総積を返す．
+    fn prefix(&self, mut r: usize, y_range: &impl Fn(&[K]) -> Range<usize>) -> O::Element {
+        let mut res = self.op.id();
+        while r > 0 {
+            let range = y_range(&self.ys[r - 1]);
+            if range.start < range.end {
+                res = self.op.op(&self.y_fts[r - 1].fold(range), &res);
+            }
+            r -= r & r.wrapping_neg();
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use rand::Rng;
+
+    use super::*;
+    use crate::{
+        ops::{op_add::OpAdd, op_xor::OpXor},
+        utils::test_utils::random::get_test_rng,
+    };
+
+    #[test]
+    fn test_add() {
+        let points = vec![(10, 3), (10, 8), (20, 3), (30, 100)];
+        let mut ft = CompressedFenwickTree2d::<i32, OpAdd<i64>>::new(points);
+        ft.set((10, 3), 5);
+        ft.set((20, 3), 7);
+        assert_eq!(ft.fold(10..30, 0..10), 12);
+        assert_eq!(ft.fold(10..31, 0..101), 12);
+        assert_eq!(ft.fold(11..20, 0..101), 0);
+        ft.op((30, 100), &10);
+        assert_eq!(ft.fold(10..31, 0..101), 22);
+        assert_eq!(ft.fold(-100..100, 100..101), 10);
+    }
+
+    #[test]
+    fn test_get() {
+        let points = vec![(10, 3), (10, 8), (20, 3), (30, 100)];
+        let mut ft = CompressedFenwickTree2d::<i64, OpXor<u32>>::new(points);
+        ft.op((10, 3), &5);
+        ft.op((10, 3), &3);
+        ft.op((20, 3), &7);
+        assert_eq!(ft.get((10, 3)), 6);
+        assert_eq!(ft.get((20, 3)), 7);
+        assert_eq!(ft.get((10, 8)), 0);
+        assert_eq!(ft.get((40, 50)), 0);
+        assert_eq!(ft.get((10, 50)), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_unregistered_point() {
+        let points = vec![(10, 3), (10, 8), (20, 3)];
+        let mut ft = CompressedFenwickTree2d::<i64, OpAdd<i64>>::new(points);
+        ft.op((20, 5), &1);
+    }
+
+    #[test]
+    fn test_randomized() {
+        let mut rng = get_test_rng();
+
+        const T: usize = 50;
+        const Q: usize = 2000;
+        const N_MAX: usize = 50;
+        const COORD_MAX: i64 = 30;
+
+        for _ in 0..T {
+            let n = rng.random_range(1..=N_MAX);
+            let points = (0..n)
+                .map(|_| {
+                    (
+                        rng.random_range(-COORD_MAX..=COORD_MAX),
+                        rng.random_range(-COORD_MAX..=COORD_MAX),
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            let mut ft = CompressedFenwickTree2d::<i64, OpAdd<i64>>::new(points.clone());
+            let mut naive = HashMap::new();
+
+            for _ in 0..Q {
+                if rng.random_ratio(1, 2) {
+                    let p = points[rng.random_range(0..n)];
+                    let value = rng.random_range(-1000000000..=1000000000);
+                    if rng.random_ratio(1, 2) {
+                        ft.set(p, value);
+                        naive.insert(p, value);
+                    } else {
+                        ft.op(p, &value);
+                        *naive.entry(p).or_insert(0) += value;
+                    }
+                } else {
+                    let xl = rng.random_range(-COORD_MAX - 1..=COORD_MAX + 1);
+                    let xr = rng.random_range(xl..=COORD_MAX + 1);
+                    let yl = rng.random_range(-COORD_MAX - 1..=COORD_MAX + 1);
+                    let yr = rng.random_range(yl..=COORD_MAX + 1);
+                    let expected = naive
+                        .iter()
+                        .filter(|&(&(x, y), _)| (xl..xr).contains(&x) && (yl..yr).contains(&y))
+                        .map(|(_, &v)| v)
+                        .sum::<i64>();
+                    assert_eq!(ft.fold(xl..xr, yl..yr), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_reversed_y_range() {
+        let ft = CompressedFenwickTree2d::<i64, OpAdd<i64>>::new(vec![(10, 3), (20, 8)]);
+        let (yl, yr) = (8, 3);
+        ft.fold(0..30, yl..yr);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_reversed_x_range() {
+        let ft = CompressedFenwickTree2d::<i64, OpAdd<i64>>::new(vec![(10, 3), (20, 8)]);
+        let (xl, xr) = (15, 12);
+        ft.fold(xl..xr, 0..10);
+    }
+}
@@ -0,0 +1,138 @@
+//! 座標圧縮セグメント木(Compressed Segment Tree)
+//!
+//! 事前に登録したキーを添字とするセグメント木．
+//! キーを[`CoordinateCompressor`]で座標圧縮し，キーの範囲で直接区間積を取得できる．
+//! 以下の操作をいずれも O(log n) で処理できる(nは登録したキーの個数)．
+//! - 登録したキーの要素の1点変更．
+//! - 任意のキーの範囲に含まれる要素の総積の取得．
+//!
+//! # 使用例
+//! ```
+//! use reprol::{ds::compressed_segment_tree::CompressedSegmentTree, ops::op_add::OpAdd};
+//!
+//! let mut seg = CompressedSegmentTree::<i64, OpAdd<i64>>::new([-1_000_000_000, 5, 30, 1 << 40]);
+//! seg.set(5, 3);
+//! seg.set(30, 4);
+//! seg.set(1 << 40, 10);
+//! assert_eq!(seg.fold(0..=30), 7);
+//! assert_eq!(seg.fold(6..), 14);
+//! assert_eq!(seg.fold(..), 17);
+//! assert_eq!(seg.get(&30), Some(&4));
+//! assert_eq!(seg.get(&31), None);
+//! ```
+
+use std::ops::RangeBounds;
+
+use crate::{compress::CoordinateCompressor, ds::segment_tree::SegmentTree, ops::monoid::Monoid};
+
+/// 座標圧縮セグメント木
+pub struct CompressedSegmentTree<K, O: Monoid> {
+    keys: CoordinateCompressor<K>,
+    seg: SegmentTree<O>,
+}
+
+impl<K: Ord, O: Monoid> CompressedSegmentTree<K, O> {
+    /// 更新するキーの集合`keys`から，単位元で初期化して構築する．
+    pub fn new(keys: impl IntoIterator<Item = K>) -> Self
+    where
+        O: Default,
+    {
+        Self::with_op(keys, O::default())
+    }
+
+    /// 演算`op`を指定して，更新するキーの集合`keys`から構築する．
+    pub fn with_op(keys: impl IntoIterator<Item = K>, op: O) -> Self {
+        let keys = CoordinateCompressor::new(keys);
+        let seg = SegmentTree::with_op(keys.len(), op);
+        Self { keys, seg }
+    }
+
+    /// 登録したキーの個数を返す．
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// キーが1つも登録されていないかを返す．
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// 登録したキーの座標圧縮を返す．
+    pub fn keys(&self) -> &CoordinateCompressor<K> {
+        &self.keys
+    }
+
+    /// キー`key`の要素を返す．登録されていないキーの場合は`None`を返す．
+    pub fn get(&self, key: &K) -> Option<&O::Element> {
+        self.keys
+            .contains(key)
+            .then(|| self.seg.get(self.keys.index_of(key)))
+    }
+
+    /// キー`key`の要素を`value`に更新する．
+    ///
+    /// # Panics
+    ///
+    /// `key`が登録されていない場合はパニックする．
+    pub fn set(&mut self, key: K, value: O::Element) {
+        self.seg.set(self.keys.index_of(&key), value);
+    }
+
+    /// キーが`range`に含まれる要素の総積を返す．
+    pub fn fold(&self, range: impl RangeBounds<K>) -> O::Element {
+        self.seg.fold(self.keys.range(range))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use rand::Rng;
+
+    use super::*;
+    use crate::{
+        ops::{op_add::OpAdd, op_min::OpMin},
+        utils::test_utils::random::get_test_rng,
+    };
+
+    #[test]
+    fn test_randomized() {
+        let mut rng = get_test_rng();
+
+        for _ in 0..50 {
+            let n = rng.random_range(1..50);
+            let keys = (0..n)
+                .map(|_| rng.random_range(-1000..=1000))
+                .collect::<Vec<i64>>();
+            let mut sum = CompressedSegmentTree::<_, OpAdd<i64>>::new(keys.clone());
+            let mut min = CompressedSegmentTree::<_, OpMin<i64>>::new(keys.clone());
+            let mut naive = BTreeMap::new();
+
+            for _ in 0..200 {
+                if rng.random() {
+                    let key = keys[rng.random_range(0..n)];
+                    let value = rng.random_range(-1000..=1000);
+                    sum.set(key, value);
+                    min.set(key, value);
+                    naive.insert(key, value);
+                } else {
+                    let l = rng.random_range(-1100..=1100);
+                    let r = rng.random_range(l..=1100);
+                    assert_eq!(sum.fold(l..r), naive.range(l..r).map(|(_, v)| v).sum());
+                    assert_eq!(
+                        min.fold(l..=r),
+                        naive
+                            .range(l..=r)
+                            .map(|(_, &v)| v)
+                            .min()
+                            .unwrap_or(i64::MAX)
+                    );
+                }
+                let key = rng.random_range(-1000..=1000);
+                let expected = keys.contains(&key).then(|| naive.get(&key).unwrap_or(&0));
+                assert_eq!(sum.get(&key), expected);
+            }
+        }
+    }
+}
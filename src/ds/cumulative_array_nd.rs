@@ -0,0 +1,241 @@
+//! D次元累積積(累積和)
+//!
+//! 群のD次元配列に対する累積積を管理するデータ構造．
+//! 次元数をconst genericsで指定し，区間積[`fold`](CumulativeArrayNd::fold)を 2^D 回の演算で求める．
+//! 配列は各次元の長さ`shape`と行優先(row-major)で並べた1次元配列で与える．
+//!
+//! 次元ごとに累積をとるため，2次元以上では演算が可換である必要がある．
+//!
+//! # 使用例
+//! ## 3次元累積和
+//! ```
+//! use reprol::ds::cumulative_array_nd::CumulativeSumNd;
+//!
+//! // v[i][j][k] = i + j + k
+//! let v = (0..2 * 3 * 4).map(|x| x / 12 + x / 4 % 3 + x % 4).collect();
+//! let cum = CumulativeSumNd::<i64, 3>::new([2, 3, 4], v);
+//! assert_eq!(cum.fold([0..2, 0..3, 0..4]), 72);
+//! assert_eq!(cum.fold([1..2, 1..3, 2..3]), 9);
+//! assert_eq!(cum.get([1, 2, 3]), 6);
+//! ```
+//!
+//! ## xorとModInt
+//! ```
+//! use reprol::{
+//!     ds::cumulative_array_nd::CumulativeArrayNd,
+//!     math::modint::ModInt998244353,
+//!     ops::{op_add::OpAdd, op_xor::OpXor},
+//! };
+//!
+//! let cum = CumulativeArrayNd::<OpXor<u32>, 2>::from_fn([2, 2], |[i, j]| (i * 2 + j) as u32);
+//! assert_eq!(cum.fold([0..2, 0..2]), 0);
+//!
+//! type Mint = ModInt998244353;
+//! let cum = CumulativeArrayNd::<OpAdd<Mint>, 1>::from_fn([3], |[i]| Mint::new(i as u64));
+//! assert_eq!(cum.fold([1..3]), Mint::new(3));
+//! ```
+
+use std::ops::Range;
+
+use crate::ops::{group::Group, op_add::OpAdd};
+
+/// D次元累積積を管理するデータ構造
+pub struct CumulativeArrayNd<O: Group, const D: usize> {
+    shape: [usize; D],
+
+    /// 各次元の長さに1を足した配列におけるストライド
+    strides: [usize; D],
+
+    inner: Vec<O::Element>,
+    op: O,
+}
+
+impl<O: Group, const D: usize> CumulativeArrayNd<O, D> {
+    /// 各次元の長さが`shape`で行優先に並べた配列`v`の累積配列を構築する．
+    pub fn new(shape: [usize; D], v: Vec<O::Element>) -> Self
+    where
+        O: Default,
+    {
+        Self::with_op(shape, v, O::default())
+    }
+
+    /// 演算`op`を明示的に渡して累積配列を構築する．
+    pub fn with_op(shape: [usize; D], v: Vec<O::Element>, op: O) -> Self {
+        assert_eq!(v.len(), shape.iter().product::<usize>());
+        let mut v = v.into_iter();
+        Self::from_fn_with_op(shape, |_| v.next().unwrap(), op)
+    }
+
+    /// 位置`index`の要素を`f(index)`として累積配列を構築する．
+    pub fn from_fn(shape: [usize; D], f: impl FnMut([usize; D]) -> O::Element) -> Self
+    where
+        O: Default,
+    {
+        Self::from_fn_with_op(shape, f, O::default())
+    }
+
+    /// 演算`op`を明示的に渡して，位置`index`の要素を`f(index)`として累積配列を構築する．
+    pub fn from_fn_with_op(
+        shape: [usize; D],
+        mut f: impl FnMut([usize; D]) -> O::Element,
+        op: O,
+    ) -> Self {
+        let strides = strides(&shape);
+        let len = shape.iter().map(|&n| n + 1).product::<usize>();
+
+        let mut inner = Vec::with_capacity(len);
+        let mut index = [0; D];
+        for p in 0..len {
+            for d in 0..D {
+                index[d] = p / strides[d] % (shape[d] + 1);
+            }
+            if index.iter().all(|&i| i > 0) {
+                inner.push(f(index.map(|i| i - 1)));
+            } else {
+                inner.push(op.id());
+            }
+        }
+
+        for (d, &stride) in strides.iter().enumerate() {
+            for p in 0..len {
+                if p / stride % (shape[d] + 1) > 0 {
+                    inner[p] = op.op(&inner[p - stride], &inner[p]);
+                }
+            }
+        }
+
+        Self {
+            shape,
+            strides,
+            inner,
+            op,
+        }
+    }
+
+    /// 各次元の長さを返す．
+    pub fn shape(&self) -> [usize; D] {
+        self.shape
+    }
+
+    /// `[0, index[0]) x ... x [0, index[D - 1])`の累積積を返す．
+    pub fn prefix(&self, index: [usize; D]) -> &O::Element {
+        let p = index
+            .iter()
+            .zip(&self.strides)
+            .map(|(&i, &stride)| i * stride)
+            .sum::<usize>();
+        &self.inner[p]
+    }
+
+    /// 位置`index`の要素の値を返す．
+    pub fn get(&self, index: [usize; D]) -> O::Element {
+        self.fold(index.map(|i| i..i + 1))
+    }
+
+    /// 区間`ranges[0] x ... x ranges[D - 1]`の累積積を返す．
+    pub fn fold(&self, ranges: [Range<usize>; D]) -> O::Element {
+        for (range, &n) in ranges.iter().zip(&self.shape) {
+            assert!(range.start <= range.end && range.end <= n);
+        }
+
+        // 包除原理: 各次元で左端を選んだ数の偶奇で符号が決まる
+        let mut res = self.op.id();
+        for mask in 0..1usize << D {
+            let mut p = 0;
+            for (d, range) in ranges.iter().enumerate() {
+                let i = if mask >> d & 1 == 1 {
+                    range.start
+                } else {
+                    range.end
+                };
+                p += i * self.strides[d];
+            }
+            if mask.count_ones() % 2 == 0 {
+                res = self.op.op(&res, &self.inner[p]);
+            } else {
+                res = self.op.op(&res, &self.op.inv(&self.inner[p]));
+            }
+        }
+        res
+    }
+}
+
+/// 各次元の長さに1を足した配列の行優先でのストライドを返す．
+pub(crate) fn strides<const D: usize>(shape: &[usize; D]) -> [usize; D] {
+    let mut strides = [1; D];
+    for d in (0..D.saturating_sub(1)).rev() {
+        strides[d] = strides[d + 1] * (shape[d + 1] + 1);
+    }
+    strides
+}
+
+/// D次元累積和
+pub type CumulativeSumNd<T, const D: usize> = CumulativeArrayNd<OpAdd<T>, D>;
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::*;
+    use crate::{ops::op_xor::OpXor, utils::test_utils::random::get_test_rng};
+
+    #[test]
+    fn test_2d() {
+        let v = vec![1, 2, 3, 4, 5, 6, 7, 8, 9];
+        let cum = CumulativeSumNd::<i32, 2>::new([3, 3], v);
+        assert_eq!(cum.fold([0..3, 0..3]), 45);
+        assert_eq!(cum.fold([1..3, 1..3]), 28);
+        assert_eq!(cum.fold([0..2, 1..3]), 16);
+        assert_eq!(cum.fold([2..2, 0..3]), 0);
+        assert_eq!(*cum.prefix([2, 2]), 12);
+        assert_eq!(cum.get([1, 2]), 6);
+    }
+
+    #[test]
+    fn test_zero_dim() {
+        let cum = CumulativeSumNd::<i32, 0>::new([], vec![5]);
+        assert_eq!(cum.fold([]), 5);
+        assert_eq!(cum.get([]), 5);
+    }
+
+    #[test]
+    fn test_random_3d() {
+        let mut rng = get_test_rng();
+
+        for _ in 0..50 {
+            let shape = [
+                rng.random_range(1..=6),
+                rng.random_range(1..=6),
+                rng.random_range(1..=6),
+            ];
+            let v = (0..shape.iter().product::<usize>())
+                .map(|_| rng.random_range(-1000..=1000))
+                .collect::<Vec<i64>>();
+            let x = (0..shape.iter().product::<usize>())
+                .map(|_| rng.random::<u32>())
+                .collect::<Vec<_>>();
+            let cum = CumulativeSumNd::<i64, 3>::new(shape, v.clone());
+            let cum_xor = CumulativeArrayNd::<OpXor<u32>, 3>::new(shape, x.clone());
+
+            for _ in 0..50 {
+                let ranges = shape.map(|n| {
+                    let l = rng.random_range(0..=n);
+                    l..rng.random_range(l..=n)
+                });
+                let mut sum = 0;
+                let mut xor = 0;
+                for i in ranges[0].clone() {
+                    for j in ranges[1].clone() {
+                        for k in ranges[2].clone() {
+                            let p = (i * shape[1] + j) * shape[2] + k;
+                            sum += v[p];
+                            xor ^= x[p];
+                        }
+                    }
+                }
+                assert_eq!(cum.fold(ranges.clone()), sum);
+                assert_eq!(cum_xor.fold(ranges), xor);
+            }
+        }
+    }
+}
@@ -0,0 +1,173 @@
+//! 斜めいもす法(DiagonalImos)
+//!
+//! 2次元グリッドを45°回転した座標系でいもす法を行うデータ構造．
+//! マス`(i, j)`を`(u, v) = (i + j, i - j + w - 1)`に写すと，
+//! マンハッタン距離が`r`以下のひし形領域は`u`と`v`の正方形領域になる．
+//! 回転後の座標系で[`ImosNd`]を用いて矩形加算を行い，最後に`build`を呼び出すことで各マスの値が得られる．
+//!
+//! # 計算量
+//! - `add_diamond`，`add_rotated`: O(1)
+//! - `build`: O((H + W)^2)
+//!
+//! # 使用例
+//! ```
+//! use reprol::{ds::diagonal_imos::DiagonalImos, ops::op_add::OpAdd};
+//!
+//! let mut imos = DiagonalImos::<OpAdd<i64>>::new(5, 5);
+//! imos.add_diamond(2, 2, 1, 3); // (2, 2)からのマンハッタン距離が1以下のマスに3を加算
+//! imos.add_diamond(0, 0, 2, 1);
+//! imos.build();
+//! assert_eq!(*imos.get(2, 2), 3);
+//! assert_eq!(*imos.get(1, 2), 3);
+//! assert_eq!(*imos.get(1, 1), 1);
+//! assert_eq!(*imos.get(0, 2), 1);
+//! assert_eq!(*imos.get(3, 3), 0);
+//! ```
+
+use std::ops::{Range, RangeBounds};
+
+use crate::{ds::imos_nd::ImosNd, ops::group::Group, utils::normalize_range::normalize_index};
+
+/// 45°回転した座標系で領域への加算を管理するいもす法
+pub struct DiagonalImos<O: Group> {
+    h: usize,
+    w: usize,
+    imos: ImosNd<O, 2>,
+}
+
+impl<O: Group> DiagonalImos<O> {
+    /// `h x w`の単位元で初期化されたテーブルを構築する．
+    pub fn new(h: usize, w: usize) -> Self
+    where
+        O: Default,
+    {
+        Self::with_op(h, w, O::default())
+    }
+
+    /// 演算`op`を明示的に渡してテーブルを構築する．
+    pub fn with_op(h: usize, w: usize, op: O) -> Self {
+        let n = (h + w).saturating_sub(1);
+        Self {
+            h,
+            w,
+            imos: ImosNd::with_op([n, n], op),
+        }
+    }
+
+    /// 回転後の座標で，`u`が`u_range`，`v`が`v_range`に含まれるマスに`value`を加算する．
+    /// ただし，`u = i + j`，`v = i - j + w - 1`である．
+    ///
+    /// `build`前にのみ呼び出せる．
+    pub fn add_rotated(
+        &mut self,
+        u_range: impl RangeBounds<usize>,
+        v_range: impl RangeBounds<usize>,
+        value: O::Element,
+    ) {
+        let n = self.imos.shape()[0];
+        let u_range = normalize_index(u_range, n);
+        let v_range = normalize_index(v_range, n);
+        self.imos.add([u_range, v_range], value);
+    }
+
+    /// マス`(i, j)`からのマンハッタン距離が`r`以下のマスに`value`を加算する．
+    ///
+    /// `build`前にのみ呼び出せる．
+    pub fn add_diamond(&mut self, i: usize, j: usize, r: usize, value: O::Element) {
+        assert!(i < self.h && j < self.w);
+        let n = self.imos.shape()[0];
+        let clip = |c: usize| Range {
+            start: c.saturating_sub(r),
+            end: c.saturating_add(r).saturating_add(1).min(n),
+        };
+        let (u, v) = self.rotate(i, j);
+        self.imos.add([clip(u), clip(v)], value);
+    }
+
+    /// いもす法の前計算を行い，各マスの値を確定させる．
+    pub fn build(&mut self) {
+        self.imos.build();
+    }
+
+    /// `build`後にマス`(i, j)`の値を返す．
+    pub fn get(&self, i: usize, j: usize) -> &O::Element {
+        assert!(i < self.h && j < self.w);
+        let (u, v) = self.rotate(i, j);
+        self.imos.get([u, v])
+    }
+
+    #[inline]
+    fn rotate(&self, i: usize, j: usize) -> (usize, usize) {
+        (i + j, i + self.w - 1 - j)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::*;
+    use crate::{ops::op_add::OpAdd, utils::test_utils::random::get_test_rng};
+
+    #[test]
+    fn test_random() {
+        let mut rng = get_test_rng();
+
+        for _ in 0..100 {
+            let h = rng.random_range(1..=20);
+            let w = rng.random_range(1..=20);
+            let mut imos = DiagonalImos::<OpAdd<i64>>::new(h, w);
+            let mut naive = vec![vec![0; w]; h];
+
+            for _ in 0..30 {
+                let value = rng.random_range(-100..=100);
+                if rng.random() {
+                    let (ci, cj) = (rng.random_range(0..h), rng.random_range(0..w));
+                    let r = rng.random_range(0..h + w);
+                    imos.add_diamond(ci, cj, r, value);
+                    for (i, row) in naive.iter_mut().enumerate() {
+                        for (j, x) in row.iter_mut().enumerate() {
+                            if i.abs_diff(ci) + j.abs_diff(cj) <= r {
+                                *x += value;
+                            }
+                        }
+                    }
+                } else {
+                    let n = h + w - 1;
+                    let ul = rng.random_range(0..=n);
+                    let ur = rng.random_range(ul..=n);
+                    let vl = rng.random_range(0..=n);
+                    let vr = rng.random_range(vl..=n);
+                    imos.add_rotated(ul..ur, vl..vr, value);
+                    for (i, row) in naive.iter_mut().enumerate() {
+                        for (j, x) in row.iter_mut().enumerate() {
+                            if (ul..ur).contains(&(i + j)) && (vl..vr).contains(&(i + w - 1 - j)) {
+                                *x += value;
+                            }
+                        }
+                    }
+                }
+            }
+
+            imos.build();
+
+            for (i, row) in naive.iter().enumerate() {
+                for (j, &x) in row.iter().enumerate() {
+                    assert_eq!(*imos.get(i, j), x);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_huge_radius() {
+        let mut imos = DiagonalImos::<OpAdd<i64>>::new(3, 4);
+        imos.add_diamond(1, 2, usize::MAX, 5);
+        imos.build();
+        for i in 0..3 {
+            for j in 0..4 {
+                assert_eq!(*imos.get(i, j), 5);
+            }
+        }
+    }
+}
@@ -13,6 +13,8 @@
 //! let dst = DisjointSparseTable::<OpMin<i64>>::new(vec![3, 5, 4, 100, 1]);
 //! assert_eq!(dst.fold(1..4), 4); // 区間`[1, 4)`の最小値
 //! assert_eq!(dst.fold(0..5), 1); // 区間`[0, 5)`の最小値
+//! assert_eq!(dst.bisect_right(0, |&x| x >= 3), 4); // 区間`[0, r)`の最小値が3以上となる最大のr
+//! assert_eq!(dst.bisect_left(4, |&x| x >= 4), 1); // 区間`[l, 4)`の最小値が4以上となる最小のl
 //! ```
 //!
 //! # Reference
@@ -23,7 +25,7 @@ use std::{
     ops::{Range, RangeBounds},
 };
 
-use crate::{ops::monoid::Monoid, utils::normalize_range::normalize_index};
+use crate::{bisect::Bisect, ops::monoid::Monoid, utils::normalize_range::normalize_index};
 
 pub struct DisjointSparseTable<O: Monoid> {
     len: usize,
@@ -90,6 +92,49 @@ impl<O: Monoid> DisjointSparseTable<O> {
         let datum = &self.data[i as usize];
         self.op.op(&datum[l], &datum[r])
     }
+
+    /// Disjoint Sparse Table上の二分探索(max_right)．
+    ///
+    /// `g(r) = f(fold(l..r))`として，
+    /// 単調な`g`に対して，`g(r) = true`となる最大の`r`を返す．
+    ///
+    /// # 計算量
+    /// - O(log n)
+    ///
+    /// # 制約
+    /// - `0 <= l <= len`
+    /// - `f(identity()) = true`
+    pub fn bisect_right(&self, l: usize, mut f: impl FnMut(&O::Element) -> bool) -> usize {
+        assert!(l <= self.len);
+        debug_assert!(f(&self.op.id()));
+
+        if l == self.len {
+            return self.len;
+        }
+        (l + 1..=self.len).bisect(|&r| f(&self.fold(l..r))) - 1
+    }
+
+    /// Disjoint Sparse Table上の二分探索(min_left)．
+    ///
+    /// `g(l) = f(fold(l..r))`として，
+    /// 単調な`g`に対して，`g(l) = true`となる最小の`l`を返す．
+    ///
+    /// # 計算量
+    /// - O(log n)
+    ///
+    /// # 制約
+    /// - `0 <= r <= len`
+    /// - `f(identity()) = true`
+    pub fn bisect_left(&self, r: usize, mut f: impl FnMut(&O::Element) -> bool) -> usize {
+        assert!(r <= self.len);
+        debug_assert!(f(&self.op.id()));
+
+        if r == 0 {
+            return 0;
+        }
+        // 区間の長さについて二分探索する
+        r + 1 - (1..=r).bisect(|&d| f(&self.fold(r - d..r)))
+    }
 }
 
 impl<O: Monoid> From<(Vec<O::Element>, O)> for DisjointSparseTable<O> {
@@ -200,4 +245,30 @@ mod tests {
     random_min_max_test!(test_random_min_u32, test_random_max_u32, u32);
     random_min_max_test!(test_random_min_i64, test_random_max_i64, i64);
     random_min_max_test!(test_random_min_u64, test_random_max_u64, u64);
+
+    #[test]
+    fn test_bisect_randomized() {
+        let mut rng = get_test_rng();
+
+        for _ in 0..100 {
+            let n = rng.random_range(1..50);
+            let v = (0..n)
+                .map(|_| rng.random_range(0..10))
+                .collect::<Vec<i64>>();
+            let dst = DisjointSparseTable::<OpAdd<i64>>::new(v.clone());
+
+            for _ in 0..50 {
+                let x = rng.random_range(0..100);
+                let f = |&sum: &i64| sum <= x;
+
+                let l = rng.random_range(0..=n);
+                let expected = (l..=n).rev().find(|&r| f(&dst.fold(l..r))).unwrap();
+                assert_eq!(dst.bisect_right(l, f), expected);
+
+                let r = rng.random_range(0..=n);
+                let expected = (0..=r).find(|&l| f(&dst.fold(l..r))).unwrap();
+                assert_eq!(dst.bisect_left(r, f), expected);
+            }
+        }
+    }
 }
@@ -0,0 +1,495 @@
+//! Euler Tour Tree
+//!
+//! 各頂点に可換モノイドの値を持つ森を管理するデータ構造．
+//! 以下の操作をいずれも O(log N) で処理できる．
+//! - 辺の追加(`link`)・削除(`cut`)．
+//! - 根の変更(`reroot`)．
+//! - 2頂点の連結判定．
+//! - 頂点の値の更新．
+//! - 連結成分および部分木の頂点の値の総積・頂点数の取得．
+//!
+//! 各木のオイラーツアーを，頂点を表すノードと有向辺を表すノードの列として，
+//! 親へのポインタを持つAVL木([`AvlTreeVec`](crate::ds::avl_tree_vec::AvlTreeVec)と共通の実装)で管理する．
+//! 部分木はツアー上で連続するとは限らない(列の先頭と末尾に分かれる)ため，演算は可換である必要がある．
+//!
+//! # 使用例
+//! ```
+//! use reprol::{ds::euler_tour_tree::EulerTourTree, ops::op_add::OpAdd};
+//!
+//! let mut ett = EulerTourTree::<OpAdd<i64>>::from(vec![1, 2, 4, 8, 16]);
+//! ett.link(0, 1);
+//! ett.link(1, 2);
+//! ett.link(1, 3);
+//! assert!(ett.connected(0, 3));
+//! assert_eq!(ett.component_fold(2), 15);
+//!
+//! // 頂点1を親としたときの頂点0の部分木と，頂点0を親としたときの頂点1の部分木
+//! assert_eq!(ett.subtree_fold(0, 1), 1);
+//! assert_eq!(ett.subtree_fold(1, 0), 14);
+//! assert_eq!(ett.subtree_size(1, 0), 3);
+//!
+//! ett.cut(1, 2);
+//! ett.set(4, 32);
+//! ett.link(2, 4);
+//! assert!(!ett.connected(0, 2));
+//! assert_eq!(ett.component_fold(2), 36);
+//! assert_eq!(ett.component_size(0), 3);
+//! ```
+//!
+//! # 問題例
+//! - [Dynamic Tree Vertex Add Subtree Sum](https://judge.yosupo.jp/problem/dynamic_tree_vertex_add_subtree_sum)
+
+use std::{collections::HashMap, mem::swap};
+
+use crate::{
+    ds::avl_tree_vec::{self, Augment, Link, Node, NodePtr, fetch, free, len},
+    ops::monoid::CommutativeMonoid,
+};
+
+/// 木の各ノードが持つ情報
+struct Data<T> {
+    /// 頂点の値(辺を表すノードでは単位元)
+    value: T,
+
+    /// 部分木の総積
+    prod: T,
+
+    /// 部分木に含まれる頂点を表すノードの個数
+    count: usize,
+
+    /// 頂点を表すノードであるか
+    is_vertex: bool,
+
+    parent: Link<Data<T>>,
+}
+
+/// オイラーツアーを管理するAVL木
+type Tour<T> = Link<Data<T>>;
+
+/// Euler Tour Tree
+pub struct EulerTourTree<O: CommutativeMonoid> {
+    /// 頂点を表すノード
+    vertices: Vec<NodePtr<Data<O::Element>>>,
+
+    /// 有向辺`(u, v)`を表すノード
+    edges: HashMap<(usize, usize), NodePtr<Data<O::Element>>>,
+
+    /// 演算(可換モノイド)
+    op: O,
+}
+
+impl<O: CommutativeMonoid> EulerTourTree<O>
+where
+    O::Element: Clone,
+{
+    /// 頂点数`n`の辺のない森で初期化する．
+    /// 各頂点の値は単位元で初期化される．
+    pub fn new(n: usize) -> Self
+    where
+        O: Default,
+    {
+        Self::with_op(n, O::default())
+    }
+
+    /// 演算`op`を指定して頂点数`n`の辺のない森で初期化する．
+    pub fn with_op(n: usize, op: O) -> Self {
+        let values = (0..n).map(|_| op.id()).collect();
+        Self::from((values, op))
+    }
+
+    /// 頂点`u`と`v`が連結であるかを判定する．
+    pub fn connected(&self, u: usize, v: usize) -> bool {
+        root_of(self.vertices[u]) == root_of(self.vertices[v])
+    }
+
+    /// 辺`(u, v)`を追加する．
+    ///
+    /// # Panics
+    ///
+    /// `u`と`v`が既に連結である場合はパニックする．
+    pub fn link(&mut self, u: usize, v: usize) {
+        assert!(!self.connected(u, v), "u and v are already connected");
+
+        self.reroot(u);
+        let tour_u = root_of(self.vertices[u]);
+
+        let root = root_of(self.vertices[v]);
+        let (left, right) = self.split(Some(root), index_of(self.vertices[v]) + 1);
+
+        let uv = self.create_edge(u, v);
+        let vu = self.create_edge(v, u);
+
+        let left = self.merge(left, Some(vu));
+        let left = self.merge(left, Some(tour_u));
+        let left = self.merge(left, Some(uv));
+        self.merge(left, right);
+    }
+
+    /// 辺`(u, v)`を削除する．
+    ///
+    /// # Panics
+    ///
+    /// 辺`(u, v)`が存在しない場合はパニックする．
+    pub fn cut(&mut self, u: usize, v: usize) {
+        let (Some(mut e1), Some(mut e2)) = (self.edges.remove(&(u, v)), self.edges.remove(&(v, u)))
+        else {
+            panic!("edge not found in EulerTourTree");
+        };
+
+        let mut i = index_of(e1);
+        let mut j = index_of(e2);
+        if i > j {
+            swap(&mut i, &mut j);
+            swap(&mut e1, &mut e2);
+        }
+
+        // [0, i) e1 (i, j) e2 (j, len)
+        let root = root_of(e1);
+        let (left, rest) = self.split(Some(root), i);
+        let (_, rest) = self.split(rest, 1);
+        let (_, rest) = self.split(rest, j - i - 1);
+        let (_, right) = self.split(rest, 1);
+        self.merge(left, right);
+
+        free(e1);
+        free(e2);
+    }
+
+    /// 頂点`v`が属する木の根を`v`に変更する．
+    pub fn reroot(&mut self, v: usize) {
+        let root = root_of(self.vertices[v]);
+        let (left, right) = self.split(Some(root), index_of(self.vertices[v]));
+        self.merge(right, left);
+    }
+
+    /// 頂点`v`の値を返す．
+    pub fn get(&self, v: usize) -> &O::Element {
+        &unsafe { self.vertices[v].as_ref() }.value.value
+    }
+
+    /// 頂点`v`の値を`value`に更新する．
+    pub fn set(&mut self, v: usize, value: O::Element) {
+        let mut node = self.vertices[v];
+        unsafe { node.as_mut() }.value.value = value;
+        let mut node = Some(node);
+        while let Some(x) = node {
+            fetch(x, self);
+            node = unsafe { x.as_ref() }.value.parent;
+        }
+    }
+
+    /// 頂点`v`が属する連結成分の頂点数を返す．
+    pub fn component_size(&self, v: usize) -> usize {
+        unsafe { root_of(self.vertices[v]).as_ref() }.value.count
+    }
+
+    /// 頂点`v`が属する連結成分の頂点の値の総積を返す．
+    pub fn component_fold(&self, v: usize) -> O::Element {
+        unsafe { root_of(self.vertices[v]).as_ref() }
+            .value
+            .prod
+            .clone()
+    }
+
+    /// 頂点`p`を親としたときの頂点`v`の部分木の頂点数を返す．
+    ///
+    /// # Panics
+    ///
+    /// 辺`(v, p)`が存在しない場合はパニックする．
+    pub fn subtree_size(&self, v: usize, p: usize) -> usize {
+        self.subtree(v, p).1
+    }
+
+    /// 頂点`p`を親としたときの頂点`v`の部分木の頂点の値の総積を返す．
+    ///
+    /// # Panics
+    ///
+    /// 辺`(v, p)`が存在しない場合はパニックする．
+    pub fn subtree_fold(&self, v: usize, p: usize) -> O::Element {
+        self.subtree(v, p).0
+    }
+
+    /// 頂点`p`を親としたときの頂点`v`の部分木の(総積, 頂点数)を返す．
+    fn subtree(&self, v: usize, p: usize) -> (O::Element, usize) {
+        let (Some(&down), Some(&up)) = (self.edges.get(&(p, v)), self.edges.get(&(v, p))) else {
+            panic!("edge not found in EulerTourTree");
+        };
+
+        let root = Some(root_of(down));
+        let i = index_of(down);
+        let j = index_of(up);
+
+        if i < j {
+            // ツアー上で p -> v ... v -> p の順に現れる
+            self.fold_range(root, i + 1, j)
+        } else {
+            // ツアー上で v -> p ... p -> v の順に現れる(列の両端が部分木)
+            let (prefix, prefix_count) = self.fold_range(root, 0, j);
+            let (suffix, suffix_count) = self.fold_range(root, i + 1, len(root));
+            (self.op.op(&prefix, &suffix), prefix_count + suffix_count)
+        }
+    }
+
+    fn create_edge(&mut self, u: usize, v: usize) -> NodePtr<Data<O::Element>> {
+        let node = Node::new(Data {
+            value: self.op.id(),
+            prod: self.op.id(),
+            count: 0,
+            is_vertex: false,
+            parent: None,
+        });
+        self.edges.insert((u, v), node);
+        node
+    }
+
+    /// nodeの部分木のうち，区間`[l, r)`の(総積, 頂点数)を返す．
+    fn fold_range(&self, node: Tour<O::Element>, l: usize, r: usize) -> (O::Element, usize) {
+        let raw = match node {
+            Some(node) if l < r => unsafe { node.as_ref() },
+            _ => return (self.op.id(), 0),
+        };
+        if l == 0 && r == raw.len {
+            return (raw.value.prod.clone(), raw.value.count);
+        }
+
+        let left_len = len(raw.left);
+        let (mut prod, mut count) = self.fold_range(raw.left, l, r.min(left_len));
+        if l <= left_len && left_len < r {
+            prod = self.op.op(&prod, &raw.value.value);
+            count += usize::from(raw.value.is_vertex);
+        }
+        let (right_prod, right_count) = self.fold_range(
+            raw.right,
+            l.saturating_sub(left_len + 1),
+            r.saturating_sub(left_len + 1),
+        );
+        (self.op.op(&prod, &right_prod), count + right_count)
+    }
+
+    /// [0, index)の部分木と[index, n)の部分木に分割する
+    fn split(&self, root: Tour<O::Element>, index: usize) -> (Tour<O::Element>, Tour<O::Element>) {
+        let (left, right) = avl_tree_vec::split(root, index, self);
+        detach(left);
+        detach(right);
+        (left, right)
+    }
+
+    /// 2つの木をマージして新たなrootを返す
+    fn merge(&self, left: Tour<O::Element>, right: Tour<O::Element>) -> Tour<O::Element> {
+        let root = avl_tree_vec::merge(left, right, self);
+        detach(root);
+        root
+    }
+}
+
+/// 木の根となったノードの親を外す．
+fn detach<T>(node: Link<Data<T>>) {
+    if let Some(mut node) = node {
+        unsafe { node.as_mut() }.value.parent = None;
+    }
+}
+
+/// ノードを含むAVL木の根を返す．
+fn root_of<T>(mut node: NodePtr<Data<T>>) -> NodePtr<Data<T>> {
+    while let Some(parent) = unsafe { node.as_ref() }.value.parent {
+        node = parent;
+    }
+    node
+}
+
+/// ノードの列上の位置を返す．
+fn index_of<T>(node: NodePtr<Data<T>>) -> usize {
+    let mut res = len(unsafe { node.as_ref() }.left);
+    let mut cur = node;
+    while let Some(parent) = unsafe { cur.as_ref() }.value.parent {
+        let raw = unsafe { parent.as_ref() };
+        if raw.right == Some(cur) {
+            res += len(raw.left) + 1;
+        }
+        cur = parent;
+    }
+    res
+}
+
+impl<O: CommutativeMonoid> Augment<Data<O::Element>> for EulerTourTree<O>
+where
+    O::Element: Clone,
+{
+    /// 子の情報からノードの総積と頂点数を更新し，子の親をノードにする．
+    fn update(&self, mut node: NodePtr<Data<O::Element>>) {
+        let raw = unsafe { node.as_mut() };
+        let mut prod = raw.value.value.clone();
+        let mut count = usize::from(raw.value.is_vertex);
+        for mut child in [raw.left, raw.right].into_iter().flatten() {
+            let child = &mut unsafe { child.as_mut() }.value;
+            prod = self.op.op(&prod, &child.prod);
+            count += child.count;
+            child.parent = Some(node);
+        }
+        raw.value.prod = prod;
+        raw.value.count = count;
+    }
+}
+
+impl<O: CommutativeMonoid> Drop for EulerTourTree<O> {
+    fn drop(&mut self) {
+        self.vertices.drain(..).for_each(free);
+        self.edges.drain().for_each(|(_, node)| free(node));
+    }
+}
+
+impl<O: CommutativeMonoid> From<(Vec<O::Element>, O)> for EulerTourTree<O>
+where
+    O::Element: Clone,
+{
+    fn from((values, op): (Vec<O::Element>, O)) -> Self {
+        let vertices = values
+            .into_iter()
+            .map(|value| {
+                Node::new(Data {
+                    prod: value.clone(),
+                    value,
+                    count: 1,
+                    is_vertex: true,
+                    parent: None,
+                })
+            })
+            .collect();
+        Self {
+            vertices,
+            edges: HashMap::new(),
+            op,
+        }
+    }
+}
+
+impl<O: CommutativeMonoid + Default> From<Vec<O::Element>> for EulerTourTree<O>
+where
+    O::Element: Clone,
+{
+    fn from(values: Vec<O::Element>) -> Self {
+        Self::from((values, O::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::*;
+    use crate::{ops::op_add::OpAdd, utils::test_utils::random::get_test_rng};
+
+    /// 辺`(v, p)`を通らずに頂点`v`から到達できる頂点を返す．
+    fn naive_reachable(adj: &[Vec<usize>], v: usize, p: usize) -> Vec<usize> {
+        let mut visited = vec![false; adj.len()];
+        visited[v] = true;
+        let mut stack = vec![v];
+        let mut res = vec![];
+        while let Some(x) = stack.pop() {
+            res.push(x);
+            for &y in &adj[x] {
+                if !visited[y] && (x, y) != (v, p) {
+                    visited[y] = true;
+                    stack.push(y);
+                }
+            }
+        }
+        res
+    }
+
+    #[test]
+    fn test() {
+        let mut ett = EulerTourTree::<OpAdd<i64>>::new(4);
+        assert_eq!(ett.component_size(0), 1);
+        ett.set(3, 5);
+        ett.link(3, 0);
+        ett.link(1, 3);
+        assert_eq!(ett.component_fold(0), 5);
+        assert_eq!(ett.component_size(1), 3);
+        assert_eq!(ett.subtree_size(3, 0), 2);
+        ett.reroot(1);
+        assert_eq!(ett.subtree_size(3, 0), 2);
+        assert_eq!(ett.subtree_size(0, 3), 1);
+        assert_eq!(ett.subtree_fold(3, 1), 5);
+        ett.cut(0, 3);
+        assert!(!ett.connected(0, 1));
+        assert!(ett.connected(3, 1));
+        assert_eq!(ett.get(3), &5);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_cut_missing_edge() {
+        let mut ett = EulerTourTree::<OpAdd<i64>>::new(3);
+        ett.link(0, 1);
+        ett.cut(0, 2);
+    }
+
+    #[test]
+    fn test_randomized() {
+        let mut rng = get_test_rng();
+
+        const T: usize = 50;
+        const N_MAX: usize = 20;
+        const Q: usize = 1000;
+
+        for _ in 0..T {
+            let n = rng.random_range(1..=N_MAX);
+            let mut naive = (0..n)
+                .map(|_| rng.random_range(-100..=100))
+                .collect::<Vec<i64>>();
+            let mut adj = vec![vec![]; n];
+            let mut edges = vec![];
+            let mut ett = EulerTourTree::<OpAdd<i64>>::from(naive.clone());
+
+            for _ in 0..Q {
+                let u = rng.random_range(0..n);
+                let v = rng.random_range(0..n);
+                let component = naive_reachable(&adj, u, usize::MAX);
+                assert_eq!(ett.connected(u, v), component.contains(&v));
+                match rng.random_range(0..5) {
+                    0 => {
+                        if !component.contains(&v) {
+                            ett.link(u, v);
+                            adj[u].push(v);
+                            adj[v].push(u);
+                            edges.push((u, v));
+                        }
+                    }
+                    1 if !edges.is_empty() => {
+                        let (a, b) = edges.swap_remove(rng.random_range(0..edges.len()));
+                        ett.cut(b, a);
+                        adj[a].retain(|&x| x != b);
+                        adj[b].retain(|&x| x != a);
+                    }
+                    2 => {
+                        if rng.random_ratio(1, 2) {
+                            ett.reroot(u);
+                        } else {
+                            let x = rng.random_range(-100..=100);
+                            ett.set(u, x);
+                            naive[u] = x;
+                        }
+                    }
+                    3 => {
+                        assert_eq!(ett.component_size(u), component.len());
+                        assert_eq!(
+                            ett.component_fold(u),
+                            component.iter().map(|&x| naive[x]).sum::<i64>()
+                        );
+                    }
+                    _ => {
+                        if let Some(&p) = adj[u].first() {
+                            let subtree = naive_reachable(&adj, u, p);
+                            assert_eq!(ett.subtree_size(u, p), subtree.len());
+                            assert_eq!(
+                                ett.subtree_fold(u, p),
+                                subtree.iter().map(|&x| naive[x]).sum::<i64>()
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -3,6 +3,9 @@
 //! (群をなす集合の要素からなる)配列を管理するデータ構造．
 //! 要素の1点更新と区間積の取得をO(logN)で行うことができる．
 //!
+//! 演算がモノイドの場合でも，1点への作用と接頭辞積(`prefix`, `bisect_prefix`)は利用できる．
+//! 区間加算・区間和には[`RangeAddFenwickTree`](crate::ds::range_add_fenwick_tree::RangeAddFenwickTree)を用いる．
+//!
 //! # 使用例
 //! ```
 //! use reprol::{ds::fenwick_tree::FenwickTree, ops::op_add::OpAdd};
@@ -13,6 +16,17 @@
 //! assert_eq!(ft.fold(..2), 5); // 区間[0, 2)の区間和
 //! assert_eq!(ft.fold(..=2), 8); // 区間[0, 2]の区間和
 //! assert_eq!(ft.fold(2..5), 5); // 区間[2, 5)の区間和
+//! assert_eq!(ft.bisect_prefix(|&s| s < 8), 2); // 接頭辞和が8以上となる最小のインデックス
+//! ```
+//!
+//! ```
+//! use reprol::{ds::fenwick_tree::FenwickTree, ops::op_max::OpMax};
+//! // モノイド(最大値)による接頭辞最大値
+//! let mut ft = FenwickTree::<OpMax<i32>>::new(5);
+//! ft.op(1, &5); // v[1] <- max(v[1], 5)
+//! ft.op(3, &7); // v[3] <- max(v[3], 7)
+//! assert_eq!(ft.prefix(3), 5); // 区間[0, 3)の最大値
+//! assert_eq!(ft.prefix(5), 7); // 区間[0, 5)の最大値
 //! ```
 
 use std::{
@@ -20,15 +34,18 @@ use std::{
     ops::{Range, RangeBounds},
 };
 
-use crate::{ops::group::Group, utils::normalize_range::normalize_index};
+use crate::{
+    ops::{group::Group, monoid::Monoid},
+    utils::normalize_range::normalize_index,
+};
 
 /// Fenwick Tree
-pub struct FenwickTree<O: Group> {
+pub struct FenwickTree<O: Monoid> {
     nodes: Vec<O::Element>,
     op: O,
 }
 
-impl<O: Group> FenwickTree<O> {
+impl<O: Monoid> FenwickTree<O> {
     /// 長さ`n`で初期化する．
     /// 要素はすべて単位元で初期化される．
     pub fn new(n: usize) -> Self
@@ -57,6 +74,55 @@ impl<O: Group> FenwickTree<O> {
         }
     }
 
+    /// 区間`[0, r)`の区間積を返す．
+    pub fn prefix(&self, mut r: usize) -> O::Element {
+        assert!(r <= self.nodes.len());
+        let mut res = self.op.id();
+        while r > 0 {
+            res = self.op.op(&self.nodes[r - 1], &res);
+            r -= r & r.wrapping_neg();
+        }
+        res
+    }
+
+    /// Fenwick Tree上の二分探索．
+    ///
+    /// `g(r) = f(prefix(r))`として，
+    /// 単調な`g`に対して，`g(r) = true`となる最大の`r`を返す．
+    ///
+    /// # 計算量
+    /// - O(log n)
+    ///
+    /// # 制約
+    /// - `f(identity()) = true`
+    pub fn bisect_prefix(&self, mut f: impl FnMut(&O::Element) -> bool) -> usize {
+        debug_assert!(f(&self.op.id()));
+
+        let n = self.nodes.len();
+        let mut r = 0;
+        let mut prod = self.op.id();
+
+        if n == 0 {
+            return 0;
+        }
+
+        let mut k = 1 << n.ilog2();
+        while k > 0 {
+            if r + k <= n {
+                let tmp = self.op.op(&prod, &self.nodes[r + k - 1]);
+                if f(&tmp) {
+                    prod = tmp;
+                    r += k;
+                }
+            }
+            k >>= 1;
+        }
+
+        r
+    }
+}
+
+impl<O: Group> FenwickTree<O> {
     /// `index`番目の要素の値を`value`にする．
     pub fn set(&mut self, index: usize, value: O::Element) {
         let diff = self.op.op(&value, &self.op.inv(&self.get(index)));
@@ -68,28 +134,17 @@ impl<O: Group> FenwickTree<O> {
         self.fold(index..=index)
     }
 
-    /// 区間`[0, r)`の区間積を返す．
-    fn prefix(&self, mut r: usize) -> O::Element {
-        assert!(r <= self.nodes.len());
-        let mut res = self.op.id();
-        while r > 0 {
-            res = self.op.op(&res, &self.nodes[r - 1]);
-            r -= r & r.wrapping_neg();
-        }
-        res
-    }
-
     /// 区間`[l, r)`の区間積を返す．
     pub fn fold(&self, range: impl RangeBounds<usize>) -> O::Element {
         let Range { start: l, end: r } = normalize_index(range, self.nodes.len());
         assert!(l <= r);
         let prefix_l = self.prefix(l);
         let prefix_r = self.prefix(r);
-        self.op.op(&prefix_r, &self.op.inv(&prefix_l))
+        self.op.op(&self.op.inv(&prefix_l), &prefix_r)
     }
 }
 
-impl<O: Group> From<(Vec<O::Element>, O)> for FenwickTree<O> {
+impl<O: Monoid> From<(Vec<O::Element>, O)> for FenwickTree<O> {
     fn from((v, op): (Vec<O::Element>, O)) -> Self {
         let mut res = Self::with_op(v.len(), op);
         v.into_iter()
@@ -99,25 +154,25 @@ impl<O: Group> From<(Vec<O::Element>, O)> for FenwickTree<O> {
     }
 }
 
-impl<O: Group, const N: usize> From<([O::Element; N], O)> for FenwickTree<O> {
+impl<O: Monoid, const N: usize> From<([O::Element; N], O)> for FenwickTree<O> {
     fn from((v, op): ([O::Element; N], O)) -> Self {
         Self::from((v.into_iter().collect::<Vec<_>>(), op))
     }
 }
 
-impl<O: Group + Default> From<Vec<O::Element>> for FenwickTree<O> {
+impl<O: Monoid + Default> From<Vec<O::Element>> for FenwickTree<O> {
     fn from(v: Vec<O::Element>) -> Self {
         Self::from((v, O::default()))
     }
 }
 
-impl<O: Group + Default, const N: usize> From<[O::Element; N]> for FenwickTree<O> {
+impl<O: Monoid + Default, const N: usize> From<[O::Element; N]> for FenwickTree<O> {
     fn from(v: [O::Element; N]) -> Self {
         Self::from((v, O::default()))
     }
 }
 
-impl<O: Group + Default> FromIterator<O::Element> for FenwickTree<O> {
+impl<O: Monoid + Default> FromIterator<O::Element> for FenwickTree<O> {
     fn from_iter<I: IntoIterator<Item = O::Element>>(iter: I) -> Self {
         Self::from(iter.into_iter().collect::<Vec<_>>())
     }
@@ -130,6 +185,7 @@ mod tests {
     use super::*;
     use crate::{
         ops::op_add::OpAdd,
+        ops::op_max::OpMax,
         ops::op_xor::OpXor,
         utils::test_utils::{dynamic_range_query::*, random::get_test_rng, static_range_query::*},
     };
@@ -156,6 +212,60 @@ mod tests {
         assert_eq!(ft.fold(3..4), 50);
     }
 
+    #[test]
+    fn test_prefix_max() {
+        let mut ft = FenwickTree::<OpMax<i64>>::new(6);
+        ft.op(2, &3);
+        ft.op(4, &10);
+        ft.op(1, &5);
+        assert_eq!(ft.prefix(0), i64::MIN);
+        assert_eq!(ft.prefix(2), 5);
+        assert_eq!(ft.prefix(4), 5);
+        assert_eq!(ft.prefix(6), 10);
+        assert_eq!(ft.bisect_prefix(|&x| x < 5), 1);
+        assert_eq!(ft.bisect_prefix(|&x| x < 10), 4);
+        assert_eq!(ft.bisect_prefix(|&x| x <= 10), 6);
+    }
+
+    #[test]
+    fn test_bisect_prefix() {
+        let ft = FenwickTree::<OpAdd<i64>>::from(vec![1, 0, 2, 3, 0, 4]);
+        assert_eq!(ft.bisect_prefix(|&s| s < 1), 0);
+        assert_eq!(ft.bisect_prefix(|&s| s <= 1), 2);
+        assert_eq!(ft.bisect_prefix(|&s| s < 6), 3);
+        assert_eq!(ft.bisect_prefix(|&s| s <= 6), 5);
+        assert_eq!(ft.bisect_prefix(|&s| s <= 10), 6);
+
+        let ft = FenwickTree::<OpAdd<i64>>::new(0);
+        assert_eq!(ft.bisect_prefix(|&s| s <= 10), 0);
+    }
+
+    #[test]
+    fn test_bisect_prefix_randomized() {
+        let mut rng = get_test_rng();
+
+        const T: usize = 100;
+        const Q: usize = 1000;
+        const N_MAX: usize = 100;
+
+        for _ in 0..T {
+            let n = rng.random_range(1..=N_MAX);
+            let v = (0..n)
+                .map(|_| rng.random_range(0..=100))
+                .collect::<Vec<i64>>();
+            let ft = FenwickTree::<OpAdd<i64>>::from(v.clone());
+
+            for _ in 0..Q {
+                let x = rng.random_range(0..=100 * n as i64);
+                let expected = (0..=n)
+                    .take_while(|&r| v[..r].iter().sum::<i64>() <= x)
+                    .last()
+                    .unwrap();
+                assert_eq!(ft.bisect_prefix(|&s| s <= x), expected);
+            }
+        }
+    }
+
     macro_rules! ft_randomized_static_range_sum_exhaustive_test {
         ($test_name: ident, $ty: ty, $range: expr) => {
             randomized_static_range_sum_exhaustive_test!(
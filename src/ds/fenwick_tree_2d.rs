@@ -0,0 +1,226 @@
+//! 2次元Fenwick Tree(2D Binary Indexed Tree)
+//!
+//! (可換群をなす集合の要素からなる)2次元配列を管理するデータ構造．
+//! 以下の操作をいずれも O(log h × log w) で処理できる．
+//! - 要素の1点更新．
+//! - 任意の長方形区間の要素の総積(和，xorなど)の取得．
+//!
+//! [`SegmentTree2d`](crate::ds::segment_tree_2d::SegmentTree2d)より省メモリかつ高速である．
+//!
+//! # 使用例
+//! ```
+//! use reprol::{ds::fenwick_tree_2d::FenwickTree2d, ops::op_add::OpAdd};
+//!
+//! let mut ft = FenwickTree2d::<OpAdd<i64>>::new(3, 4);
+//! ft.op(0, 1, &5); // v[0][1] += 5
+//! ft.op(2, 3, &7); // v[2][3] += 7
+//! ft.op(1, 1, &2); // v[1][1] += 2
+//! assert_eq!(ft.fold(.., ..), 14);
+//! assert_eq!(ft.fold(0..2, 1..2), 7);
+//! assert_eq!(ft.fold(1..=2, 1..), 9);
+//! ft.set(0, 1, 1);
+//! assert_eq!(ft.get(0, 1), 1);
+//! ```
+
+use std::ops::{Range, RangeBounds};
+
+use crate::{ops::group::Group, utils::normalize_range::normalize_index};
+
+/// 2次元Fenwick Tree
+pub struct FenwickTree2d<O: Group> {
+    /// 行の長さ
+    len_rows: usize,
+
+    /// 列の長さ
+    len_cols: usize,
+
+    /// Fenwick Treeを構成するノード(1次元に平坦化)
+    nodes: Vec<O::Element>,
+
+    /// 演算(群)
+    op: O,
+}
+
+impl<O: Group> FenwickTree2d<O> {
+    /// 高さ`h`，幅`w`で初期化する．
+    /// 要素はすべて単位元で初期化される．
+    pub fn new(h: usize, w: usize) -> Self
+    where
+        O: Default,
+    {
+        Self::with_op(h, w, O::default())
+    }
+
+    /// 演算`op`を指定して高さ`h`，幅`w`で初期化する．
+    pub fn with_op(h: usize, w: usize, op: O) -> Self {
+        Self {
+            len_rows: h,
+            len_cols: w,
+            nodes: (0..h * w).map(|_| op.id()).collect(),
+            op,
+        }
+    }
+
+    #[inline(always)]
+    fn idx(&self, i: usize, j: usize) -> usize {
+        i * self.len_cols + j
+    }
+
+    /// (`i`, `j`)番目の要素に`rhs`を作用させる．
+    /// `v[i][j] <- v[i][j] * rhs`
+    pub fn op(&mut self, i: usize, j: usize, rhs: &O::Element) {
+        assert!(i < self.len_rows && j < self.len_cols);
+        let mut i = i + 1;
+        while i <= self.len_rows {
+            let mut j = j + 1;
+            while j <= self.len_cols {
+                let index = self.idx(i - 1, j - 1);
+                self.nodes[index] = self.op.op(&self.nodes[index], rhs);
+                j += j & j.wrapping_neg();
+            }
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    /// (`i`, `j`)番目の要素の値を`value`にする．
+    pub fn set(&mut self, i: usize, j: usize, value: O::Element) {
+        let diff = self.op.op(&value, &self.op.inv(&self.get(i, j)));
+        self.op(i, j, &diff);
+    }
+
+    /// (`i`, `j`)番目の要素の値を返す．
+    pub fn get(&self, i: usize, j: usize) -> O::Element {
+        self.fold(i..=i, j..=j)
+    }
+
+    /// 区間`[0, i) x [0, j)`の総積を返す．
+    pub fn prefix(&self, i: usize, j: usize) -> O::Element {
+        assert!(i <= self.len_rows && j <= self.len_cols);
+        let mut res = self.op.id();
+        let mut i = i;
+        while i > 0 {
+            let mut j = j;
+            while j > 0 {
+                res = self.op.op(&res, &self.nodes[self.idx(i - 1, j - 1)]);
+                j -= j & j.wrapping_neg();
+            }
+            i -= i & i.wrapping_neg();
+        }
+        res
+    }
+
+    /// 区間`[row_range] x [col_range]`の総積を返す．
+    pub fn fold(
+        &self,
+        row_range: impl RangeBounds<usize>,
+        col_range: impl RangeBounds<usize>,
+    ) -> O::Element {
+        let Range { start: il, end: ir } = normalize_index(row_range, self.len_rows);
+        let Range { start: jl, end: jr } = normalize_index(col_range, self.len_cols);
+        let pos = self.op.op(&self.prefix(ir, jr), &self.prefix(il, jl));
+        let neg = self.op.op(&self.prefix(il, jr), &self.prefix(ir, jl));
+        self.op.op(&pos, &self.op.inv(&neg))
+    }
+}
+
+impl<O: Group> From<(Vec<Vec<O::Element>>, O)> for FenwickTree2d<O> {
+    fn from((v, op): (Vec<Vec<O::Element>>, O)) -> Self {
+        let h = v.len();
+        let w = v.first().map_or(0, |vi| vi.len());
+        debug_assert!(v.iter().all(|vi| vi.len() == w));
+
+        let mut res = Self::with_op(h, w, op);
+        for (i, vi) in v.into_iter().enumerate() {
+            for (j, vij) in vi.into_iter().enumerate() {
+                res.op(i, j, &vij);
+            }
+        }
+        res
+    }
+}
+
+impl<O: Group, const N: usize, const M: usize> From<([[O::Element; M]; N], O)>
+    for FenwickTree2d<O>
+{
+    fn from((v, op): ([[O::Element; M]; N], O)) -> Self {
+        let v: Vec<Vec<_>> = v.into_iter().map(|vi| vi.into_iter().collect()).collect();
+        Self::from((v, op))
+    }
+}
+
+impl<O: Group + Default> From<Vec<Vec<O::Element>>> for FenwickTree2d<O> {
+    fn from(v: Vec<Vec<O::Element>>) -> Self {
+        Self::from((v, O::default()))
+    }
+}
+
+impl<O: Group + Default, const N: usize, const M: usize> From<[[O::Element; M]; N]>
+    for FenwickTree2d<O>
+{
+    fn from(v: [[O::Element; M]; N]) -> Self {
+        Self::from((v, O::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::*;
+    use crate::{
+        ops::{op_add::OpAdd, op_xor::OpXor},
+        utils::test_utils::{dynamic_range_query_2d::*, random::get_test_rng},
+    };
+
+    #[test]
+    fn test_2d_add() {
+        let a = vec![vec![1, 2, 3, 4], vec![5, 6, 7, 8], vec![9, 10, 11, 12]];
+
+        let mut ft = FenwickTree2d::<OpAdd<i64>>::from(a);
+
+        assert_eq!(ft.fold(.., ..), 78);
+        assert_eq!(ft.fold(1..3, 1..4), 54);
+        assert_eq!(ft.fold(0..1, 0..2), 3);
+        assert_eq!(ft.fold(1..1, ..), 0);
+
+        ft.set(0, 0, 100);
+        assert_eq!(ft.get(0, 0), 100);
+        assert_eq!(ft.fold(0..1, 0..2), 102);
+
+        ft.op(2, 3, &-12);
+        assert_eq!(ft.get(2, 3), 0);
+        assert_eq!(ft.prefix(3, 4), 165);
+    }
+
+    #[test]
+    fn test_2d_xor() {
+        let a = [[1u32, 2, 4], [8, 16, 32]];
+        let ft = FenwickTree2d::<OpXor<u32>>::from(a);
+        assert_eq!(ft.fold(.., ..), 63);
+        assert_eq!(ft.fold(0..2, 1..3), 54);
+        assert_eq!(ft.get(1, 0), 8);
+    }
+
+    randomized_point_set_range_sum_2d_test!(
+        test_randomized_point_set_range_sum_2d_i64,
+        i64,
+        FenwickTree2d::<OpAdd<i64>>::from,
+        |ds: &FenwickTree2d<_>, r, c| ds.fold(r, c),
+        |ds: &mut FenwickTree2d<_>, i, j, val| ds.set(i, j, val),
+        50,
+        1000,
+        20,
+        -1000000000..=1000000000
+    );
+
+    randomized_point_set_range_xor_2d_test!(
+        test_randomized_point_set_range_xor_2d_u64,
+        u64,
+        FenwickTree2d::<OpXor<u64>>::from,
+        |ds: &FenwickTree2d<_>, r, c| ds.fold(r, c),
+        |ds: &mut FenwickTree2d<_>, i, j, val| ds.set(i, j, val),
+        10,
+        1000,
+        20
+    );
+}
@@ -0,0 +1,212 @@
+//! D次元いもす法(ImosNd)
+//!
+//! 群のD次元配列に対する直方体領域への加算と最終値の取得を行うデータ構造．
+//! 次元数をconst genericsで指定し，`add`で直方体領域に加算して最後に`build`を呼び出すことで各要素の値が得られる．
+//! 整数の加算に限らず，xorや[`ModInt`](crate::math::modint::ModInt)などの可換群で使える．
+//!
+//! # 計算量
+//! - `add`: O(2^D)
+//! - `build`: O(D Π(n_i + 1))
+//!
+//! # 使用例
+//! ```
+//! use reprol::{ds::imos_nd::ImosNd, ops::op_add::OpAdd};
+//!
+//! let mut imos = ImosNd::<OpAdd<i64>, 3>::new([2, 3, 4]);
+//! imos.add([0..2, 1..3, 0..2], 5);
+//! imos.add([1..2, 0..3, 1..4], -1);
+//! imos.build();
+//! assert_eq!(*imos.get([0, 1, 1]), 5);
+//! assert_eq!(*imos.get([1, 2, 1]), 4);
+//! assert_eq!(imos[[1, 0, 3]], -1);
+//! ```
+
+use std::ops::{Index, Range};
+
+use crate::{ds::cumulative_array_nd::strides, ops::group::Group};
+
+/// D次元配列上の直方体領域への加算を管理するいもす法
+pub struct ImosNd<O: Group, const D: usize> {
+    shape: [usize; D],
+
+    /// 各次元の長さに1を足した配列におけるストライド
+    strides: [usize; D],
+
+    imos: Vec<O::Element>,
+    has_built: bool,
+    op: O,
+}
+
+impl<O: Group, const D: usize> ImosNd<O, D> {
+    /// 各次元の長さが`shape`で，単位元で初期化されたテーブルを構築する．
+    pub fn new(shape: [usize; D]) -> Self
+    where
+        O: Default,
+    {
+        Self::with_op(shape, O::default())
+    }
+
+    /// 演算`op`を明示的に渡してテーブルを構築する．
+    pub fn with_op(shape: [usize; D], op: O) -> Self {
+        let strides = strides(&shape);
+        let len = shape.iter().map(|&n| n + 1).product::<usize>();
+        Self {
+            shape,
+            strides,
+            imos: (0..len).map(|_| op.id()).collect(),
+            has_built: false,
+            op,
+        }
+    }
+
+    /// 各次元の長さを返す．
+    pub fn shape(&self) -> [usize; D] {
+        self.shape
+    }
+
+    /// 直方体領域`ranges[0] x ... x ranges[D - 1]`に`value`を加算する．
+    ///
+    /// `build`前にのみ呼び出せる．
+    pub fn add(&mut self, ranges: [Range<usize>; D], value: O::Element) {
+        assert!(!self.has_built);
+        for (range, &n) in ranges.iter().zip(&self.shape) {
+            assert!(range.start <= range.end && range.end <= n);
+        }
+
+        let inv = self.op.inv(&value);
+        for mask in 0..1usize << D {
+            let mut p = 0;
+            for (d, range) in ranges.iter().enumerate() {
+                let i = if mask >> d & 1 == 1 {
+                    range.end
+                } else {
+                    range.start
+                };
+                p += i * self.strides[d];
+            }
+            let x = if mask.count_ones() % 2 == 0 {
+                &value
+            } else {
+                &inv
+            };
+            self.imos[p] = self.op.op(&self.imos[p], x);
+        }
+    }
+
+    /// いもす法の前計算を行い，各要素の値を確定させる．
+    pub fn build(&mut self) {
+        assert!(!self.has_built);
+
+        for (d, &stride) in self.strides.iter().enumerate() {
+            for p in 0..self.imos.len() {
+                if p / stride % (self.shape[d] + 1) > 0 {
+                    self.imos[p] = self.op.op(&self.imos[p - stride], &self.imos[p]);
+                }
+            }
+        }
+
+        self.has_built = true;
+    }
+
+    /// `build`後に位置`index`の値を返す．
+    pub fn get(&self, index: [usize; D]) -> &O::Element {
+        assert!(self.has_built);
+        for (&i, &n) in index.iter().zip(&self.shape) {
+            assert!(i < n);
+        }
+        let p = index
+            .iter()
+            .zip(&self.strides)
+            .map(|(&i, &stride)| i * stride)
+            .sum::<usize>();
+        &self.imos[p]
+    }
+}
+
+impl<O: Group, const D: usize> Index<[usize; D]> for ImosNd<O, D> {
+    type Output = O::Element;
+    fn index(&self, index: [usize; D]) -> &Self::Output {
+        self.get(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::*;
+    use crate::{
+        ops::{op_add::OpAdd, op_xor::OpXor},
+        utils::test_utils::random::get_test_rng,
+    };
+
+    #[test]
+    fn test_basic() {
+        let mut imos = ImosNd::<OpAdd<i64>, 2>::new([3, 4]);
+        imos.add([0..2, 1..3], 2);
+        imos.add([1..3, 2..4], -1);
+        imos.build();
+        let expected = [[0, 2, 2, 0], [0, 2, 1, -1], [0, 0, -1, -1]];
+        for i in 0..3 {
+            for j in 0..4 {
+                assert_eq!(imos[[i, j]], expected[i][j]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_3d() {
+        let mut rng = get_test_rng();
+
+        for _ in 0..50 {
+            let shape = [
+                rng.random_range(1..=6),
+                rng.random_range(1..=6),
+                rng.random_range(1..=6),
+            ];
+            let mut imos = ImosNd::<OpXor<u32>, 3>::new(shape);
+            let mut naive = vec![vec![vec![0u32; shape[2]]; shape[1]]; shape[0]];
+
+            for _ in 0..50 {
+                let ranges = shape.map(|n| {
+                    let l = rng.random_range(0..=n);
+                    l..rng.random_range(l..=n)
+                });
+                let value = rng.random();
+                imos.add(ranges.clone(), value);
+                for i in ranges[0].clone() {
+                    for j in ranges[1].clone() {
+                        for k in ranges[2].clone() {
+                            naive[i][j][k] ^= value;
+                        }
+                    }
+                }
+            }
+
+            imos.build();
+
+            for i in 0..shape[0] {
+                for j in 0..shape[1] {
+                    for k in 0..shape[2] {
+                        assert_eq!(imos[[i, j, k]], naive[i][j][k]);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_add_after_build() {
+        let mut imos = ImosNd::<OpAdd<i64>, 2>::new([3, 3]);
+        imos.build();
+        imos.add([0..2, 0..2], 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_get_before_build() {
+        let imos = ImosNd::<OpAdd<i64>, 2>::new([3, 3]);
+        let _ = imos.get([0, 0]);
+    }
+}
@@ -0,0 +1,308 @@
+//! 両端優先度付きキュー(Interval Heap)
+//!
+//! 最小値と最大値の両方を取り出せる優先度付きキュー．
+//! 各ノードが2つの要素からなる区間`[lo, hi]`を持ち，子の区間が親の区間に含まれるように管理する．
+//! 以下の操作をいずれも O(log n) で処理できる．
+//! - 要素の追加．
+//! - 最小値・最大値の削除．
+//!
+//! 最小値・最大値の取得は O(1) で処理できる．
+//!
+//! # 使用例
+//! ```
+//! use reprol::ds::interval_heap::IntervalHeap;
+//!
+//! let mut heap = IntervalHeap::from([3, 1, 4, 1, 5]);
+//! assert_eq!(heap.peek_min(), Some(&1));
+//! assert_eq!(heap.peek_max(), Some(&5));
+//! assert_eq!(heap.pop_max(), Some(5));
+//! assert_eq!(heap.pop_min(), Some(1));
+//! assert_eq!(heap.pop_min(), Some(1));
+//! assert_eq!(heap.len(), 2);
+//! ```
+//!
+//! # 問題例
+//! - [Double-Ended Priority Queue](https://judge.yosupo.jp/problem/double_ended_priority_queue)
+
+use std::iter::FromIterator;
+
+/// 両端優先度付きキュー
+///
+/// ノード`k`は`data[2k]`(区間の下端)と`data[2k + 1]`(区間の上端)からなる．
+#[derive(Clone)]
+pub struct IntervalHeap<T> {
+    data: Vec<T>,
+}
+
+impl<T: Ord> IntervalHeap<T> {
+    /// 空のキューを生成する．
+    pub fn new() -> Self {
+        Self { data: Vec::new() }
+    }
+
+    /// 要素数を返す．
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// キューが空かを返す．
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// すべての要素を削除する．
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+
+    /// 最小値を返す．
+    pub fn peek_min(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    /// 最大値を返す．
+    pub fn peek_max(&self) -> Option<&T> {
+        self.data.get(1).or_else(|| self.data.first())
+    }
+
+    /// 要素を追加する．
+    pub fn push(&mut self, value: T) {
+        self.data.push(value);
+        let i = self.data.len() - 1;
+        if i % 2 == 1 {
+            if self.data[i - 1] > self.data[i] {
+                self.data.swap(i - 1, i);
+                self.up(i - 1, false);
+            } else {
+                self.up(i, true);
+            }
+        } else if i > 0 {
+            let parent = Self::parent(i);
+            if self.data[i] < self.data[parent] {
+                self.up(i, false);
+            } else if self.data[i] > self.data[parent + 1] {
+                self.up(i, true);
+            }
+        }
+    }
+
+    /// 最小値を削除して返す．
+    pub fn pop_min(&mut self) -> Option<T> {
+        if self.data.is_empty() {
+            return None;
+        }
+        let res = self.data.swap_remove(0);
+        self.down_min();
+        Some(res)
+    }
+
+    /// 最大値を削除して返す．
+    pub fn pop_max(&mut self) -> Option<T> {
+        if self.data.len() <= 1 {
+            return self.data.pop();
+        }
+        let res = self.data.swap_remove(1);
+        self.down_max();
+        Some(res)
+    }
+
+    /// 要素を昇順に並べた`Vec`を返す．
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        let mut data = self.data;
+        data.sort_unstable();
+        data
+    }
+
+    /// 位置`i`の要素を含むノードの親ノードの下端の位置を返す．
+    #[inline]
+    fn parent(i: usize) -> usize {
+        ((i / 2 - 1) / 2) * 2
+    }
+
+    /// 位置`i`の要素を，下端(`is_max = false`)または上端(`is_max = true`)の列に沿って上に移動する．
+    fn up(&mut self, mut i: usize, is_max: bool) {
+        while i >= 2 {
+            let p = Self::parent(i) + is_max as usize;
+            let should_swap = if is_max {
+                self.data[i] > self.data[p]
+            } else {
+                self.data[i] < self.data[p]
+            };
+            if !should_swap {
+                break;
+            }
+            self.data.swap(i, p);
+            i = p;
+        }
+    }
+
+    /// 根の下端の要素を下に移動する．
+    fn down_min(&mut self) {
+        let n = self.data.len();
+        let mut i = 0;
+        loop {
+            if i + 1 < n && self.data[i] > self.data[i + 1] {
+                self.data.swap(i, i + 1);
+            }
+            let (c1, c2) = (2 * i + 2, 2 * i + 4);
+            let c = match (c1 < n, c2 < n) {
+                (true, true) if self.data[c2] < self.data[c1] => c2,
+                (true, _) => c1,
+                _ => break,
+            };
+            if self.data[c] >= self.data[i] {
+                break;
+            }
+            self.data.swap(i, c);
+            i = c;
+        }
+    }
+
+    /// 根の上端の要素を下に移動する．
+    fn down_max(&mut self) {
+        let n = self.data.len();
+        let mut i = 1;
+        while i < n {
+            if self.data[i - 1] > self.data[i] {
+                self.data.swap(i - 1, i);
+            }
+            // 子ノードの上端(要素が1つのノードでは下端)
+            let child = |c: usize| {
+                if c + 1 < n {
+                    Some(c + 1)
+                } else if c < n {
+                    Some(c)
+                } else {
+                    None
+                }
+            };
+            let c = match (child(2 * i), child(2 * i + 2)) {
+                (Some(c1), Some(c2)) if self.data[c2] > self.data[c1] => c2,
+                (Some(c1), _) => c1,
+                _ => break,
+            };
+            if self.data[c] <= self.data[i] {
+                break;
+            }
+            self.data.swap(i, c);
+            i = c;
+            if i % 2 == 0 {
+                break;
+            }
+        }
+    }
+}
+
+impl<T: Ord> Default for IntervalHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> Extend<T> for IntervalHeap<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|x| self.push(x));
+    }
+}
+
+impl<T: Ord> FromIterator<T> for IntervalHeap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut res = Self::new();
+        res.extend(iter);
+        res
+    }
+}
+
+impl<T: Ord> From<Vec<T>> for IntervalHeap<T> {
+    fn from(v: Vec<T>) -> Self {
+        Self::from_iter(v)
+    }
+}
+
+impl<T: Ord, const N: usize> From<[T; N]> for IntervalHeap<T> {
+    fn from(array: [T; N]) -> Self {
+        Self::from_iter(array)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use rand::Rng;
+
+    use super::*;
+    use crate::utils::test_utils::random::get_test_rng;
+
+    #[test]
+    fn test() {
+        let mut heap = IntervalHeap::new();
+        assert_eq!(heap.pop_min(), None);
+        assert_eq!(heap.pop_max(), None);
+        assert_eq!(heap.peek_max(), None);
+
+        heap.push(2);
+        assert_eq!(heap.peek_min(), Some(&2));
+        assert_eq!(heap.peek_max(), Some(&2));
+        heap.push(1);
+        heap.push(3);
+        assert_eq!(heap.pop_max(), Some(3));
+        assert_eq!(heap.pop_max(), Some(2));
+        assert_eq!(heap.pop_max(), Some(1));
+        assert!(heap.is_empty());
+
+        let heap = IntervalHeap::from(vec![5, 2, 8, 2]);
+        assert_eq!(heap.into_sorted_vec(), vec![2, 2, 5, 8]);
+    }
+
+    #[test]
+    fn test_randomized() {
+        let mut rng = get_test_rng();
+
+        for _ in 0..100 {
+            let mut heap = IntervalHeap::new();
+            let mut naive = BTreeMap::new();
+            let mut len = 0;
+
+            for _ in 0..1000 {
+                match rng.random_range(0..5) {
+                    0 | 1 => {
+                        let x = rng.random_range(0..50);
+                        heap.push(x);
+                        *naive.entry(x).or_insert(0) += 1;
+                        len += 1;
+                    }
+                    2 => {
+                        let expected = naive.first_entry().map(|mut e| {
+                            *e.get_mut() -= 1;
+                            let x = *e.key();
+                            if *e.get() == 0 {
+                                e.remove();
+                            }
+                            x
+                        });
+                        len -= expected.is_some() as usize;
+                        assert_eq!(heap.pop_min(), expected);
+                    }
+                    3 => {
+                        let expected = naive.last_entry().map(|mut e| {
+                            *e.get_mut() -= 1;
+                            let x = *e.key();
+                            if *e.get() == 0 {
+                                e.remove();
+                            }
+                            x
+                        });
+                        len -= expected.is_some() as usize;
+                        assert_eq!(heap.pop_max(), expected);
+                    }
+                    _ => {
+                        assert_eq!(heap.peek_min(), naive.keys().next());
+                        assert_eq!(heap.peek_max(), naive.keys().next_back());
+                    }
+                }
+                assert_eq!(heap.len(), len);
+            }
+        }
+    }
+}
@@ -2,6 +2,9 @@
 //!
 //! 半開区間`[l, r)`を互いに素になるよう管理し，同じ値で連続する部分は自動的に併合される．
 //! 区間の一部を削除すると自動で分割され，常に重ならない列として保持される．
+//! 区間に含まれる部分の値を一括で更新することもできる．
+//!
+//! `IntervalSet`は値を持たない区間の集合で，mexや補集合・和集合・共通部分を求められる．
 //!
 //! # 使用例
 //! ```
@@ -19,12 +22,31 @@
 //! set.insert(4..6);
 //! assert_eq!(set.iter().map(|itv| (itv.start(), itv.end())).collect::<Vec<_>>(), vec![(1, 6)]);
 //! assert_eq!(set.remove(2..3), vec![Interval::new(2..3)]);
+//!
+//! // [2, 7)に含まれる部分の値に1を足す
+//! let mut map = IntervalMap::new();
+//! map.insert(0..4, 0);
+//! map.insert(6..9, 1);
+//! map.update(2..7, |v| v + 1);
+//! assert_eq!(
+//!     map.iter().map(|(itv, v)| (itv.start(), itv.end(), *v)).collect::<Vec<_>>(),
+//!     vec![(0, 2, 0), (2, 4, 1), (6, 7, 2), (7, 9, 1)]
+//! );
+//! assert_eq!(map.overlapping(3..7).count(), 2);
+//! assert_eq!(map.covered_length(1..8), 5);
+//!
+//! let mut set = IntervalSet::new();
+//! set.insert(0..3);
+//! set.insert(4..6);
+//! assert_eq!(set.mex(), 3);
+//! assert_eq!(set.first_gap_from(4), 6);
+//! assert_eq!(set.complement(0..8).iter().collect::<Vec<_>>(), vec![&Interval::new(3..4), &Interval::new(6..8)]);
 //! ```
 
 use std::{
     collections::BTreeMap,
     fmt::Debug,
-    iter::FusedIterator,
+    iter::{FusedIterator, Sum},
     ops::{Bound, Range, RangeBounds, Sub},
 };
 
@@ -299,6 +321,65 @@ impl<K: Clone + Ord, V: Clone + PartialEq> IntervalMap<K, V> {
         None
     }
 
+    /// 点`x`を含む区間と値を返す．
+    pub fn get(&self, x: &K) -> Option<(&Interval<K>, &V)> {
+        let point = Interval::new(x.clone()..x.clone());
+
+        if let Some((interval, v)) = self.inner.range(..point.clone()).next_back()
+            && interval.contains(x)
+        {
+            return Some((interval, v));
+        }
+
+        self.inner
+            .range(point..)
+            .next()
+            .filter(|(interval, _)| interval.contains(x))
+    }
+
+    /// 指定した区間と重なる区間と値を，左端の昇順に返すイテレータを返す．
+    /// 区間は切り取られず，保持しているものがそのまま返される．
+    pub fn overlapping(
+        &self,
+        range: Range<K>,
+    ) -> impl DoubleEndedIterator<Item = (&Interval<K>, &V)> + '_ {
+        let target = Interval::new(range);
+
+        let (prev, end) = if target.is_empty() {
+            (None, target.start_point_inteval())
+        } else {
+            let prev = self
+                .inner
+                .range(..target.start_point_inteval())
+                .next_back()
+                .filter(|(interval, _)| interval.end() > target.start());
+            (prev, target.end_point_inteval())
+        };
+
+        prev.into_iter()
+            .chain(self.inner.range(target.start_point_inteval()..end))
+    }
+
+    /// 指定した区間に含まれる部分の値を`f`で更新する．
+    /// 区間の境界で自動的に分割され，更新後に同じ値で隣接する区間は結合される．
+    /// 値が割り当てられていない部分は変更されない．
+    pub fn update(&mut self, range: Range<K>, mut f: impl FnMut(&V) -> V) {
+        for (interval, v) in self.remove(range) {
+            self.insert(interval.into_range(), f(&v));
+        }
+    }
+
+    /// 指定した区間のうち，いずれかの区間に含まれる部分の長さの総和を返す．
+    pub fn covered_length(&self, range: Range<K>) -> K
+    where
+        K: Sub<Output = K> + Sum,
+    {
+        let target = Interval::new(range);
+        self.overlapping(target.clone().into_range())
+            .map(|(interval, _)| interval.intersect(&target).len())
+            .sum()
+    }
+
     pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&Interval<K>, &V)> + '_ {
         self.inner.iter()
     }
@@ -369,6 +450,90 @@ impl<K: Clone + Ord> IntervalSet<K> {
     pub fn superset_of(&self, range: Range<K>) -> Option<&Interval<K>> {
         self.0.superset_of(range).map(|(interval, _)| interval)
     }
+
+    /// 点`x`を含む区間への参照を返す．
+    pub fn get(&self, x: &K) -> Option<&Interval<K>> {
+        self.0.get(x).map(|(interval, _)| interval)
+    }
+
+    /// 点`x`が集合に含まれるかを返す．
+    pub fn contains(&self, x: &K) -> bool {
+        self.get(x).is_some()
+    }
+
+    /// 指定した区間と重なる区間を，左端の昇順に返すイテレータを返す．
+    pub fn overlapping(&self, range: Range<K>) -> impl DoubleEndedIterator<Item = &Interval<K>> {
+        self.0.overlapping(range).map(|(interval, _)| interval)
+    }
+
+    /// 指定した区間のうち，集合に含まれる部分の長さを返す．
+    pub fn covered_length(&self, range: Range<K>) -> K
+    where
+        K: Sub<Output = K> + Sum,
+    {
+        self.0.covered_length(range)
+    }
+
+    /// `x`以上で集合に含まれない最小の値を返す．
+    pub fn first_gap_from(&self, x: K) -> K {
+        match self.get(&x) {
+            Some(interval) => interval.end(),
+            None => x,
+        }
+    }
+
+    /// `K::default()`以上で集合に含まれない最小の値を返す．
+    pub fn mex(&self) -> K
+    where
+        K: Default,
+    {
+        self.first_gap_from(K::default())
+    }
+
+    /// 区間`range`のうち，集合に含まれない部分からなる集合を返す．
+    pub fn complement(&self, range: Range<K>) -> Self {
+        let universe = Interval::new(range);
+        let mut res = Self::new();
+        let mut cur = universe.start();
+        for interval in self.overlapping(universe.clone().into_range()) {
+            res.insert(cur..interval.start());
+            cur = interval.end();
+        }
+        res.insert(cur..universe.end());
+        res
+    }
+
+    /// 2つの集合の和集合を返す．
+    pub fn union(&self, other: &Self) -> Self {
+        let (mut res, other) = if self.len() >= other.len() {
+            (self.clone(), other)
+        } else {
+            (other.clone(), self)
+        };
+        for interval in other {
+            res.insert(interval.clone().into_range());
+        }
+        res
+    }
+
+    /// 2つの集合の共通部分を返す．
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut res = Self::new();
+        let mut lhs = self.iter().peekable();
+        let mut rhs = other.iter().peekable();
+        while let (Some(a), Some(b)) = (lhs.peek(), rhs.peek()) {
+            let interval = a.intersect(b);
+            if !interval.is_empty() {
+                res.0.inner.insert(interval, ());
+            }
+            if a.end() <= b.end() {
+                lhs.next();
+            } else {
+                rhs.next();
+            }
+        }
+        res
+    }
 }
 
 impl<K> IntervalSet<K> {
@@ -566,4 +731,130 @@ mod tests {
             assert_eq!(set.superset_of(3..5), None);
         }
     }
+
+    mod random {
+        use rand::Rng;
+
+        use super::*;
+        use crate::utils::test_utils::random::get_test_rng;
+
+        const N: usize = 30;
+
+        fn random_range(rng: &mut impl Rng) -> Range<usize> {
+            let l = rng.random_range(0..=N);
+            let r = rng.random_range(0..=N);
+            l.min(r)..l.max(r)
+        }
+
+        fn to_naive<V: Clone>(map: &IntervalMap<usize, V>) -> Vec<Option<V>> {
+            let mut naive = vec![None; N];
+            for (interval, v) in map {
+                naive[interval.start()..interval.end()].fill(Some(v.clone()));
+            }
+            naive
+        }
+
+        fn to_naive_set(set: &IntervalSet<usize>) -> Vec<bool> {
+            (0..N).map(|x| set.contains(&x)).collect()
+        }
+
+        fn random_set(rng: &mut impl Rng) -> (IntervalSet<usize>, Vec<bool>) {
+            let mut set = IntervalSet::new();
+            let mut naive = vec![false; N];
+            for _ in 0..rng.random_range(0..6) {
+                let range = random_range(rng);
+                naive[range.clone()].fill(true);
+                set.insert(range);
+            }
+            (set, naive)
+        }
+
+        #[test]
+        fn test_map_randomized() {
+            let mut rng = get_test_rng();
+
+            for _ in 0..200 {
+                let mut map = IntervalMap::new();
+                let mut naive: Vec<Option<u32>> = vec![None; N];
+
+                for _ in 0..100 {
+                    let range = random_range(&mut rng);
+                    match rng.random_range(0..4) {
+                        0 => {
+                            let v = rng.random_range(0..3);
+                            naive[range.clone()].fill(Some(v));
+                            map.insert(range, v);
+                        }
+                        1 => {
+                            naive[range.clone()].fill(None);
+                            map.remove(range);
+                        }
+                        2 => {
+                            naive[range.clone()]
+                                .iter_mut()
+                                .for_each(|v| *v = v.map(|v| (v + 1) % 3));
+                            map.update(range, |v| (v + 1) % 3);
+                        }
+                        _ => {
+                            let expected = naive[range.clone()].iter().flatten().count();
+                            assert_eq!(map.covered_length(range.clone()), expected);
+
+                            let overlapping = map.overlapping(range.clone()).collect::<Vec<_>>();
+                            let expected = map
+                                .iter()
+                                .filter(|(interval, _)| {
+                                    !range.is_empty()
+                                        && interval.start() < range.end
+                                        && range.start < interval.end()
+                                })
+                                .collect::<Vec<_>>();
+                            assert_eq!(overlapping, expected);
+                        }
+                    }
+
+                    assert_eq!(to_naive(&map), naive);
+                    assert!(
+                        map.iter()
+                            .zip(map.iter().skip(1))
+                            .all(|((a, u), (b, v))| a.end() < b.start() || u != v)
+                    );
+                }
+            }
+        }
+
+        #[test]
+        fn test_set_randomized() {
+            let mut rng = get_test_rng();
+
+            for _ in 0..500 {
+                let (lhs, x) = random_set(&mut rng);
+                let (rhs, y) = random_set(&mut rng);
+
+                let union = lhs.union(&rhs);
+                let intersection = lhs.intersection(&rhs);
+                assert_eq!(
+                    to_naive_set(&union),
+                    (0..N).map(|i| x[i] | y[i]).collect::<Vec<_>>()
+                );
+                assert_eq!(
+                    to_naive_set(&intersection),
+                    (0..N).map(|i| x[i] & y[i]).collect::<Vec<_>>()
+                );
+
+                let range = random_range(&mut rng);
+                let complement = lhs.complement(range.clone());
+                assert_eq!(
+                    to_naive_set(&complement),
+                    (0..N)
+                        .map(|i| range.contains(&i) && !x[i])
+                        .collect::<Vec<_>>()
+                );
+
+                let from = rng.random_range(0..N);
+                let expected = (from..).find(|&i| i >= N || !x[i]).unwrap();
+                assert_eq!(lhs.first_gap_from(from), expected);
+                assert_eq!(lhs.mex(), (0..).find(|&i| i >= N || !x[i]).unwrap());
+            }
+        }
+    }
 }
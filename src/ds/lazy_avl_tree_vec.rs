@@ -0,0 +1,833 @@
+//! 遅延評価付きAVL木による列(Lazy AVL Tree Vec)
+//!
+//! 要素としてモノイドを持つ列を管理するデータ構造．
+//! [`AvlTreeVec`](crate::ds::avl_tree_vec::AvlTreeVec)の各ノードに部分木の総積を持たせたもので，
+//! 以下の操作をいずれも O(log n) で処理できる．
+//! - 任意の位置への要素の挿入・削除，列の連結・分割．
+//! - 任意の区間の要素の総積の取得．
+//! - 任意の区間の要素に対する作用の一括適用．
+//! - 任意の区間の反転．
+//! - 区間の総積に対する二分探索．
+//!
+//! 区間の反転のために，部分木の総積を左右逆順に取ったもの(逆順の総積)も保持する．
+//! そのため，演算が可換でない場合でも反転を正しく扱える．
+//!
+//! 作用を使わない場合は，作用の型パラメータを省略すると[`ActNone`]が用いられる．
+//!
+//! # 使用例
+//! ```
+//! use reprol::{
+//!     ds::lazy_avl_tree_vec::LazyAvlTreeVec,
+//!     ops::{act_add::ActAdd, op_min::OpMin},
+//! };
+//!
+//! let mut tree = LazyAvlTreeVec::<OpMin<i64>, ActAdd<i64>>::from(vec![5, 3, 8, 1, 4]);
+//! assert_eq!(tree.fold(0..3), 3);
+//!
+//! tree.act(1..4, &10); // [5, 13, 18, 11, 4]
+//! assert_eq!(tree.fold(1..4), 11);
+//!
+//! tree.reverse(0..4); // [11, 18, 13, 5, 4]
+//! assert_eq!(tree.get(0), Some(11));
+//!
+//! tree.insert(2, 0); // [11, 18, 0, 13, 5, 4]
+//! assert_eq!(tree.fold(..), 0);
+//! assert_eq!(tree.remove(2), Some(0));
+//! assert_eq!(tree.bisect_right(0, |&x| x > 5), 3);
+//! ```
+//!
+//! ## 演算が可換でない場合の反転
+//! ```
+//! use reprol::{
+//!     ds::lazy_avl_tree_vec::LazyAvlTreeVec,
+//!     ops::act_affine::{ActAffine, ActAffineElement},
+//! };
+//!
+//! // 各要素はアフィン変換で，総積は関数合成
+//! let f = ActAffineElement { a: 2, b: 0 };
+//! let g = ActAffineElement { a: 1, b: 3 };
+//! let mut tree = LazyAvlTreeVec::<ActAffine<i64>>::from(vec![f, g]);
+//! assert_eq!(tree.fold(..), ActAffineElement { a: 2, b: 6 }); // f∘g
+//! tree.reverse(..);
+//! assert_eq!(tree.fold(..), ActAffineElement { a: 2, b: 3 }); // g∘f
+//! ```
+
+use std::{
+    cmp::Ordering,
+    iter::FromIterator,
+    mem::{replace, swap},
+    ops::{Range, RangeBounds},
+};
+
+use crate::{
+    ds::avl_tree_vec::{
+        Augment, Link, Node, NodePtr, fetch, free, len, merge, merge_with_root, split,
+        traverse_postorder,
+    },
+    ops::{act_none::ActNone, action::Action, monoid::Monoid},
+    utils::normalize_range::normalize_index,
+};
+
+/// 木の各ノードが持つ情報
+struct Data<O: Monoid, A: Action<O>> {
+    value: O::Element,
+
+    /// 部分木の総積
+    prod: O::Element,
+
+    /// 部分木の逆順の総積
+    rev_prod: O::Element,
+
+    /// 子に伝播していない作用
+    lazy: A::Element,
+
+    /// 子に伝播していない反転があるか
+    rev: bool,
+}
+
+/// 実際の左右の子と，子の祖先に遅延している作用と反転
+type Children<O, A> = (
+    Link<Data<O, A>>,
+    Link<Data<O, A>>,
+    <A as Monoid>::Element,
+    bool,
+);
+
+/// 遅延評価付きAVL木による列
+pub struct LazyAvlTreeVec<O: Monoid, A: Action<O> = ActNone> {
+    root: Link<Data<O, A>>,
+
+    /// 演算(モノイド)
+    op: O,
+
+    /// モノイドに対する作用
+    action: A,
+}
+
+impl<O: Monoid, A: Action<O>> LazyAvlTreeVec<O, A> {
+    pub fn len(&self) -> usize {
+        len(self.root)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<O: Monoid, A: Action<O>> LazyAvlTreeVec<O, A>
+where
+    O::Element: Clone,
+{
+    /// 空の列を生成する．
+    pub fn new() -> Self
+    where
+        O: Default,
+        A: Default,
+    {
+        Self::with_op(O::default(), A::default())
+    }
+
+    /// モノイド`op`と作用`action`を指定して空の列を生成する．
+    pub fn with_op(op: O, action: A) -> Self {
+        Self {
+            root: None,
+            op,
+            action,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        traverse_postorder(self.root.take(), free);
+    }
+
+    /// `index`番目の要素を返す．
+    pub fn get(&self, index: usize) -> Option<O::Element> {
+        let mut node = self.root;
+        let mut index = index;
+        let mut lazy = self.action.id();
+        let mut rev = false;
+        while let Some(ptr) = node {
+            let (left, right, child_lazy, child_rev) = self.children(ptr, &lazy, rev);
+            let left_len = len(left);
+            match index.cmp(&left_len) {
+                Ordering::Less => node = left,
+                Ordering::Greater => {
+                    index -= left_len + 1;
+                    node = right;
+                }
+                Ordering::Equal => {
+                    let value = &unsafe { ptr.as_ref() }.value.value;
+                    return Some(self.action.act(&lazy, value));
+                }
+            }
+            lazy = child_lazy;
+            rev = child_rev;
+        }
+        None
+    }
+
+    /// `index`番目の要素を`value`に更新する．
+    pub fn set(&mut self, index: usize, value: O::Element) {
+        assert!(index < self.len());
+        self.set_node(self.root.unwrap(), index, value);
+    }
+
+    pub fn front(&self) -> Option<O::Element> {
+        self.get(0)
+    }
+
+    pub fn back(&self) -> Option<O::Element> {
+        self.get(self.len().checked_sub(1)?)
+    }
+
+    pub fn push_front(&mut self, value: O::Element) {
+        self.insert(0, value);
+    }
+
+    pub fn push_back(&mut self, value: O::Element) {
+        self.insert(self.len(), value);
+    }
+
+    pub fn pop_front(&mut self) -> Option<O::Element> {
+        self.remove(0)
+    }
+
+    pub fn pop_back(&mut self) -> Option<O::Element> {
+        self.remove(self.len().checked_sub(1)?)
+    }
+
+    /// `index`番目に`value`を挿入する．
+    pub fn insert(&mut self, index: usize, value: O::Element) {
+        assert!(index <= self.len());
+        let new_node = self.create_node(value);
+        let (left, right) = split(self.root.take(), index, self);
+        self.root = merge_with_root(left, Some(new_node), right, self);
+    }
+
+    /// `index`番目の要素を削除して返す．
+    pub fn remove(&mut self, index: usize) -> Option<O::Element> {
+        (index < self.len()).then(|| {
+            let (left, right) = split(self.root.take(), index, self);
+            let (removed, right) = split(right, 1, self);
+            self.root = merge(left, right, self);
+            unsafe { Box::from_raw(removed.unwrap().as_ptr()) }
+                .value
+                .value
+        })
+    }
+
+    /// `other`の要素をすべて末尾に移動する．
+    pub fn append(&mut self, other: &mut Self) {
+        self.root = merge(self.root.take(), other.root.take(), self);
+    }
+
+    /// `[index, len)`の要素を切り離して返す．
+    pub fn split_off(&mut self, index: usize) -> Self
+    where
+        O: Clone,
+        A: Clone,
+    {
+        assert!(index <= self.len());
+        let (left, right) = split(self.root.take(), index, self);
+        self.root = left;
+        Self {
+            root: right,
+            op: self.op.clone(),
+            action: self.action.clone(),
+        }
+    }
+
+    /// 区間`range`の要素の総積を返す．
+    pub fn fold(&self, range: impl RangeBounds<usize>) -> O::Element {
+        let Range { start: l, end: r } = normalize_index(range, self.len());
+        assert!(l <= r);
+        assert!(r <= self.len());
+        self.fold_node(self.root, l, r, &self.action.id(), false)
+    }
+
+    /// 区間`range`の要素に作用`f`を適用する．
+    pub fn act(&mut self, range: impl RangeBounds<usize>, f: &A::Element) {
+        self.with_range(range, |tree, node| tree.apply(node, f));
+    }
+
+    /// 区間`range`の要素を反転する．
+    pub fn reverse(&mut self, range: impl RangeBounds<usize>) {
+        self.with_range(range, |_, node| toggle(node));
+    }
+
+    /// 列上の二分探索(max_right)．
+    ///
+    /// `g(r) = f(fold(l..r))`として，
+    /// 単調な`g`に対して，`g(r) = true`となる最大の`r`を返す．
+    ///
+    /// # 計算量
+    /// - O(log n)
+    ///
+    /// # 制約
+    /// - `0 <= l <= len`
+    /// - `f(identity()) = true`
+    pub fn bisect_right(&self, l: usize, mut f: impl FnMut(&O::Element) -> bool) -> usize {
+        assert!(l <= self.len());
+        debug_assert!(f(&self.op.id()));
+        let mut prod = self.op.id();
+        self.max_right(self.root, l, &self.action.id(), false, &mut prod, &mut f)
+            .unwrap_or(self.len())
+    }
+
+    /// 列上の二分探索(min_left)．
+    ///
+    /// `g(l) = f(fold(l..r))`として，
+    /// 単調な`g`に対して，`g(l) = true`となる最小の`l`を返す．
+    ///
+    /// # 計算量
+    /// - O(log n)
+    ///
+    /// # 制約
+    /// - `0 <= r <= len`
+    /// - `f(identity()) = true`
+    pub fn bisect_left(&self, r: usize, mut f: impl FnMut(&O::Element) -> bool) -> usize {
+        assert!(r <= self.len());
+        debug_assert!(f(&self.op.id()));
+        let mut prod = self.op.id();
+        self.min_left(self.root, r, &self.action.id(), false, &mut prod, &mut f)
+            .unwrap_or(0)
+    }
+
+    /// 要素を先頭から順に返すイテレータを返す．
+    pub fn iter(&self) -> impl Iterator<Item = O::Element> {
+        fn dfs<O: Monoid, A: Action<O>>(
+            tree: &LazyAvlTreeVec<O, A>,
+            node: Link<Data<O, A>>,
+            lazy: &A::Element,
+            rev: bool,
+            res: &mut Vec<O::Element>,
+        ) where
+            O::Element: Clone,
+        {
+            if let Some(ptr) = node {
+                let (left, right, child_lazy, child_rev) = tree.children(ptr, lazy, rev);
+                dfs(tree, left, &child_lazy, child_rev, res);
+                let value = &unsafe { ptr.as_ref() }.value.value;
+                res.push(tree.action.act(lazy, value));
+                dfs(tree, right, &child_lazy, child_rev, res);
+            }
+        }
+
+        let mut res = Vec::with_capacity(self.len());
+        dfs(self, self.root, &self.action.id(), false, &mut res);
+        res.into_iter()
+    }
+
+    /// 区間`range`の部分木を切り出して`f`に渡し，元に戻す．
+    fn with_range<R>(
+        &mut self,
+        range: impl RangeBounds<usize>,
+        f: impl FnOnce(&Self, Link<Data<O, A>>) -> R,
+    ) -> R {
+        let Range { start: l, end: r } = normalize_index(range, self.len());
+        assert!(l <= r);
+        assert!(r <= self.len());
+
+        let (left, right) = split(self.root.take(), l, self);
+        let (mid, right) = split(right, r - l, self);
+        let res = f(self, mid);
+        let right = merge(mid, right, self);
+        self.root = merge(left, right, self);
+        res
+    }
+
+    fn create_node(&self, value: O::Element) -> NodePtr<Data<O, A>> {
+        Node::new(Data {
+            prod: value.clone(),
+            rev_prod: value.clone(),
+            value,
+            lazy: self.action.id(),
+            rev: false,
+        })
+    }
+
+    /// 部分木全体に作用`f`を適用する．
+    fn apply(&self, node: Link<Data<O, A>>, f: &A::Element) {
+        if let Some(mut node) = node {
+            let data = &mut unsafe { node.as_mut() }.value;
+            data.value = self.action.act(f, &data.value);
+            data.prod = self.action.act(f, &data.prod);
+            data.rev_prod = self.action.act(f, &data.rev_prod);
+            data.lazy = self.action.op(f, &data.lazy);
+        }
+    }
+
+    /// 祖先に作用`lazy`と反転`rev`が遅延しているノードについて，
+    /// 実際の左右の子と，子の祖先に遅延している作用と反転を返す．
+    fn children(&self, node: NodePtr<Data<O, A>>, lazy: &A::Element, rev: bool) -> Children<O, A> {
+        let raw = unsafe { node.as_ref() };
+        let rev = rev ^ raw.value.rev;
+        let (left, right) = if rev {
+            (raw.right, raw.left)
+        } else {
+            (raw.left, raw.right)
+        };
+        (left, right, self.action.op(lazy, &raw.value.lazy), rev)
+    }
+
+    /// 祖先に作用`lazy`と反転`rev`が遅延しているノードについて，部分木の実際の総積を返す．
+    fn prod(&self, node: Link<Data<O, A>>, lazy: &A::Element, rev: bool) -> O::Element {
+        node.map_or_else(
+            || self.op.id(),
+            |node| {
+                let data = &unsafe { node.as_ref() }.value;
+                let prod = if rev { &data.rev_prod } else { &data.prod };
+                self.action.act(lazy, prod)
+            },
+        )
+    }
+
+    /// nodeの部分木のうち[l, r)番目の要素の総積を返す
+    fn fold_node(
+        &self,
+        node: Link<Data<O, A>>,
+        l: usize,
+        r: usize,
+        lazy: &A::Element,
+        rev: bool,
+    ) -> O::Element {
+        let ptr = match node {
+            Some(ptr) if l < r => ptr,
+            _ => return self.op.id(),
+        };
+        if l == 0 && r == len(node) {
+            return self.prod(node, lazy, rev);
+        }
+
+        let (left, right, child_lazy, child_rev) = self.children(ptr, lazy, rev);
+        let left_len = len(left);
+        let mut res = self.fold_node(left, l, r.min(left_len), &child_lazy, child_rev);
+        if l <= left_len && left_len < r {
+            let value = &unsafe { ptr.as_ref() }.value.value;
+            res = self.op.op(&res, &self.action.act(lazy, value));
+        }
+        if left_len < r {
+            let l = l.saturating_sub(left_len + 1);
+            let r = r - left_len - 1;
+            let tmp = self.fold_node(right, l, r, &child_lazy, child_rev);
+            res = self.op.op(&res, &tmp);
+        }
+        res
+    }
+
+    /// nodeの部分木のl番目以降の要素を順に`prod`に掛けていき，
+    /// はじめて`f`がfalseとなる位置を返す
+    fn max_right(
+        &self,
+        node: Link<Data<O, A>>,
+        l: usize,
+        lazy: &A::Element,
+        rev: bool,
+        prod: &mut O::Element,
+        f: &mut impl FnMut(&O::Element) -> bool,
+    ) -> Option<usize> {
+        let ptr = node?;
+        if l == 0 {
+            let tmp = self.op.op(prod, &self.prod(node, lazy, rev));
+            if f(&tmp) {
+                *prod = tmp;
+                return None;
+            }
+        }
+
+        let (left, right, child_lazy, child_rev) = self.children(ptr, lazy, rev);
+        let left_len = len(left);
+        if l < left_len
+            && let Some(i) = self.max_right(left, l, &child_lazy, child_rev, prod, f)
+        {
+            return Some(i);
+        }
+        if l <= left_len {
+            let value = &unsafe { ptr.as_ref() }.value.value;
+            let tmp = self.op.op(prod, &self.action.act(lazy, value));
+            if !f(&tmp) {
+                return Some(left_len);
+            }
+            *prod = tmp;
+        }
+        let l = l.saturating_sub(left_len + 1);
+        self.max_right(right, l, &child_lazy, child_rev, prod, f)
+            .map(|i| i + left_len + 1)
+    }
+
+    /// nodeの部分木のr番目より前の要素を逆順に`prod`に掛けていき，
+    /// はじめて`f`がfalseとなる位置を返す
+    fn min_left(
+        &self,
+        node: Link<Data<O, A>>,
+        r: usize,
+        lazy: &A::Element,
+        rev: bool,
+        prod: &mut O::Element,
+        f: &mut impl FnMut(&O::Element) -> bool,
+    ) -> Option<usize> {
+        let ptr = node?;
+        if r == len(node) {
+            let tmp = self.op.op(&self.prod(node, lazy, rev), prod);
+            if f(&tmp) {
+                *prod = tmp;
+                return None;
+            }
+        }
+
+        let (left, right, child_lazy, child_rev) = self.children(ptr, lazy, rev);
+        let left_len = len(left);
+        if r > left_len + 1 {
+            let r = r - left_len - 1;
+            if let Some(i) = self.min_left(right, r, &child_lazy, child_rev, prod, f) {
+                return Some(i + left_len + 1);
+            }
+        }
+        if r > left_len {
+            let value = &unsafe { ptr.as_ref() }.value.value;
+            let tmp = self.op.op(&self.action.act(lazy, value), prod);
+            if !f(&tmp) {
+                return Some(left_len + 1);
+            }
+            *prod = tmp;
+        }
+        self.min_left(left, r.min(left_len), &child_lazy, child_rev, prod, f)
+    }
+
+    /// index番目のノードの値を更新する
+    fn set_node(&self, mut node: NodePtr<Data<O, A>>, index: usize, value: O::Element) {
+        self.push(node);
+        let raw = unsafe { node.as_mut() };
+        let left_len = len(raw.left);
+        match index.cmp(&left_len) {
+            Ordering::Less => self.set_node(raw.left.unwrap(), index, value),
+            Ordering::Greater => self.set_node(raw.right.unwrap(), index - left_len - 1, value),
+            Ordering::Equal => raw.value.value = value,
+        }
+        fetch(node, self);
+    }
+
+    /// `n`個の要素から平衡な木を構築する
+    fn build(&self, iter: &mut impl Iterator<Item = O::Element>, n: usize) -> Link<Data<O, A>> {
+        if n == 0 {
+            return None;
+        }
+        let left = self.build(iter, n / 2);
+        let mut root = self.create_node(iter.next().unwrap());
+        let right = self.build(iter, n - n / 2 - 1);
+        let raw_root = unsafe { root.as_mut() };
+        raw_root.left = left;
+        raw_root.right = right;
+        fetch(root, self);
+        Some(root)
+    }
+}
+
+/// 部分木全体を反転する．
+fn toggle<O: Monoid, A: Action<O>>(node: Link<Data<O, A>>) {
+    if let Some(mut node) = node {
+        let data = &mut unsafe { node.as_mut() }.value;
+        swap(&mut data.prod, &mut data.rev_prod);
+        data.rev ^= true;
+    }
+}
+
+impl<O: Monoid, A: Action<O>> Augment<Data<O, A>> for LazyAvlTreeVec<O, A>
+where
+    O::Element: Clone,
+{
+    /// 遅延している作用と反転を子に伝播する．
+    fn push(&self, mut node: NodePtr<Data<O, A>>) {
+        let raw = unsafe { node.as_mut() };
+        if raw.value.rev {
+            raw.value.rev = false;
+            swap(&mut raw.left, &mut raw.right);
+            toggle(raw.left);
+            toggle(raw.right);
+        }
+        let lazy = replace(&mut raw.value.lazy, self.action.id());
+        self.apply(raw.left, &lazy);
+        self.apply(raw.right, &lazy);
+    }
+
+    /// 子の総積からノードの総積を更新する．
+    /// (子への伝播が済んでいるノードに対してのみ呼び出される)
+    fn update(&self, mut node: NodePtr<Data<O, A>>) {
+        let raw = unsafe { node.as_mut() };
+        let mut prod = raw.value.value.clone();
+        let mut rev_prod = raw.value.value.clone();
+        if let Some(left) = raw.left {
+            let left = &unsafe { left.as_ref() }.value;
+            prod = self.op.op(&left.prod, &prod);
+            rev_prod = self.op.op(&rev_prod, &left.rev_prod);
+        }
+        if let Some(right) = raw.right {
+            let right = &unsafe { right.as_ref() }.value;
+            prod = self.op.op(&prod, &right.prod);
+            rev_prod = self.op.op(&right.rev_prod, &rev_prod);
+        }
+        raw.value.prod = prod;
+        raw.value.rev_prod = rev_prod;
+    }
+}
+
+impl<O: Monoid + Default, A: Action<O> + Default> Default for LazyAvlTreeVec<O, A>
+where
+    O::Element: Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<O: Monoid, A: Action<O>> Drop for LazyAvlTreeVec<O, A> {
+    fn drop(&mut self) {
+        traverse_postorder(self.root.take(), free);
+    }
+}
+
+impl<O: Monoid, A: Action<O>> From<(Vec<O::Element>, O, A)> for LazyAvlTreeVec<O, A>
+where
+    O::Element: Clone,
+{
+    fn from((v, op, action): (Vec<O::Element>, O, A)) -> Self {
+        let mut res = Self::with_op(op, action);
+        let n = v.len();
+        res.root = res.build(&mut v.into_iter(), n);
+        res
+    }
+}
+
+impl<O: Monoid + Default, A: Action<O> + Default> From<Vec<O::Element>> for LazyAvlTreeVec<O, A>
+where
+    O::Element: Clone,
+{
+    fn from(v: Vec<O::Element>) -> Self {
+        Self::from((v, O::default(), A::default()))
+    }
+}
+
+impl<O: Monoid + Default, A: Action<O> + Default, const N: usize> From<[O::Element; N]>
+    for LazyAvlTreeVec<O, A>
+where
+    O::Element: Clone,
+{
+    fn from(v: [O::Element; N]) -> Self {
+        Self::from(Vec::from(v))
+    }
+}
+
+impl<O: Monoid + Default, A: Action<O> + Default> FromIterator<O::Element> for LazyAvlTreeVec<O, A>
+where
+    O::Element: Clone,
+{
+    fn from_iter<I: IntoIterator<Item = O::Element>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::*;
+    use crate::{
+        ops::{
+            act_add::ActAdd,
+            act_affine::{ActAffine, ActAffineElement},
+            op_add::OpAdd,
+            op_max::OpMax,
+            op_range_sum::{OpRangeSum, OpRangeSumElement},
+        },
+        utils::test_utils::random::get_test_rng,
+    };
+
+    fn is_balanced<T>(node: Link<T>) -> bool {
+        let height = |node: Link<T>| node.map_or(0, |node| unsafe { node.as_ref() }.height);
+        node.is_none_or(|node| {
+            let raw = unsafe { node.as_ref() };
+            (height(raw.left) - height(raw.right)).abs() <= 1
+                && is_balanced(raw.left)
+                && is_balanced(raw.right)
+        })
+    }
+
+    #[test]
+    fn test_max_add() {
+        let mut tree = LazyAvlTreeVec::<OpMax<i64>, ActAdd<i64>>::new();
+        assert!(tree.is_empty());
+        assert_eq!(tree.fold(..), i64::MIN);
+
+        tree.push_back(3);
+        tree.push_back(1);
+        tree.push_front(4);
+        tree.insert(1, 5); // [4, 5, 3, 1]
+        assert!(tree.iter().eq([4, 5, 3, 1]));
+        assert_eq!(tree.fold(2..), 3);
+
+        tree.act(2.., &10); // [4, 5, 13, 11]
+        assert_eq!(tree.fold(..2), 5);
+        assert_eq!(tree.fold(1..=3), 13);
+
+        tree.reverse(1..); // [4, 11, 13, 5]
+        assert!(tree.iter().eq([4, 11, 13, 5]));
+        assert_eq!(tree.bisect_right(0, |&x| x < 13), 2);
+        assert_eq!(tree.bisect_left(4, |&x| x < 13), 3);
+
+        tree.set(2, 0); // [4, 11, 0, 5]
+        assert_eq!(tree.fold(2..), 5);
+        assert_eq!(tree.pop_front(), Some(4));
+        assert_eq!(tree.pop_back(), Some(5));
+        assert_eq!(tree.back(), Some(0));
+
+        let mut other = tree.split_off(1);
+        assert!(tree.iter().eq([11]));
+        assert!(other.iter().eq([0]));
+        other.append(&mut tree);
+        assert!(tree.is_empty());
+        assert!(other.iter().eq([0, 11]));
+    }
+
+    #[test]
+    fn test_without_action() {
+        let mut tree = LazyAvlTreeVec::<OpAdd<i64>>::from_iter(1..=10);
+        assert_eq!(tree.fold(..), 55);
+        tree.reverse(..5);
+        assert!(tree.iter().eq([5, 4, 3, 2, 1, 6, 7, 8, 9, 10]));
+        assert_eq!(tree.bisect_right(3, |&x| x <= 10), 6);
+        assert_eq!(tree.bisect_left(10, |&x| x <= 20), 8);
+    }
+
+    #[test]
+    fn test_affine_randomized() {
+        type Op = OpRangeSum<i64>;
+        type Act = ActAffine<i64>;
+
+        let mut rng = get_test_rng();
+
+        const T: usize = 50;
+        const N_MAX: usize = 40;
+        const Q: usize = 1000;
+
+        for _ in 0..T {
+            let n = rng.random_range(0..=N_MAX);
+            let mut naive = (0..n)
+                .map(|_| rng.random_range(-100..=100))
+                .collect::<Vec<i64>>();
+            let mut tree = LazyAvlTreeVec::<Op, Act>::from_iter(
+                naive.iter().map(|&x| OpRangeSumElement::leaf(x)),
+            );
+
+            for _ in 0..Q {
+                let l = rng.random_range(0..=naive.len());
+                let r = rng.random_range(l..=naive.len());
+                match rng.random_range(0..7) {
+                    0 => {
+                        let x = rng.random_range(-100..=100);
+                        tree.insert(l, OpRangeSumElement::leaf(x));
+                        naive.insert(l, x);
+                    }
+                    1 => {
+                        assert_eq!(
+                            tree.remove(l).map(|x| x.value()),
+                            (l < naive.len()).then(|| naive.remove(l))
+                        );
+                    }
+                    2 => {
+                        let f = ActAffineElement {
+                            a: rng.random_range(-3..=3),
+                            b: rng.random_range(-100..=100),
+                        };
+                        tree.act(l..r, &f);
+                        for x in &mut naive[l..r] {
+                            *x = x.wrapping_mul(f.a).wrapping_add(f.b);
+                        }
+                    }
+                    3 => {
+                        tree.reverse(l..r);
+                        naive[l..r].reverse();
+                    }
+                    4 => {
+                        let expected = naive[l..r].iter().fold(0i64, |acc, &x| acc.wrapping_add(x));
+                        assert_eq!(tree.fold(l..r).value(), expected);
+                        assert_eq!(tree.fold(l..r).len(), (r - l) as i64);
+                    }
+                    5 => {
+                        if l < naive.len() {
+                            assert_eq!(tree.get(l).unwrap().value(), naive[l]);
+                        } else {
+                            assert!(tree.get(l).is_none());
+                        }
+                    }
+                    _ => {
+                        // 要素数がlen以下となる最大の区間
+                        let len = rng.random_range(0..=naive.len() - l) as i64;
+                        assert_eq!(tree.bisect_right(l, |x| x.len() <= len), l + len as usize);
+                        let len = rng.random_range(0..=r) as i64;
+                        assert_eq!(tree.bisect_left(r, |x| x.len() <= len), r - len as usize);
+                    }
+                }
+                assert_eq!(tree.len(), naive.len());
+                assert!(is_balanced(tree.root));
+            }
+
+            assert!(tree.iter().map(|x| x.value()).eq(naive.iter().copied()));
+        }
+    }
+
+    #[test]
+    fn test_non_commutative_randomized() {
+        type Op = ActAffine<i64>;
+
+        let mut rng = get_test_rng();
+
+        const T: usize = 50;
+        const N_MAX: usize = 40;
+        const Q: usize = 1000;
+
+        let op = Op::default();
+
+        for _ in 0..T {
+            let n = rng.random_range(1..=N_MAX);
+            let mut naive = (0..n)
+                .map(|_| ActAffineElement {
+                    a: rng.random_range(-3..=3),
+                    b: rng.random_range(-100..=100),
+                })
+                .collect::<Vec<_>>();
+            let mut tree = LazyAvlTreeVec::<Op>::from(naive.clone());
+
+            for _ in 0..Q {
+                let l = rng.random_range(0..=naive.len());
+                let r = rng.random_range(l..=naive.len());
+                match rng.random_range(0..4) {
+                    0 => {
+                        tree.reverse(l..r);
+                        naive[l..r].reverse();
+                    }
+                    1 if l < naive.len() => {
+                        let x = ActAffineElement {
+                            a: rng.random_range(-3..=3),
+                            b: rng.random_range(-100..=100),
+                        };
+                        tree.set(l, x);
+                        naive[l] = x;
+                    }
+                    2 => {
+                        let mut other = tree.split_off(l);
+                        other.append(&mut tree);
+                        tree = other;
+                        naive.rotate_left(l);
+                    }
+                    _ => {
+                        let expected = naive[l..r].iter().fold(op.id(), |acc, x| op.op(&acc, x));
+                        assert_eq!(tree.fold(l..r), expected);
+                    }
+                }
+                assert!(is_balanced(tree.root));
+            }
+
+            assert!(tree.iter().eq(naive.iter().cloned()));
+        }
+    }
+}
@@ -0,0 +1,344 @@
+//! 全体加算つきSkew Heap
+//!
+//! 併合可能な優先度付きキューで，全要素への値の加算を遅延評価で処理する．
+//! 取り出す順序は型パラメータ[`Max`]・[`Min`]で指定し，`Reverse`で包む必要はない．
+//! 以下の操作をいずれも償却 O(log n) で処理できる．
+//! - 要素の追加．
+//! - 最大値(最小値)の削除．
+//! - 2つのヒープの併合．
+//!
+//! 全要素への加算は O(1) で処理できる．
+//! 木上のslope trickのように，子のヒープを併合しながら根に向かって値をずらしていく処理に用いる．
+//!
+//! # 使用例
+//! ```
+//! use reprol::ds::lazy_skew_heap::{MaxLazySkewHeap, MinLazySkewHeap};
+//!
+//! let mut heap = MinLazySkewHeap::from([5, 1, 3]);
+//! heap.add_all(10);
+//! assert_eq!(heap.pop(), Some(11));
+//!
+//! let mut other = MinLazySkewHeap::from([2]);
+//! other.meld(heap);
+//! assert_eq!(other.len(), 3);
+//! assert_eq!(other.pop(), Some(2));
+//! assert_eq!(other.pop(), Some(13));
+//!
+//! let mut heap = MaxLazySkewHeap::from([5, 1, 3]);
+//! heap.add_all(-1);
+//! assert_eq!(heap.peek(), Some(&4));
+//! ```
+//!
+//! # 問題例
+//! - [ABC217 H](https://atcoder.jp/contests/abc217/tasks/abc217_h)
+
+use std::{
+    iter::FromIterator,
+    marker::PhantomData,
+    mem::{swap, take},
+    ops::Add,
+};
+
+/// ヒープから取り出す順序を表すトレイト．
+pub trait HeapOrder {
+    /// `lhs`が`rhs`より先に取り出されるべきかを返す．
+    fn precedes<T: Ord>(lhs: &T, rhs: &T) -> bool;
+}
+
+/// 最大値から取り出す順序
+pub struct Max;
+
+impl HeapOrder for Max {
+    #[inline(always)]
+    fn precedes<T: Ord>(lhs: &T, rhs: &T) -> bool {
+        lhs > rhs
+    }
+}
+
+/// 最小値から取り出す順序
+pub struct Min;
+
+impl HeapOrder for Min {
+    #[inline(always)]
+    fn precedes<T: Ord>(lhs: &T, rhs: &T) -> bool {
+        lhs < rhs
+    }
+}
+
+/// 全体加算つきSkew Heap
+pub struct LazySkewHeap<T, D = Max> {
+    root: Option<Box<Node<T>>>,
+    len: usize,
+    _phantom: PhantomData<D>,
+}
+
+/// 最大値から取り出す全体加算つきSkew Heap
+pub type MaxLazySkewHeap<T> = LazySkewHeap<T, Max>;
+
+/// 最小値から取り出す全体加算つきSkew Heap
+pub type MinLazySkewHeap<T> = LazySkewHeap<T, Min>;
+
+impl<T, D> LazySkewHeap<T, D>
+where
+    T: Ord + Copy + Add<Output = T>,
+    D: HeapOrder,
+{
+    /// 空のヒープを生成する．
+    pub fn new() -> Self {
+        Self {
+            root: None,
+            len: 0,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// 要素数を返す．
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// ヒープが空かを返す．
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// すべての要素を削除する．
+    pub fn clear(&mut self) {
+        self.root = None;
+        self.len = 0;
+    }
+
+    /// 要素を追加する．
+    pub fn push(&mut self, value: T) {
+        self.root = meld::<T, D>(take(&mut self.root), Some(Box::new(Node::new(value))));
+        self.len += 1;
+    }
+
+    /// 先頭の要素を削除して返す．
+    pub fn pop(&mut self) -> Option<T> {
+        let mut root = take(&mut self.root)?;
+        root.push();
+        self.root = meld::<T, D>(take(&mut root.left), take(&mut root.right));
+        self.len -= 1;
+        Some(root.value)
+    }
+
+    /// 先頭の要素を返す．
+    pub fn peek(&self) -> Option<&T> {
+        self.root.as_ref().map(|node| &node.value)
+    }
+
+    /// 全要素に`x`を加算する．
+    pub fn add_all(&mut self, x: T) {
+        if let Some(root) = self.root.as_mut() {
+            root.apply(x);
+        }
+    }
+
+    /// `other`のすべての要素を併合する．
+    pub fn meld(&mut self, other: Self) {
+        self.root = meld::<T, D>(take(&mut self.root), other.root);
+        self.len += other.len;
+    }
+
+    /// `other`のすべての要素を移動して併合する．`other`は空になる．
+    pub fn append(&mut self, other: &mut Self) {
+        self.meld(take(other));
+    }
+}
+
+impl<T, D> Default for LazySkewHeap<T, D> {
+    fn default() -> Self {
+        Self {
+            root: None,
+            len: 0,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, D> Extend<T> for LazySkewHeap<T, D>
+where
+    T: Ord + Copy + Add<Output = T>,
+    D: HeapOrder,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|x| self.push(x));
+    }
+}
+
+impl<T, D> FromIterator<T> for LazySkewHeap<T, D>
+where
+    T: Ord + Copy + Add<Output = T>,
+    D: HeapOrder,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut res = Self::new();
+        res.extend(iter);
+        res
+    }
+}
+
+impl<T, D> From<Vec<T>> for LazySkewHeap<T, D>
+where
+    T: Ord + Copy + Add<Output = T>,
+    D: HeapOrder,
+{
+    fn from(v: Vec<T>) -> Self {
+        Self::from_iter(v)
+    }
+}
+
+impl<T, D, const N: usize> From<[T; N]> for LazySkewHeap<T, D>
+where
+    T: Ord + Copy + Add<Output = T>,
+    D: HeapOrder,
+{
+    fn from(array: [T; N]) -> Self {
+        Self::from_iter(array)
+    }
+}
+
+struct Node<T> {
+    value: T,
+    /// 子の部分木の全要素に加算する値
+    lazy: Option<T>,
+    left: Option<Box<Node<T>>>,
+    right: Option<Box<Node<T>>>,
+}
+
+impl<T: Copy + Add<Output = T>> Node<T> {
+    fn new(value: T) -> Self {
+        Self {
+            value,
+            lazy: None,
+            left: None,
+            right: None,
+        }
+    }
+
+    /// 部分木の全要素に`x`を加算する．
+    fn apply(&mut self, x: T) {
+        self.value = self.value + x;
+        self.lazy = Some(self.lazy.map_or(x, |lazy| lazy + x));
+    }
+
+    /// 遅延していた加算を子に伝播する．
+    fn push(&mut self) {
+        if let Some(lazy) = self.lazy.take() {
+            if let Some(left) = self.left.as_mut() {
+                left.apply(lazy);
+            }
+            if let Some(right) = self.right.as_mut() {
+                right.apply(lazy);
+            }
+        }
+    }
+}
+
+fn meld<T, D>(lhs: Option<Box<Node<T>>>, rhs: Option<Box<Node<T>>>) -> Option<Box<Node<T>>>
+where
+    T: Ord + Copy + Add<Output = T>,
+    D: HeapOrder,
+{
+    match (lhs, rhs) {
+        (Some(mut lhs), Some(mut rhs)) => {
+            if D::precedes(&rhs.value, &lhs.value) {
+                swap(&mut lhs, &mut rhs);
+            }
+            lhs.push();
+            lhs.right = meld::<T, D>(take(&mut lhs.right), Some(rhs));
+            swap(&mut lhs.left, &mut lhs.right);
+            Some(lhs)
+        }
+        (lhs, None) => lhs,
+        (None, rhs) => rhs,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::*;
+    use crate::utils::test_utils::random::get_test_rng;
+
+    #[test]
+    fn test() {
+        let mut heap = MaxLazySkewHeap::new();
+        assert_eq!(heap.pop(), None);
+        heap.add_all(3);
+        assert!(heap.is_empty());
+
+        heap.extend([1, 4, 2]);
+        heap.add_all(10);
+        assert_eq!(heap.pop(), Some(14));
+        heap.push(5);
+        assert_eq!(heap.pop(), Some(12));
+        assert_eq!(heap.pop(), Some(11));
+        assert_eq!(heap.pop(), Some(5));
+        assert_eq!(heap.pop(), None);
+
+        let mut lhs = MinLazySkewHeap::from([3, 7]);
+        let mut rhs = MinLazySkewHeap::from([4, 1]);
+        rhs.add_all(5);
+        lhs.append(&mut rhs);
+        assert!(rhs.is_empty());
+        assert_eq!(lhs.len(), 4);
+        assert_eq!(lhs.pop(), Some(3));
+        assert_eq!(lhs.pop(), Some(6));
+        assert_eq!(lhs.pop(), Some(7));
+        assert_eq!(lhs.pop(), Some(9));
+    }
+
+    #[test]
+    fn test_randomized() {
+        fn test<D: HeapOrder>(rng: &mut impl Rng, is_max: bool) {
+            const K: usize = 5;
+
+            for _ in 0..100 {
+                let mut heaps: Vec<LazySkewHeap<i64, D>> =
+                    (0..K).map(|_| LazySkewHeap::new()).collect();
+                let mut naive: Vec<Vec<i64>> = vec![vec![]; K];
+
+                for _ in 0..500 {
+                    let i = rng.random_range(0..K);
+                    match rng.random_range(0..5) {
+                        0 | 1 => {
+                            let x = rng.random_range(-100..=100);
+                            heaps[i].push(x);
+                            naive[i].push(x);
+                        }
+                        2 => {
+                            let x = rng.random_range(-10..=10);
+                            heaps[i].add_all(x);
+                            naive[i].iter_mut().for_each(|y| *y += x);
+                        }
+                        3 => {
+                            let j = rng.random_range(0..K);
+                            if i != j {
+                                let mut other = take(&mut heaps[j]);
+                                heaps[i].append(&mut other);
+                                let other = take(&mut naive[j]);
+                                naive[i].extend(other);
+                            }
+                        }
+                        _ => {
+                            naive[i].sort_unstable();
+                            if is_max {
+                                naive[i].reverse();
+                            }
+                            let expected = (!naive[i].is_empty()).then(|| naive[i].remove(0));
+                            assert_eq!(heaps[i].pop(), expected);
+                        }
+                    }
+                    assert_eq!(heaps[i].len(), naive[i].len());
+                }
+            }
+        }
+
+        let mut rng = get_test_rng();
+        test::<Max>(&mut rng, true);
+        test::<Min>(&mut rng, false);
+    }
+}
@@ -0,0 +1,202 @@
+//! 線形時間構築のRMQ(Range Minimum Query)
+//!
+//! 静的な配列の区間最小値を求めるデータ構造．
+//! 配列を64要素ずつのブロックに分け，ブロックの最小値に対してSparse Tableを構築する．
+//! ブロック内の区間については，各位置までの単調スタックをビットマスクで持つことで O(1) で求める．
+//!
+//! 区間最大値を求める場合は，要素を[`Reverse`](std::cmp::Reverse)で包む．
+//!
+//! # 計算量
+//! - 構築: O(N)
+//! - 区間最小値の取得: O(1)
+//!
+//! # 使用例
+//! ```
+//! use reprol::ds::linear_rmq::LinearRmq;
+//!
+//! let rmq = LinearRmq::new(vec![3, 5, 4, 100, 1, 4]);
+//! assert_eq!(rmq.min(1..4), Some(&4)); // 区間`[1, 4)`の最小値
+//! assert_eq!(rmq.argmin(..), Some(4)); // 最小値の位置
+//! assert_eq!(rmq.min(2..2), None);
+//! ```
+//!
+//! # 問題例
+//! - [Static RMQ](https://judge.yosupo.jp/problem/staticrmq)
+
+use std::{
+    iter::FromIterator,
+    ops::{Range, RangeBounds},
+};
+
+use crate::utils::normalize_range::normalize_index;
+
+const BLOCK: usize = 64;
+
+/// 線形時間構築のRMQ
+pub struct LinearRmq<T> {
+    v: Vec<T>,
+
+    /// `mask[i]`: `i`を含むブロックの先頭から`i`までの単調スタックに含まれる位置のビットマスク
+    mask: Vec<u64>,
+
+    /// `table[k][b]`: ブロック`b`から`2^k`個のブロックの最小値の位置
+    table: Vec<Vec<usize>>,
+}
+
+impl<T: Ord> LinearRmq<T> {
+    /// 配列`v`から構築する．
+    pub fn new(v: Vec<T>) -> Self {
+        let n = v.len();
+
+        let mut mask = vec![0; n];
+        let mut block_min = Vec::with_capacity(n.div_ceil(BLOCK));
+        for start in (0..n).step_by(BLOCK) {
+            let end = (start + BLOCK).min(n);
+            let mut stack = 0u64;
+            for i in start..end {
+                // 値が大きい位置をスタックから取り除く
+                while stack != 0 {
+                    let top = start + 63 - stack.leading_zeros() as usize;
+                    if v[top] <= v[i] {
+                        break;
+                    }
+                    stack ^= 1 << (top - start);
+                }
+                stack |= 1 << (i - start);
+                mask[i] = stack;
+            }
+            // スタックの底がブロック全体の最小値の位置
+            block_min.push(start + mask[end - 1].trailing_zeros() as usize);
+        }
+
+        let mut table = vec![block_min];
+        let mut k = 1;
+        while 1 << k <= table[0].len() {
+            let prev = &table[k - 1];
+            let next = (0..=table[0].len() - (1 << k))
+                .map(|b| Self::choose(&v, prev[b], prev[b + (1 << (k - 1))]))
+                .collect();
+            table.push(next);
+            k += 1;
+        }
+
+        Self { v, mask, table }
+    }
+
+    /// 配列の長さを返す．
+    pub fn len(&self) -> usize {
+        self.v.len()
+    }
+
+    /// 配列が空かを返す．
+    pub fn is_empty(&self) -> bool {
+        self.v.is_empty()
+    }
+
+    /// `index`番目の要素を返す．
+    pub fn get(&self, index: usize) -> &T {
+        &self.v[index]
+    }
+
+    /// 区間`range`の最小値の位置を返す．
+    /// 最小値が複数ある場合は最も左の位置を返す．区間が空の場合は`None`を返す．
+    pub fn argmin(&self, range: impl RangeBounds<usize>) -> Option<usize> {
+        let Range { start: l, end: r } = normalize_index(range, self.len());
+        assert!(l <= r && r <= self.len());
+        if l == r {
+            return None;
+        }
+
+        let r = r - 1;
+        let (bl, br) = (l / BLOCK, r / BLOCK);
+        if bl == br {
+            return Some(self.in_block(l, r));
+        }
+
+        let mut res = self.in_block(l, (bl + 1) * BLOCK - 1);
+        if bl + 1 < br {
+            let (l, r) = (bl + 1, br);
+            let k = (usize::BITS - 1 - (r - l).leading_zeros()) as usize;
+            res = Self::choose(&self.v, res, self.table[k][l]);
+            res = Self::choose(&self.v, res, self.table[k][r - (1 << k)]);
+        }
+        Some(Self::choose(&self.v, res, self.in_block(br * BLOCK, r)))
+    }
+
+    /// 区間`range`の最小値を返す．区間が空の場合は`None`を返す．
+    pub fn min(&self, range: impl RangeBounds<usize>) -> Option<&T> {
+        self.argmin(range).map(|i| &self.v[i])
+    }
+
+    /// 同じブロック内の区間`[l, r]`の最小値の位置を返す．
+    #[inline]
+    fn in_block(&self, l: usize, r: usize) -> usize {
+        let m = self.mask[r] >> (l % BLOCK);
+        l + m.trailing_zeros() as usize
+    }
+
+    /// 位置`i`と`j`(`i < j`)のうち，値が小さい方(等しければ`i`)を返す．
+    #[inline]
+    fn choose(v: &[T], i: usize, j: usize) -> usize {
+        if v[j] < v[i] { j } else { i }
+    }
+}
+
+impl<T: Ord> From<Vec<T>> for LinearRmq<T> {
+    fn from(v: Vec<T>) -> Self {
+        Self::new(v)
+    }
+}
+
+impl<T: Ord, const N: usize> From<[T; N]> for LinearRmq<T> {
+    fn from(v: [T; N]) -> Self {
+        Self::new(v.into_iter().collect())
+    }
+}
+
+impl<T: Ord> FromIterator<T> for LinearRmq<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Reverse;
+
+    use rand::Rng;
+
+    use super::*;
+    use crate::utils::test_utils::random::get_test_rng;
+
+    #[test]
+    fn test_empty() {
+        let rmq = LinearRmq::<i32>::new(vec![]);
+        assert!(rmq.is_empty());
+        assert_eq!(rmq.argmin(..), None);
+    }
+
+    #[test]
+    fn test_randomized() {
+        let mut rng = get_test_rng();
+
+        for _ in 0..100 {
+            let n = rng.random_range(1..300);
+            let m = rng.random_range(1..1000);
+            let v = (0..n).map(|_| rng.random_range(0..m)).collect::<Vec<_>>();
+            let rmq = LinearRmq::new(v.clone());
+            let rmq_max = LinearRmq::from_iter(v.iter().map(|&x| Reverse(x)));
+
+            for _ in 0..300 {
+                let l = rng.random_range(0..n);
+                let r = rng.random_range(l + 1..=n);
+                let min = *v[l..r].iter().min().unwrap();
+                let argmin = l + v[l..r].iter().position(|&x| x == min).unwrap();
+                assert_eq!(rmq.argmin(l..r), Some(argmin));
+                assert_eq!(rmq.min(l..r), Some(&min));
+                let max = *v[l..r].iter().max().unwrap();
+                assert_eq!(rmq_max.min(l..r), Some(&Reverse(max)));
+            }
+        }
+    }
+}
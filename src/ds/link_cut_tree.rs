@@ -0,0 +1,635 @@
+//! Link-Cut Tree
+//!
+//! 各頂点にモノイドの値を持つ森を管理するデータ構造．
+//! 以下の操作をいずれも償却 O(log N) で処理できる．
+//! - 辺の追加(`link`)・削除(`cut`)．
+//! - 根の変更(`evert`)．
+//! - 2頂点の連結判定，LCAの取得．
+//! - パス上の頂点の値の総積の取得．
+//! - パス上の頂点の値に対する作用の一括適用．
+//!
+//! 各パスをSplay木で管理する．
+//! パスに対する操作では一時的に根を変更するが，操作後には元の根に戻す．
+//! パスの向きを反転するために逆順の総積も保持するため，演算が可換でない場合でもパスの総積を正しく求められる．
+//!
+//! 作用を使わない場合は，作用の型パラメータを省略すると[`ActNone`]が用いられる．
+//!
+//! # 使用例
+//! ```
+//! use reprol::{
+//!     ds::link_cut_tree::LinkCutTree,
+//!     ops::{act_add::ActAdd, op_min::OpMin},
+//! };
+//!
+//! let mut lct = LinkCutTree::<OpMin<i64>, ActAdd<i64>>::from(vec![5, 3, 8, 1, 4]);
+//! lct.link(0, 1);
+//! lct.link(1, 2);
+//! lct.link(3, 1);
+//! assert!(lct.connected(0, 3));
+//! assert!(!lct.connected(0, 4));
+//! assert_eq!(lct.path_fold(0, 2), 3);
+//!
+//! lct.path_act(2, 3, &10); // 頂点2, 1, 3に10を加算
+//! assert_eq!(lct.path_fold(0, 3), 5);
+//! assert_eq!(lct.get(1), &13);
+//!
+//! lct.evert(0);
+//! assert_eq!(lct.lca(2, 3), 1);
+//!
+//! lct.cut(1, 0);
+//! assert!(!lct.connected(0, 2));
+//! ```
+//!
+//! # 問題例
+//! - [Dynamic Tree Vertex Set Path Composite](https://judge.yosupo.jp/problem/dynamic_tree_vertex_set_path_composite)
+
+use std::mem::{replace, swap};
+
+use crate::ops::{act_none::ActNone, action::Action, monoid::Monoid};
+
+const NIL: usize = usize::MAX;
+
+struct Node<O: Monoid, A: Action<O>> {
+    value: O::Element,
+
+    /// Splay木の部分木の総積
+    prod: O::Element,
+
+    /// Splay木の部分木の逆順の総積
+    rev_prod: O::Element,
+
+    /// 子に伝播していない作用
+    lazy: A::Element,
+
+    /// 子に伝播していない反転があるか
+    rev: bool,
+
+    left: usize,
+    right: usize,
+
+    /// Splay木上の親，またはSplay木の根の場合はパスの親
+    parent: usize,
+}
+
+/// Link-Cut Tree
+pub struct LinkCutTree<O: Monoid, A: Action<O> = ActNone> {
+    nodes: Vec<Node<O, A>>,
+
+    /// 演算(モノイド)
+    op: O,
+
+    /// モノイドに対する作用
+    action: A,
+}
+
+impl<O: Monoid, A: Action<O>> LinkCutTree<O, A>
+where
+    O::Element: Clone,
+{
+    /// 頂点数`n`の辺のない森で初期化する．
+    /// 各頂点の値は単位元で初期化される．
+    pub fn new(n: usize) -> Self
+    where
+        O: Default,
+        A: Default,
+    {
+        Self::with_op(n, O::default(), A::default())
+    }
+
+    /// モノイド`op`と作用`action`を指定して頂点数`n`の辺のない森で初期化する．
+    pub fn with_op(n: usize, op: O, action: A) -> Self {
+        let values = (0..n).map(|_| op.id()).collect();
+        Self::from((values, op, action))
+    }
+
+    /// 頂点数を返す．
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// 辺`(u, v)`を追加する．
+    ///
+    /// # Panics
+    ///
+    /// `u`と`v`が既に連結である場合はパニックする．
+    pub fn link(&mut self, u: usize, v: usize) {
+        assert!(!self.connected(u, v), "u and v are already connected");
+        self.evert(u);
+        self.nodes[u].parent = v;
+    }
+
+    /// 辺`(u, v)`を削除する．
+    ///
+    /// # Panics
+    ///
+    /// 辺`(u, v)`が存在しない場合はパニックする．
+    pub fn cut(&mut self, u: usize, v: usize) {
+        self.evert(u);
+        self.access(v);
+        assert!(
+            self.nodes[v].left == u && self.nodes[u].left == NIL && self.nodes[u].right == NIL,
+            "edge not found in LinkCutTree"
+        );
+        self.nodes[v].left = NIL;
+        self.nodes[u].parent = NIL;
+        self.update(v);
+    }
+
+    /// 頂点`v`を根とする．
+    pub fn evert(&mut self, v: usize) {
+        self.access(v);
+        self.toggle(v);
+        self.push(v);
+    }
+
+    /// 頂点`v`を根とする．
+    /// [`evert`](Self::evert)の別名．
+    pub fn make_root(&mut self, v: usize) {
+        self.evert(v);
+    }
+
+    /// 頂点`v`が属する木の根を返す．
+    pub fn root(&mut self, v: usize) -> usize {
+        self.access(v);
+        let mut v = v;
+        loop {
+            self.push(v);
+            if self.nodes[v].left == NIL {
+                break;
+            }
+            v = self.nodes[v].left;
+        }
+        self.splay(v);
+        v
+    }
+
+    /// 頂点`u`と`v`が連結であるかを判定する．
+    pub fn connected(&mut self, u: usize, v: usize) -> bool {
+        self.root(u) == self.root(v)
+    }
+
+    /// 現在の根に関する頂点`u`と`v`のLCAを返す．
+    ///
+    /// # Panics
+    ///
+    /// `u`と`v`が連結でない場合はパニックする．
+    pub fn lca(&mut self, u: usize, v: usize) -> usize {
+        assert!(self.connected(u, v), "u and v are not connected");
+        self.access(u);
+        self.access(v)
+    }
+
+    /// 頂点`v`の値を返す．
+    pub fn get(&mut self, v: usize) -> &O::Element {
+        self.access(v);
+        &self.nodes[v].value
+    }
+
+    /// 頂点`v`の値を`value`に更新する．
+    pub fn set(&mut self, v: usize, value: O::Element) {
+        self.access(v);
+        self.nodes[v].value = value;
+        self.update(v);
+    }
+
+    /// 頂点`u`から`v`へのパス上の頂点の値の総積を返す．
+    ///
+    /// # Panics
+    ///
+    /// `u`と`v`が連結でない場合はパニックする．
+    pub fn path_fold(&mut self, u: usize, v: usize) -> O::Element {
+        let root = self.expose_path(u, v);
+        let res = self.nodes[v].prod.clone();
+        self.evert(root);
+        res
+    }
+
+    /// 頂点`u`から`v`へのパス上の頂点の値に作用`f`を適用する．
+    ///
+    /// # Panics
+    ///
+    /// `u`と`v`が連結でない場合はパニックする．
+    pub fn path_act(&mut self, u: usize, v: usize, f: &A::Element) {
+        let root = self.expose_path(u, v);
+        self.apply(v, f);
+        self.evert(root);
+    }
+
+    /// 頂点`u`を根として，`u`から`v`へのパスを`v`を根とするSplay木にまとめる．
+    /// 元の根を返す．
+    fn expose_path(&mut self, u: usize, v: usize) -> usize {
+        let root = self.root(u);
+        assert_eq!(root, self.root(v), "u and v are not connected");
+        self.evert(u);
+        self.access(v);
+        root
+    }
+
+    /// 根から頂点`v`までのパスを`v`を根とするSplay木にまとめる．
+    /// 直前にまとめられていたパスと新しいパスの分岐点を返す．
+    fn access(&mut self, v: usize) -> usize {
+        let mut last = NIL;
+        let mut cur = v;
+        while cur != NIL {
+            self.splay(cur);
+            self.nodes[cur].right = last;
+            self.update(cur);
+            last = cur;
+            cur = self.nodes[cur].parent;
+        }
+        self.splay(v);
+        last
+    }
+
+    /// 頂点`v`がSplay木の根であるかを判定する．
+    fn is_root(&self, v: usize) -> bool {
+        let p = self.nodes[v].parent;
+        p == NIL || (self.nodes[p].left != v && self.nodes[p].right != v)
+    }
+
+    fn rotate(&mut self, v: usize) {
+        let p = self.nodes[v].parent;
+        let g = self.nodes[p].parent;
+
+        if self.nodes[p].left == v {
+            let c = self.nodes[v].right;
+            self.nodes[p].left = c;
+            if c != NIL {
+                self.nodes[c].parent = p;
+            }
+            self.nodes[v].right = p;
+        } else {
+            let c = self.nodes[v].left;
+            self.nodes[p].right = c;
+            if c != NIL {
+                self.nodes[c].parent = p;
+            }
+            self.nodes[v].left = p;
+        }
+
+        if g != NIL {
+            if self.nodes[g].left == p {
+                self.nodes[g].left = v;
+            } else if self.nodes[g].right == p {
+                self.nodes[g].right = v;
+            }
+        }
+
+        self.nodes[p].parent = v;
+        self.nodes[v].parent = g;
+
+        self.update(p);
+        self.update(v);
+    }
+
+    /// 頂点`v`をSplay木の根に移動する．
+    fn splay(&mut self, v: usize) {
+        let mut path = vec![v];
+        while !self.is_root(*path.last().unwrap()) {
+            path.push(self.nodes[*path.last().unwrap()].parent);
+        }
+        for &u in path.iter().rev() {
+            self.push(u);
+        }
+
+        while !self.is_root(v) {
+            let p = self.nodes[v].parent;
+            if !self.is_root(p) {
+                let g = self.nodes[p].parent;
+                if (self.nodes[g].left == p) == (self.nodes[p].left == v) {
+                    self.rotate(p);
+                } else {
+                    self.rotate(v);
+                }
+            }
+            self.rotate(v);
+        }
+    }
+
+    /// Splay木の部分木全体に作用`f`を適用する．
+    fn apply(&mut self, v: usize, f: &A::Element) {
+        if v == NIL {
+            return;
+        }
+        let node = &mut self.nodes[v];
+        node.value = self.action.act(f, &node.value);
+        node.prod = self.action.act(f, &node.prod);
+        node.rev_prod = self.action.act(f, &node.rev_prod);
+        node.lazy = self.action.op(f, &node.lazy);
+    }
+
+    /// Splay木の部分木全体を反転する．
+    fn toggle(&mut self, v: usize) {
+        if v == NIL {
+            return;
+        }
+        let node = &mut self.nodes[v];
+        swap(&mut node.prod, &mut node.rev_prod);
+        node.rev ^= true;
+    }
+
+    /// 遅延している作用と反転を子に伝播する．
+    fn push(&mut self, v: usize) {
+        let node = &mut self.nodes[v];
+        if node.rev {
+            node.rev = false;
+            swap(&mut node.left, &mut node.right);
+            let (left, right) = (node.left, node.right);
+            self.toggle(left);
+            self.toggle(right);
+        }
+        let node = &mut self.nodes[v];
+        let lazy = replace(&mut node.lazy, self.action.id());
+        let (left, right) = (node.left, node.right);
+        self.apply(left, &lazy);
+        self.apply(right, &lazy);
+    }
+
+    /// 子の情報から頂点`v`の情報を更新する．
+    fn update(&mut self, v: usize) {
+        let Node {
+            left, right, value, ..
+        } = &self.nodes[v];
+
+        let mut prod = value.clone();
+        let mut rev_prod = value.clone();
+        if *left != NIL {
+            let left = &self.nodes[*left];
+            prod = self.op.op(&left.prod, &prod);
+            rev_prod = self.op.op(&rev_prod, &left.rev_prod);
+        }
+        if *right != NIL {
+            let right = &self.nodes[*right];
+            prod = self.op.op(&prod, &right.prod);
+            rev_prod = self.op.op(&right.rev_prod, &rev_prod);
+        }
+
+        self.nodes[v].prod = prod;
+        self.nodes[v].rev_prod = rev_prod;
+    }
+}
+
+impl<O: Monoid, A: Action<O>> From<(Vec<O::Element>, O, A)> for LinkCutTree<O, A>
+where
+    O::Element: Clone,
+{
+    fn from((values, op, action): (Vec<O::Element>, O, A)) -> Self {
+        let nodes = values
+            .into_iter()
+            .map(|value| Node {
+                prod: value.clone(),
+                rev_prod: value.clone(),
+                value,
+                lazy: action.id(),
+                rev: false,
+                left: NIL,
+                right: NIL,
+                parent: NIL,
+            })
+            .collect();
+        Self { nodes, op, action }
+    }
+}
+
+impl<O: Monoid + Default, A: Action<O> + Default> From<Vec<O::Element>> for LinkCutTree<O, A>
+where
+    O::Element: Clone,
+{
+    fn from(values: Vec<O::Element>) -> Self {
+        Self::from((values, O::default(), A::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::*;
+    use crate::{
+        ops::{
+            act_affine::{ActAffine, ActAffineElement},
+            op_add::OpAdd,
+            op_range_sum::{OpRangeSum, OpRangeSumElement},
+        },
+        utils::test_utils::random::get_test_rng,
+    };
+
+    /// 森`adj`上の`u`から`v`へのパスを返す(連結でない場合は`None`)．
+    fn naive_path(adj: &[Vec<usize>], u: usize, v: usize) -> Option<Vec<usize>> {
+        let n = adj.len();
+        let mut parent = vec![NIL; n];
+        let mut visited = vec![false; n];
+        let mut stack = vec![u];
+        visited[u] = true;
+        while let Some(x) = stack.pop() {
+            for &y in &adj[x] {
+                if !visited[y] {
+                    visited[y] = true;
+                    parent[y] = x;
+                    stack.push(y);
+                }
+            }
+        }
+        if !visited[v] {
+            return None;
+        }
+        let mut path = vec![v];
+        while *path.last().unwrap() != u {
+            path.push(parent[*path.last().unwrap()]);
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    fn remove_edge(adj: &mut [Vec<usize>], u: usize, v: usize) {
+        adj[u].retain(|&x| x != v);
+        adj[v].retain(|&x| x != u);
+    }
+
+    #[test]
+    fn test_without_action() {
+        let mut lct = LinkCutTree::<OpAdd<i64>>::from(vec![1, 2, 4, 8, 16]);
+        lct.link(0, 1);
+        lct.link(2, 1);
+        lct.link(3, 2);
+        assert_eq!(lct.path_fold(0, 3), 15);
+        assert_eq!(lct.path_fold(3, 3), 8);
+        lct.set(1, 32);
+        assert_eq!(lct.path_fold(3, 0), 45);
+        assert_eq!(lct.root(3), 1);
+        lct.make_root(3);
+        assert_eq!(lct.root(0), 3);
+        assert_eq!(lct.lca(0, 1), 1);
+        assert_eq!(lct.lca(0, 3), 3);
+        lct.cut(2, 1);
+        assert!(!lct.connected(0, 3));
+        assert!(lct.connected(2, 3));
+        assert_eq!(lct.len(), 5);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_link_connected() {
+        let mut lct = LinkCutTree::<OpAdd<i64>>::new(3);
+        lct.link(0, 1);
+        lct.link(1, 2);
+        lct.link(2, 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_cut_missing_edge() {
+        let mut lct = LinkCutTree::<OpAdd<i64>>::new(3);
+        lct.link(0, 1);
+        lct.link(1, 2);
+        lct.cut(0, 2);
+    }
+
+    #[test]
+    fn test_affine_randomized() {
+        type Op = OpRangeSum<i64>;
+        type Act = ActAffine<i64>;
+
+        let mut rng = get_test_rng();
+
+        const T: usize = 50;
+        const N_MAX: usize = 20;
+        const Q: usize = 1000;
+
+        for _ in 0..T {
+            let n = rng.random_range(1..=N_MAX);
+            let mut naive = (0..n)
+                .map(|_| rng.random_range(-100..=100))
+                .collect::<Vec<i64>>();
+            let mut adj = vec![vec![]; n];
+            let mut edges = vec![];
+            let mut lct = LinkCutTree::<Op, Act>::from(
+                naive
+                    .iter()
+                    .map(|&x| OpRangeSumElement::leaf(x))
+                    .collect::<Vec<_>>(),
+            );
+
+            for _ in 0..Q {
+                let u = rng.random_range(0..n);
+                let v = rng.random_range(0..n);
+                let path = naive_path(&adj, u, v);
+                assert_eq!(lct.connected(u, v), path.is_some());
+                match rng.random_range(0..5) {
+                    0 => {
+                        if path.is_none() {
+                            lct.link(u, v);
+                            adj[u].push(v);
+                            adj[v].push(u);
+                            edges.push((u, v));
+                        }
+                    }
+                    1 if !edges.is_empty() => {
+                        let (a, b) = edges.swap_remove(rng.random_range(0..edges.len()));
+                        lct.cut(b, a);
+                        remove_edge(&mut adj, a, b);
+                    }
+                    2 => {
+                        if let Some(path) = path {
+                            let f = ActAffineElement {
+                                a: rng.random_range(-3..=3),
+                                b: rng.random_range(-100..=100),
+                            };
+                            lct.path_act(u, v, &f);
+                            for x in path {
+                                naive[x] = naive[x].wrapping_mul(f.a).wrapping_add(f.b);
+                            }
+                        }
+                    }
+                    3 => {
+                        if rng.random_ratio(1, 2) {
+                            let x = rng.random_range(-100..=100);
+                            lct.set(u, OpRangeSumElement::leaf(x));
+                            naive[u] = x;
+                        } else {
+                            assert_eq!(lct.get(u).value(), naive[u]);
+                        }
+                    }
+                    _ => {
+                        if let Some(path) = path {
+                            let expected =
+                                path.iter().fold(0i64, |acc, &x| acc.wrapping_add(naive[x]));
+                            let res = lct.path_fold(u, v);
+                            assert_eq!(res.value(), expected);
+                            assert_eq!(res.len(), path.len() as i64);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_non_commutative_randomized() {
+        type Op = ActAffine<i64>;
+
+        let mut rng = get_test_rng();
+
+        const T: usize = 50;
+        const N_MAX: usize = 20;
+        const Q: usize = 1000;
+
+        let op = Op::default();
+
+        for _ in 0..T {
+            let n = rng.random_range(1..=N_MAX);
+            let values = (0..n)
+                .map(|_| ActAffineElement {
+                    a: rng.random_range(-3..=3),
+                    b: rng.random_range(-100..=100),
+                })
+                .collect::<Vec<_>>();
+            let mut adj = vec![vec![]; n];
+            let mut edges = vec![];
+            let mut lct = LinkCutTree::<Op>::from(values.clone());
+
+            for _ in 0..Q {
+                let u = rng.random_range(0..n);
+                let v = rng.random_range(0..n);
+                let path = naive_path(&adj, u, v);
+                match rng.random_range(0..4) {
+                    0 => {
+                        if path.is_none() {
+                            lct.link(u, v);
+                            adj[u].push(v);
+                            adj[v].push(u);
+                            edges.push((u, v));
+                        }
+                    }
+                    1 if !edges.is_empty() => {
+                        let (a, b) = edges.swap_remove(rng.random_range(0..edges.len()));
+                        lct.cut(a, b);
+                        remove_edge(&mut adj, a, b);
+                    }
+                    2 => {
+                        // 根をrとしたときのLCA
+                        let r = rng.random_range(0..n);
+                        if let (Some(pu), Some(pv)) =
+                            (naive_path(&adj, r, u), naive_path(&adj, r, v))
+                        {
+                            let expected = pu.iter().zip(&pv).take_while(|(a, b)| a == b).count();
+                            lct.evert(r);
+                            assert_eq!(lct.lca(u, v), pu[expected - 1]);
+                        }
+                    }
+                    _ => {
+                        if let Some(path) = path {
+                            let expected =
+                                path.iter().fold(op.id(), |acc, &x| op.op(&acc, &values[x]));
+                            assert_eq!(lct.path_fold(u, v), expected);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
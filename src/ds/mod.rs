@@ -1,23 +1,47 @@
 //! データ構造(data structure)
 
+pub mod action_dsu;
 pub mod avl_tree_vec;
+pub mod binary_trie;
 pub mod bitset;
 pub mod cartesian_tree;
+pub mod compressed_fenwick_tree_2d;
+pub mod compressed_segment_tree;
 pub mod cumulative_array;
 pub mod cumulative_array_2d;
 pub mod cumulative_array_3d;
+pub mod cumulative_array_nd;
+pub mod diagonal_imos;
 pub mod disjoint_sparse_table;
 pub mod dsu;
+pub mod euler_tour_tree;
 pub mod fenwick_tree;
+pub mod fenwick_tree_2d;
 pub mod imos;
 pub mod imos2d;
+pub mod imos_nd;
+pub mod interval_heap;
 pub mod interval_map;
+pub mod lazy_avl_tree_vec;
 pub mod lazy_segment_tree;
+pub mod lazy_skew_heap;
+pub mod linear_rmq;
+pub mod link_cut_tree;
 pub mod monoid_dsu;
+pub mod ordered_multiset;
+pub mod persistent_dsu;
+pub mod polynomial_imos;
 pub mod potentialized_dsu;
+pub mod radix_heap;
+pub mod range_add_fenwick_tree;
+pub mod range_mode;
 pub mod range_tree;
+pub mod rollback_dsu;
 pub mod segment_tree;
 pub mod segment_tree_2d;
 pub mod skew_heap;
 pub mod sparse_table;
 pub mod sparse_table_2d;
+pub mod static_range_distinct;
+pub mod static_range_frequency;
+pub mod swag;
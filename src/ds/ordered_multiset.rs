@@ -0,0 +1,407 @@
+//! 順序付き多重集合(Ordered Multiset)
+//!
+//! 要素を昇順に並べた列をAVL木で管理する多重集合．
+//! 以下の操作をいずれも O(log n) で処理できる．
+//! - 要素の挿入・削除．
+//! - k番目に小さい要素の取得．
+//! - ある値未満の要素の個数(順位)の取得．
+//! - 小さい方からk個の要素に対応する値の総積の取得．
+//!
+//! 総積を求める場合は，モノイドと，要素をモノイドの値に変換する関数を指定して構築する．
+//! 総積を求めない場合は[`OrderedMultiSet::new`]で構築する．
+//!
+//! # 使用例
+//! ```
+//! use reprol::{ds::ordered_multiset::OrderedMultiSet, ops::op_add::OpAdd};
+//!
+//! let mut set = OrderedMultiSet::new();
+//! set.insert(3);
+//! set.insert(1);
+//! set.insert(3);
+//! set.insert(5);
+//! assert_eq!(set.len(), 4);
+//! assert_eq!(set.count(&3), 2);
+//! assert_eq!(set.nth(2), Some(&3));
+//! assert_eq!(set.rank(&4), 3);
+//! assert!(set.remove_one(&3));
+//! assert_eq!(set.count(&3), 1);
+//!
+//! // 小さい方からk個の和
+//! let mut set = OrderedMultiSet::with_op(OpAdd::<i64>::default(), |&x| x);
+//! set.extend([5, 1, 4, 1, 3]);
+//! assert_eq!(set.fold_smallest(3), 5); // 1 + 1 + 3
+//! assert_eq!(set.fold(2..), 12); // 3 + 4 + 5
+//! ```
+//!
+//! # 問題例
+//! - [ABC306 E](https://atcoder.jp/contests/abc306/tasks/abc306_e)
+
+use std::ops::{Range, RangeBounds};
+
+use crate::{
+    ds::avl_tree_vec::{
+        Augment, Iter, Link, Node, NodePtr, free, get, len, merge, merge_with_root, split,
+        traverse_postorder,
+    },
+    ops::{monoid::Monoid, op_unit::OpUnit},
+    utils::normalize_range::normalize_index,
+};
+
+/// 木の各ノードが持つ情報
+struct Entry<T, O: Monoid> {
+    key: T,
+
+    /// 要素に対応するモノイドの値
+    value: O::Element,
+
+    /// 部分木の値の総積
+    prod: O::Element,
+}
+
+/// 順序付き多重集合
+pub struct OrderedMultiSet<T, O: Monoid = OpUnit> {
+    root: Link<Entry<T, O>>,
+    op: O,
+
+    /// 要素をモノイドの値に変換する関数
+    map: fn(&T) -> O::Element,
+}
+
+impl<T: Ord> OrderedMultiSet<T> {
+    /// 空の多重集合を生成する．
+    pub fn new() -> Self {
+        Self::with_op(OpUnit, |_| {})
+    }
+}
+
+impl<T: Ord, O: Monoid> OrderedMultiSet<T, O>
+where
+    O::Element: Clone,
+{
+    /// モノイド`op`と，要素をモノイドの値に変換する関数`map`を指定して空の多重集合を生成する．
+    pub fn with_op(op: O, map: fn(&T) -> O::Element) -> Self {
+        Self {
+            root: None,
+            op,
+            map,
+        }
+    }
+
+    /// 要素数を返す．
+    /// 重複する要素はその個数だけ数える．
+    pub fn len(&self) -> usize {
+        len(self.root)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&mut self) {
+        traverse_postorder(self.root.take(), free);
+    }
+
+    /// `value`を1個挿入する．
+    pub fn insert(&mut self, value: T) {
+        let index = self.upper_bound(&value);
+        let mapped = (self.map)(&value);
+        let node = Node::new(Entry {
+            key: value,
+            prod: mapped.clone(),
+            value: mapped,
+        });
+        let (left, right) = split(self.root.take(), index, self);
+        self.root = merge_with_root(left, Some(node), right, self);
+    }
+
+    /// `value`を1個削除する．
+    /// `value`が含まれていない場合は`false`を返す．
+    pub fn remove_one(&mut self, value: &T) -> bool {
+        let index = self.lower_bound(value);
+        let found = self.nth(index) == Some(value);
+        if found {
+            self.remove_range(index..index + 1);
+        }
+        found
+    }
+
+    /// `value`をすべて削除し，削除した個数を返す．
+    pub fn remove_all(&mut self, value: &T) -> usize {
+        let l = self.lower_bound(value);
+        let r = self.upper_bound(value);
+        self.remove_range(l..r);
+        r - l
+    }
+
+    /// `value`の個数を返す．
+    pub fn count(&self, value: &T) -> usize {
+        self.upper_bound(value) - self.lower_bound(value)
+    }
+
+    /// `value`が1個以上含まれているかを判定する．
+    pub fn contains(&self, value: &T) -> bool {
+        self.count(value) > 0
+    }
+
+    /// 小さい方から`k`番目(0-indexed)の要素を返す．
+    pub fn nth(&self, k: usize) -> Option<&T> {
+        get(self.root, k).map(|node| &unsafe { node.as_ref() }.value.key)
+    }
+
+    /// 最小の要素を返す．
+    pub fn first(&self) -> Option<&T> {
+        self.nth(0)
+    }
+
+    /// 最大の要素を返す．
+    pub fn last(&self) -> Option<&T> {
+        self.nth(self.len().checked_sub(1)?)
+    }
+
+    /// 最小の要素を1個削除して返す．
+    pub fn pop_first(&mut self) -> Option<T> {
+        (!self.is_empty()).then(|| self.remove_at(0))
+    }
+
+    /// 最大の要素を1個削除して返す．
+    pub fn pop_last(&mut self) -> Option<T> {
+        let index = self.len().checked_sub(1)?;
+        Some(self.remove_at(index))
+    }
+
+    /// `value`未満の要素の個数を返す．
+    ///
+    /// [`lower_bound`](Self::lower_bound)の別名．
+    pub fn rank(&self, value: &T) -> usize {
+        self.lower_bound(value)
+    }
+
+    /// `value`以上の最小の要素の順位を返す．
+    pub fn lower_bound(&self, value: &T) -> usize {
+        self.bisect(|key| key < value)
+    }
+
+    /// `value`より大きい最小の要素の順位を返す．
+    pub fn upper_bound(&self, value: &T) -> usize {
+        self.bisect(|key| key <= value)
+    }
+
+    /// 順位が`range`に含まれる要素に対応する値の総積を返す．
+    pub fn fold(&self, range: impl RangeBounds<usize>) -> O::Element {
+        let Range { start: l, end: r } = normalize_index(range, self.len());
+        assert!(l <= r);
+        assert!(r <= self.len());
+        self.fold_node(self.root, l, r)
+    }
+
+    /// 小さい方から`k`個の要素に対応する値の総積を返す．
+    /// `k`が要素数以上の場合はすべての要素の総積を返す．
+    pub fn fold_smallest(&self, k: usize) -> O::Element {
+        self.fold(..k.min(self.len()))
+    }
+
+    /// 大きい方から`k`個の要素に対応する値の総積を返す．
+    /// `k`が要素数以上の場合はすべての要素の総積を返す．
+    pub fn fold_largest(&self, k: usize) -> O::Element {
+        self.fold(self.len().saturating_sub(k)..)
+    }
+
+    /// 要素を昇順に返すイテレータを返す．
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        Iter::new(self.root).map(|entry| &entry.key)
+    }
+
+    /// はじめて`f(key)`がfalseとなる要素の順位を返す．
+    fn bisect(&self, mut f: impl FnMut(&T) -> bool) -> usize {
+        let mut res = 0;
+        let mut node = self.root;
+        while let Some(ptr) = node {
+            let raw = unsafe { ptr.as_ref() };
+            if f(&raw.value.key) {
+                res += len(raw.left) + 1;
+                node = raw.right;
+            } else {
+                node = raw.left;
+            }
+        }
+        res
+    }
+
+    /// nodeの部分木のうち[l, r)番目の要素の総積を返す
+    fn fold_node(&self, node: Link<Entry<T, O>>, l: usize, r: usize) -> O::Element {
+        let raw = match node {
+            Some(node) if l < r => unsafe { node.as_ref() },
+            _ => return self.op.id(),
+        };
+        if l == 0 && r == raw.len {
+            return raw.value.prod.clone();
+        }
+
+        let left_len = len(raw.left);
+        let mut res = self.fold_node(raw.left, l, r.min(left_len));
+        if l <= left_len && left_len < r {
+            res = self.op.op(&res, &raw.value.value);
+        }
+        if left_len < r {
+            let right = self.fold_node(raw.right, l.saturating_sub(left_len + 1), r - left_len - 1);
+            res = self.op.op(&res, &right);
+        }
+        res
+    }
+
+    /// 順位が`index`の要素を削除して返す
+    fn remove_at(&mut self, index: usize) -> T {
+        let (left, right) = split(self.root.take(), index, self);
+        let (removed, right) = split(right, 1, self);
+        self.root = merge(left, right, self);
+        unsafe { Box::from_raw(removed.unwrap().as_ptr()) }
+            .value
+            .key
+    }
+
+    /// 順位が`range`に含まれる要素を削除する
+    fn remove_range(&mut self, range: Range<usize>) {
+        let (left, right) = split(self.root.take(), range.start, self);
+        let (removed, right) = split(right, range.len(), self);
+        traverse_postorder(removed, free);
+        self.root = merge(left, right, self);
+    }
+}
+
+impl<T, O: Monoid> Augment<Entry<T, O>> for OrderedMultiSet<T, O>
+where
+    O::Element: Clone,
+{
+    fn update(&self, mut node: NodePtr<Entry<T, O>>) {
+        let raw = unsafe { node.as_mut() };
+        let mut prod = raw.value.value.clone();
+        if let Some(left) = raw.left {
+            prod = self.op.op(&unsafe { left.as_ref() }.value.prod, &prod);
+        }
+        if let Some(right) = raw.right {
+            prod = self.op.op(&prod, &unsafe { right.as_ref() }.value.prod);
+        }
+        raw.value.prod = prod;
+    }
+}
+
+impl<T, O: Monoid> Drop for OrderedMultiSet<T, O> {
+    fn drop(&mut self) {
+        traverse_postorder(self.root, free);
+    }
+}
+
+impl<T: Ord> Default for OrderedMultiSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord, O: Monoid> Extend<T> for OrderedMultiSet<T, O>
+where
+    O::Element: Clone,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|value| self.insert(value));
+    }
+}
+
+impl<T: Ord> FromIterator<T> for OrderedMultiSet<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut res = Self::new();
+        res.extend(iter);
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::*;
+    use crate::{ops::op_add::OpAdd, utils::test_utils::random::get_test_rng};
+
+    #[test]
+    fn test() {
+        let mut set = OrderedMultiSet::from_iter([3, 1, 4, 1, 5, 9, 2, 6]);
+        assert!(set.iter().copied().eq([1, 1, 2, 3, 4, 5, 6, 9]));
+        assert_eq!(set.count(&1), 2);
+        assert_eq!(set.count(&7), 0);
+        assert!(set.contains(&9));
+        assert_eq!(set.nth(3), Some(&3));
+        assert_eq!(set.nth(8), None);
+        assert_eq!(set.rank(&4), 4);
+        assert_eq!(set.upper_bound(&4), 5);
+        assert_eq!(set.lower_bound(&10), 8);
+        assert_eq!(set.first(), Some(&1));
+        assert_eq!(set.last(), Some(&9));
+
+        assert_eq!(set.remove_all(&1), 2);
+        assert_eq!(set.remove_all(&1), 0);
+        assert!(!set.remove_one(&7));
+        assert!(set.remove_one(&9));
+        assert_eq!(set.pop_first(), Some(2));
+        assert_eq!(set.pop_last(), Some(6));
+        assert!(set.iter().copied().eq([3, 4, 5]));
+        assert_eq!(set.len(), 3);
+
+        set.clear();
+        assert!(set.is_empty());
+        assert_eq!(set.pop_first(), None);
+    }
+
+    #[test]
+    fn test_randomized() {
+        let mut rng = get_test_rng();
+
+        const T: usize = 50;
+        const Q: usize = 1000;
+        const V: i64 = 30;
+
+        for _ in 0..T {
+            let mut set = OrderedMultiSet::with_op(OpAdd::<i64>::default(), |&x| x);
+            let mut naive: Vec<i64> = vec![];
+
+            for _ in 0..Q {
+                let x = rng.random_range(-V..=V);
+                match rng.random_range(0..7) {
+                    0 | 1 => {
+                        set.insert(x);
+                        let i = naive.partition_point(|&y| y <= x);
+                        naive.insert(i, x);
+                    }
+                    2 => {
+                        let i = naive.iter().position(|&y| y == x);
+                        assert_eq!(set.remove_one(&x), i.is_some());
+                        if let Some(i) = i {
+                            naive.remove(i);
+                        }
+                    }
+                    3 if rng.random_ratio(1, 5) => {
+                        let count = naive.iter().filter(|&&y| y == x).count();
+                        assert_eq!(set.remove_all(&x), count);
+                        naive.retain(|&y| y != x);
+                    }
+                    4 => {
+                        let k = rng.random_range(0..=naive.len() + 2);
+                        assert_eq!(set.nth(k), naive.get(k));
+                        let c = k.min(naive.len());
+                        assert_eq!(set.fold_smallest(k), naive[..c].iter().sum::<i64>());
+                        assert_eq!(
+                            set.fold_largest(k),
+                            naive[naive.len() - c..].iter().sum::<i64>()
+                        );
+                    }
+                    _ => {
+                        assert_eq!(set.rank(&x), naive.partition_point(|&y| y < x));
+                        assert_eq!(set.upper_bound(&x), naive.partition_point(|&y| y <= x));
+                        assert_eq!(set.count(&x), naive.iter().filter(|&&y| y == x).count());
+                    }
+                }
+                assert_eq!(set.len(), naive.len());
+            }
+
+            assert!(set.iter().eq(naive.iter()));
+        }
+    }
+}
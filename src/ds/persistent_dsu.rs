@@ -0,0 +1,234 @@
+//! 部分永続Disjoint Set Union(Partially Persistent Union-Find)
+//!
+//! 過去の任意の時点における連結性を問い合わせることができるDSU．
+//! 経路圧縮を行わず，union by sizeを用いて各親への辺に統合時刻を記録する．
+//!
+//! 時刻は`merge`を呼び出すたびに1ずつ進む．
+//! 時刻`t`の状態とは，最初の`t`回の`merge`を行った直後の状態を指す(初期状態は時刻`0`)．
+//!
+//! # 計算量
+//! - `find`, `merge`, `connected_at`, `first_connected_time`: O(log N)
+//! - `size_at`: O(log N)
+//!
+//! # 使用例
+//! ```
+//! use reprol::ds::persistent_dsu::PersistentDsu;
+//!
+//! let mut dsu = PersistentDsu::new(4);
+//! dsu.merge(0, 1); // 時刻1
+//! dsu.merge(2, 3); // 時刻2
+//! dsu.merge(1, 2); // 時刻3
+//!
+//! assert!(!dsu.connected_at(0, 1, 0));
+//! assert!(dsu.connected_at(0, 1, 1));
+//! assert!(!dsu.connected_at(0, 3, 2));
+//! assert!(dsu.connected_at(0, 3, 3));
+//! assert_eq!(dsu.size_at(3, 2), 2);
+//! assert_eq!(dsu.size_at(3, 3), 4);
+//! assert_eq!(dsu.first_connected_time(0, 3), Some(3));
+//! assert_eq!(dsu.count_components_at(1), 3);
+//! ```
+
+use std::mem::swap;
+
+pub struct PersistentDsu {
+    parents: Vec<usize>,
+
+    /// 親への辺が張られた時刻(根の場合は`usize::MAX`)
+    merged_times: Vec<usize>,
+
+    /// 各代表元について，(時刻, 要素数)の履歴
+    sizes: Vec<Vec<(usize, usize)>>,
+
+    /// 各時刻における連結成分の個数
+    count_components: Vec<usize>,
+}
+
+impl PersistentDsu {
+    /// 要素数`n`で初期化する．
+    pub fn new(n: usize) -> Self {
+        assert!(n > 0);
+        Self {
+            parents: (0..n).collect(),
+            merged_times: vec![usize::MAX; n],
+            sizes: vec![vec![(0, 1)]; n],
+            count_components: vec![n],
+        }
+    }
+
+    /// 現在の時刻を返す．
+    pub fn now(&self) -> usize {
+        self.count_components.len() - 1
+    }
+
+    /// 要素`v`が属する集合の現在の代表元を返す．
+    pub fn find(&self, v: usize) -> usize {
+        self.find_at(v, self.now())
+    }
+
+    /// 時刻`t`において要素`v`が属する集合の代表元を返す．
+    pub fn find_at(&self, mut v: usize, t: usize) -> usize {
+        while self.merged_times[v] <= t {
+            v = self.parents[v];
+        }
+        v
+    }
+
+    /// 要素`u`と`v`が属する集合を統合し，時刻を1進める．
+    /// 統合が行われた場合は`true`を返す．
+    pub fn merge(&mut self, u: usize, v: usize) -> bool {
+        let t = self.now() + 1;
+        let count = self.count_components[t - 1];
+
+        let mut u = self.find(u);
+        let mut v = self.find(v);
+
+        if u == v {
+            self.count_components.push(count);
+            return false;
+        }
+
+        let size_u = self.sizes[u].last().unwrap().1;
+        let size_v = self.sizes[v].last().unwrap().1;
+        if size_u < size_v {
+            swap(&mut u, &mut v);
+        }
+
+        self.parents[v] = u;
+        self.merged_times[v] = t;
+        self.sizes[u].push((t, size_u + size_v));
+        self.count_components.push(count - 1);
+
+        true
+    }
+
+    /// 要素`u`と`v`が現在同じ集合に属するかを判定する．
+    pub fn connected(&self, u: usize, v: usize) -> bool {
+        self.find(u) == self.find(v)
+    }
+
+    /// 時刻`t`において要素`u`と`v`が同じ集合に属するかを判定する．
+    pub fn connected_at(&self, u: usize, v: usize, t: usize) -> bool {
+        self.find_at(u, t) == self.find_at(v, t)
+    }
+
+    /// 要素`v`が属する集合の現在の要素数を返す．
+    pub fn size(&self, v: usize) -> usize {
+        self.size_at(v, self.now())
+    }
+
+    /// 時刻`t`において要素`v`が属する集合の要素数を返す．
+    pub fn size_at(&self, v: usize, t: usize) -> usize {
+        let sizes = &self.sizes[self.find_at(v, t)];
+        let i = sizes.partition_point(|&(time, _)| time <= t);
+        sizes[i - 1].1
+    }
+
+    /// 現在の連結成分の個数を返す．
+    pub fn count_components(&self) -> usize {
+        self.count_components_at(self.now())
+    }
+
+    /// 時刻`t`における連結成分の個数を返す．
+    pub fn count_components_at(&self, t: usize) -> usize {
+        self.count_components[t.min(self.now())]
+    }
+
+    /// 要素`u`と`v`が初めて同じ集合に属した時刻を返す．
+    /// 現在も異なる集合に属する場合は`None`を返す．
+    pub fn first_connected_time(&self, mut u: usize, mut v: usize) -> Option<usize> {
+        let mut res = 0;
+        while u != v {
+            if self.merged_times[u] < self.merged_times[v] {
+                res = self.merged_times[u];
+                u = self.parents[u];
+            } else if self.merged_times[v] < usize::MAX {
+                res = self.merged_times[v];
+                v = self.parents[v];
+            } else {
+                return None;
+            }
+        }
+        Some(res)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::*;
+    use crate::{ds::dsu::Dsu, utils::test_utils::random::get_test_rng};
+
+    #[test]
+    fn test() {
+        let mut dsu = PersistentDsu::new(6);
+        assert!(dsu.merge(0, 1));
+        assert!(dsu.merge(2, 3));
+        assert!(!dsu.merge(1, 0));
+        assert!(dsu.merge(1, 2));
+        assert_eq!(dsu.now(), 4);
+
+        assert_eq!(dsu.count_components(), 3);
+        assert_eq!(dsu.count_components_at(0), 6);
+        assert_eq!(dsu.count_components_at(3), 4);
+        assert_eq!(dsu.size(3), 4);
+        assert_eq!(dsu.size_at(3, 3), 2);
+        assert_eq!(dsu.size_at(4, 4), 1);
+
+        assert!(dsu.connected(0, 3));
+        assert!(!dsu.connected_at(0, 3, 3));
+        assert!(dsu.connected_at(0, 3, 4));
+        assert!(dsu.connected_at(0, 3, 100));
+
+        assert_eq!(dsu.first_connected_time(0, 0), Some(0));
+        assert_eq!(dsu.first_connected_time(1, 0), Some(1));
+        assert_eq!(dsu.first_connected_time(3, 0), Some(4));
+        assert_eq!(dsu.first_connected_time(0, 4), None);
+        assert_eq!(dsu.first_connected_time(4, 5), None);
+    }
+
+    #[test]
+    fn test_randomized() {
+        let mut rng = get_test_rng();
+
+        const T: usize = 20;
+        const N_MAX: usize = 30;
+        const M: usize = 60;
+
+        for _ in 0..T {
+            let n = rng.random_range(1..=N_MAX);
+            let mut dsu = PersistentDsu::new(n);
+
+            // naive[t]: 時刻tの状態
+            let mut naive = vec![Dsu::new(n)];
+            let mut edges = vec![];
+            for _ in 0..M {
+                let u = rng.random_range(0..n);
+                let v = rng.random_range(0..n);
+                dsu.merge(u, v);
+                edges.push((u, v));
+                let mut d = Dsu::new(n);
+                edges.iter().for_each(|&(u, v)| d.merge(u, v));
+                naive.push(d);
+            }
+
+            for u in 0..n {
+                for v in 0..n {
+                    let expected = (0..=M).find(|&t| naive[t].connected(u, v));
+                    assert_eq!(dsu.first_connected_time(u, v), expected);
+                }
+            }
+
+            for (t, d) in naive.iter_mut().enumerate() {
+                assert_eq!(dsu.count_components_at(t), d.count_components());
+                for u in 0..n {
+                    assert_eq!(dsu.size_at(u, t), d.size(u));
+                    for v in 0..n {
+                        assert_eq!(dsu.connected_at(u, v, t), d.connected(u, v));
+                    }
+                }
+            }
+        }
+    }
+}
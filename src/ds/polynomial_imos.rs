@@ -0,0 +1,192 @@
+//! 多項式いもす法(PolynomialImos)
+//!
+//! 1次元配列の区間に等差数列(1次式)を加算し，最終値を取得するデータ構造．
+//! 区間`[l, r)`の`i`番目に`a + d(i - l)`を加える操作を2階差分で O(log n) で記録し，
+//! 最後に`build`で2回累積をとることで各要素の値が得られる．
+//! 群の要素の整数倍は繰り返し二乗法で計算するため，[`ModInt`](crate::math::modint::ModInt)などの可換群で使える．
+//!
+//! # 計算量
+//! - `add`: O(log n)
+//! - `build`: O(n)
+//!
+//! # 使用例
+//! ```
+//! use reprol::{ds::polynomial_imos::PolynomialImos, ops::op_add::OpAdd};
+//!
+//! let mut imos = PolynomialImos::<OpAdd<i64>>::new(6);
+//! imos.add(1..5, 10, 3); // 10, 13, 16, 19
+//! imos.add(3..=5, 1, -1); // 1, 0, -1
+//! imos.build();
+//! assert_eq!(imos.get(0), 0);
+//! assert_eq!(imos.get(1), 10);
+//! assert_eq!(imos.get(3), 17);
+//! assert_eq!(imos.get(4), 19);
+//! assert_eq!(imos.get(5), -1);
+//! ```
+
+use std::ops::{Index, Range, RangeBounds};
+
+use crate::{ops::group::Group, utils::normalize_range::normalize_index};
+
+/// 1次元配列上の区間への等差数列の加算を管理するいもす法
+pub struct PolynomialImos<O: Group> {
+    n: usize,
+    imos: Vec<O::Element>,
+    has_built: bool,
+    op: O,
+}
+
+impl<O: Group> PolynomialImos<O> {
+    /// 長さ`n`の単位元で初期化されたテーブルを構築する．
+    pub fn new(n: usize) -> Self
+    where
+        O: Default,
+    {
+        Self::with_op(n, O::default())
+    }
+
+    /// 演算`op`を明示的に渡してテーブルを構築する．
+    pub fn with_op(n: usize, op: O) -> Self {
+        Self {
+            n,
+            imos: (0..n + 2).map(|_| op.id()).collect(),
+            has_built: false,
+            op,
+        }
+    }
+
+    /// 区間`range = [l, r)`の`i`番目に`a + d(i - l)`を加算する．
+    ///
+    /// `build`前にのみ呼び出せる．
+    pub fn add(&mut self, range: impl RangeBounds<usize>, a: O::Element, d: O::Element) {
+        assert!(!self.has_built);
+
+        let Range { start: l, end: r } = normalize_index(range, self.n);
+        assert!(l <= r && r <= self.n);
+        if l == r {
+            return;
+        }
+
+        // 1階差分は l で a，(l, r) で d，r で -(a + d(r - l - 1))
+        let last = self.op.op(&a, &self.pow(&d, r - l - 1));
+        let inv_a = self.op.inv(&a);
+        let inv_d = self.op.inv(&d);
+        let inv_last = self.op.inv(&last);
+        self.apply(l, &a);
+        self.apply(l + 1, &inv_a);
+        self.apply(l + 1, &d);
+        self.apply(r, &inv_d);
+        self.apply(r, &inv_last);
+        self.apply(r + 1, &last);
+    }
+
+    /// いもす法の前計算を行い，各位置の値を確定させる．
+    pub fn build(&mut self) {
+        assert!(!self.has_built);
+
+        for _ in 0..2 {
+            for i in 0..self.n {
+                self.imos[i + 1] = self.op.op(&self.imos[i], &self.imos[i + 1]);
+            }
+        }
+
+        self.has_built = true;
+    }
+
+    /// `build`後に`i`番目の値を返す．
+    pub fn get(&self, i: usize) -> O::Element
+    where
+        O::Element: Clone,
+    {
+        self[i].clone()
+    }
+
+    #[inline]
+    fn apply(&mut self, i: usize, x: &O::Element) {
+        self.imos[i] = self.op.op(&self.imos[i], x);
+    }
+
+    /// `x`を`k`個演算した値を返す．
+    fn pow(&self, x: &O::Element, mut k: usize) -> O::Element {
+        let mut res = self.op.id();
+        // 要素は`Clone`とは限らないので単位元との演算で複製する
+        let mut base = self.op.op(&self.op.id(), x);
+        while k > 0 {
+            if k & 1 == 1 {
+                res = self.op.op(&res, &base);
+            }
+            base = self.op.op(&base, &base);
+            k >>= 1;
+        }
+        res
+    }
+}
+
+impl<O: Group> Index<usize> for PolynomialImos<O> {
+    type Output = O::Element;
+    fn index(&self, index: usize) -> &Self::Output {
+        assert!(self.has_built);
+        assert!(index < self.n);
+        &self.imos[index]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::*;
+    use crate::{
+        math::modint::ModInt998244353, ops::op_add::OpAdd, utils::test_utils::random::get_test_rng,
+    };
+
+    #[test]
+    fn test_random() {
+        let mut rng = get_test_rng();
+
+        for _ in 0..100 {
+            let n = rng.random_range(1..=100);
+            let mut imos = PolynomialImos::<OpAdd<i64>>::new(n);
+            let mut naive = vec![0i64; n];
+
+            for _ in 0..100 {
+                let l = rng.random_range(0..=n);
+                let r = rng.random_range(l..=n);
+                let a = rng.random_range(-1000..=1000);
+                let d = rng.random_range(-1000..=1000);
+                imos.add(l..r, a, d);
+                for (k, x) in naive[l..r].iter_mut().enumerate() {
+                    *x += a + d * k as i64;
+                }
+            }
+
+            imos.build();
+
+            for i in 0..n {
+                assert_eq!(imos.get(i), naive[i]);
+                assert_eq!(imos[i], naive[i]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_modint() {
+        type Mint = ModInt998244353;
+
+        let mut imos = PolynomialImos::<OpAdd<Mint>>::new(4);
+        imos.add(.., Mint::new(1), Mint::new(998244352));
+        imos.build();
+        let expected = [1, 0, 998244352, 998244351];
+        for (i, &x) in expected.iter().enumerate() {
+            assert_eq!(imos[i], Mint::new(x));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_get_before_build() {
+        let mut imos = PolynomialImos::<OpAdd<i64>>::new(3);
+        imos.add(0..2, 1, 1);
+        let _ = imos.get(0);
+    }
+}
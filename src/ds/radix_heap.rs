@@ -0,0 +1,206 @@
+//! Radix Heap
+//!
+//! 取り出すキーが単調非減少である場合に使える，符号なし整数をキーとする優先度付きキュー．
+//! 最後に取り出したキーとのxorの最上位ビットでバケットに振り分ける．
+//! 以下の操作をいずれも償却 O(log C) で処理できる(Cはキーの最大値)．
+//! - 要素の追加．
+//! - 最小のキーを持つ要素の削除．
+//!
+//! ダイクストラ法のように，取り出したキー以上のキーしか追加しない場合に用いる．
+//!
+//! # 使用例
+//! ```
+//! use reprol::ds::radix_heap::RadixHeap;
+//!
+//! let mut heap = RadixHeap::new();
+//! heap.push(5u32, 'a');
+//! heap.push(2u32, 'b');
+//! heap.push(9u32, 'c');
+//! assert_eq!(heap.pop(), Some((2, 'b')));
+//! heap.push(3, 'd');
+//! assert_eq!(heap.pop(), Some((3, 'd')));
+//! assert_eq!(heap.pop(), Some((5, 'a')));
+//! assert_eq!(heap.len(), 1);
+//! ```
+
+use std::iter::FromIterator;
+
+/// [`RadixHeap`]のキーとして使える符号なし整数型．
+pub trait RadixKey: Copy + Ord {
+    /// ビット幅
+    const BITS: usize;
+
+    /// 最小値
+    const MIN: Self;
+
+    /// `self`と`last`のxorの最上位ビットの位置に1を足したものを返す．一致する場合は0を返す．
+    fn bucket(self, last: Self) -> usize;
+}
+
+macro_rules! impl_radix_key {
+    ($($ty:ty),*) => {
+        $(
+            impl RadixKey for $ty {
+                const BITS: usize = <$ty>::BITS as usize;
+                const MIN: Self = 0;
+
+                #[inline(always)]
+                fn bucket(self, last: Self) -> usize {
+                    (<$ty>::BITS - (self ^ last).leading_zeros()) as usize
+                }
+            }
+        )*
+    };
+}
+
+impl_radix_key! { u8, u16, u32, u64, u128, usize }
+
+/// Radix Heap
+pub struct RadixHeap<K, V> {
+    buckets: Vec<Vec<(K, V)>>,
+
+    /// 最後に取り出したキー
+    last: K,
+
+    len: usize,
+}
+
+impl<K: RadixKey, V> RadixHeap<K, V> {
+    /// 空のヒープを生成する．
+    pub fn new() -> Self {
+        Self {
+            buckets: (0..=K::BITS).map(|_| Vec::new()).collect(),
+            last: K::MIN,
+            len: 0,
+        }
+    }
+
+    /// 要素数を返す．
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// ヒープが空かを返す．
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// すべての要素を削除する．最後に取り出したキーも初期化される．
+    pub fn clear(&mut self) {
+        self.buckets.iter_mut().for_each(|bucket| bucket.clear());
+        self.last = K::MIN;
+        self.len = 0;
+    }
+
+    /// キー`key`を持つ要素`value`を追加する．
+    ///
+    /// # 制約
+    /// - `key`は最後に取り出したキー以上
+    pub fn push(&mut self, key: K, value: V) {
+        assert!(self.last <= key);
+        self.buckets[key.bucket(self.last)].push((key, value));
+        self.len += 1;
+    }
+
+    /// 最小のキーを持つ要素を削除して返す．
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        if self.len == 0 {
+            return None;
+        }
+
+        if self.buckets[0].is_empty() {
+            let i = self.buckets.iter().position(|bucket| !bucket.is_empty())?;
+            let bucket = std::mem::take(&mut self.buckets[i]);
+            self.last = bucket.iter().map(|&(key, _)| key).min()?;
+            for (key, value) in bucket {
+                self.buckets[key.bucket(self.last)].push((key, value));
+            }
+        }
+
+        self.len -= 1;
+        self.buckets[0].pop()
+    }
+}
+
+impl<K: RadixKey, V> Default for RadixHeap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: RadixKey, V> Extend<(K, V)> for RadixHeap<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        iter.into_iter()
+            .for_each(|(key, value)| self.push(key, value));
+    }
+}
+
+impl<K: RadixKey, V> FromIterator<(K, V)> for RadixHeap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut res = Self::new();
+        res.extend(iter);
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cmp::Reverse, collections::BinaryHeap};
+
+    use rand::Rng;
+
+    use super::*;
+    use crate::utils::test_utils::random::get_test_rng;
+
+    #[test]
+    fn test() {
+        let mut heap = RadixHeap::from_iter([(0u64, 0), (u64::MAX, 1), (1 << 40, 2)]);
+        assert_eq!(heap.pop(), Some((0, 0)));
+        assert_eq!(heap.pop(), Some((1 << 40, 2)));
+        heap.push(u64::MAX, 3);
+        assert_eq!(heap.pop().map(|(key, _)| key), Some(u64::MAX));
+        assert_eq!(heap.pop().map(|(key, _)| key), Some(u64::MAX));
+        assert_eq!(heap.pop(), None);
+        assert!(heap.is_empty());
+
+        heap.clear();
+        heap.push(0, 4);
+        assert_eq!(heap.pop(), Some((0, 4)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn push_panics_when_key_is_less_than_last() {
+        let mut heap = RadixHeap::new();
+        heap.push(5u32, ());
+        heap.pop();
+        heap.push(4, ());
+    }
+
+    #[test]
+    fn test_randomized() {
+        let mut rng = get_test_rng();
+
+        for _ in 0..100 {
+            let mut heap = RadixHeap::new();
+            let mut naive = BinaryHeap::new();
+            let mut last = 0u32;
+
+            for i in 0..1000 {
+                if rng.random_ratio(3, 5) {
+                    let key = last + rng.random_range(0..1000);
+                    heap.push(key, i);
+                    naive.push(Reverse((key, i)));
+                } else {
+                    let expected = naive.pop().map(|Reverse(x)| x);
+                    let actual = heap.pop();
+                    assert_eq!(actual.map(|(key, _)| key), expected.map(|(key, _)| key));
+                    if let Some((key, _)) = actual {
+                        last = key;
+                    }
+                }
+                assert_eq!(heap.len(), naive.len());
+            }
+        }
+    }
+}
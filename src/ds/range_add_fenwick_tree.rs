@@ -0,0 +1,250 @@
+//! 区間加算Fenwick Tree(Range Add Fenwick Tree)
+//!
+//! 2本のFenwick Treeを用いて，区間加算と区間和の取得をいずれもO(logN)で行うデータ構造．
+//!
+//! 区間`[l, r)`に`x`を加算するとき，接頭辞和`S(i) = sum(v[0..i])`は
+//! `i`の1次式`a(i) * i + b(i)`で表せる．係数`a`, `b`をそれぞれFenwick Treeで管理する．
+//!
+//! # 使用例
+//! ```
+//! use reprol::ds::range_add_fenwick_tree::RangeAddFenwickTree;
+//! let mut ft = RangeAddFenwickTree::<i64>::new(5);
+//! ft.add(1..4, 3); // v[1..4] += 3
+//! ft.add(2.., 2); // v[2..5] += 2
+//! assert_eq!(ft.get(1), 3);
+//! assert_eq!(ft.get(2), 5);
+//! assert_eq!(ft.fold(..), 15); // 0 + 3 + 5 + 5 + 2
+//! assert_eq!(ft.fold(2..=3), 10);
+//! ```
+//!
+//! # Notes
+//!
+//! 整数型では wrapping 演算を用いるため，オーバーフロー時は折り返す．
+
+use std::ops::{Range, RangeBounds};
+
+use crate::{
+    ds::fenwick_tree::FenwickTree,
+    math::modint::ModInt,
+    ops::{group::Group, monoid::Monoid, op_add::OpAdd},
+    utils::normalize_range::normalize_index,
+};
+
+/// 区間加算Fenwick Tree
+#[allow(private_bounds)]
+pub struct RangeAddFenwickTree<T>
+where
+    T: Copy + HasMul + HasFromIndex,
+    OpAdd<T>: Group<Element = T> + Default,
+{
+    /// 列の長さ
+    len: usize,
+
+    /// 接頭辞和の1次の係数
+    coef: FenwickTree<OpAdd<T>>,
+
+    /// 接頭辞和の定数項
+    constant: FenwickTree<OpAdd<T>>,
+}
+
+#[allow(private_bounds)]
+impl<T> RangeAddFenwickTree<T>
+where
+    T: Copy + HasMul + HasFromIndex,
+    OpAdd<T>: Group<Element = T> + Default,
+{
+    /// 長さ`n`で初期化する．
+    /// 要素はすべてゼロ値で初期化される．
+    pub fn new(n: usize) -> Self {
+        Self {
+            len: n,
+            coef: FenwickTree::new(n + 1),
+            constant: FenwickTree::new(n + 1),
+        }
+    }
+
+    /// 区間`range`の各要素に`x`を加算する．
+    pub fn add(&mut self, range: impl RangeBounds<usize>, x: T) {
+        let Range { start: l, end: r } = normalize_index(range, self.len);
+        let neg_x = OpAdd::<T>::default().inv(&x);
+        self.coef.op(l, &x);
+        self.coef.op(r, &neg_x);
+        self.constant.op(l, &neg_x.mul(T::from_index(l)));
+        self.constant.op(r, &x.mul(T::from_index(r)));
+    }
+
+    /// `index`番目の要素の値を返す．
+    pub fn get(&self, index: usize) -> T {
+        assert!(index < self.len);
+        self.fold(index..=index)
+    }
+
+    /// 区間`[0, r)`の区間和を返す．
+    pub fn prefix(&self, r: usize) -> T {
+        assert!(r <= self.len);
+        let op = OpAdd::<T>::default();
+        op.op(
+            &self.coef.prefix(r).mul(T::from_index(r)),
+            &self.constant.prefix(r),
+        )
+    }
+
+    /// 区間`range`の区間和を返す．
+    pub fn fold(&self, range: impl RangeBounds<usize>) -> T {
+        let Range { start: l, end: r } = normalize_index(range, self.len);
+        let op = OpAdd::<T>::default();
+        op.op(&self.prefix(r), &op.inv(&self.prefix(l)))
+    }
+}
+
+#[allow(private_bounds)]
+impl<T> From<Vec<T>> for RangeAddFenwickTree<T>
+where
+    T: Copy + HasMul + HasFromIndex,
+    OpAdd<T>: Group<Element = T> + Default,
+{
+    fn from(v: Vec<T>) -> Self {
+        let mut res = Self::new(v.len());
+        v.into_iter()
+            .enumerate()
+            .for_each(|(i, x)| res.add(i..=i, x));
+        res
+    }
+}
+
+#[allow(private_bounds)]
+impl<T, const N: usize> From<[T; N]> for RangeAddFenwickTree<T>
+where
+    T: Copy + HasMul + HasFromIndex,
+    OpAdd<T>: Group<Element = T> + Default,
+{
+    fn from(v: [T; N]) -> Self {
+        Self::from(v.into_iter().collect::<Vec<_>>())
+    }
+}
+
+#[allow(private_bounds)]
+impl<T> FromIterator<T> for RangeAddFenwickTree<T>
+where
+    T: Copy + HasMul + HasFromIndex,
+    OpAdd<T>: Group<Element = T> + Default,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
+/// 型固有の乗算を提供するトレイト．
+trait HasMul {
+    fn mul(self, rhs: Self) -> Self;
+}
+
+/// インデックスから型の値への変換を提供するトレイト．
+trait HasFromIndex {
+    fn from_index(index: usize) -> Self;
+}
+
+macro_rules! impl_range_add_fenwick_tree_traits_inner {
+    ($ty: ty) => {
+        impl HasMul for $ty {
+            #[inline(always)]
+            fn mul(self, rhs: Self) -> Self {
+                self.wrapping_mul(rhs)
+            }
+        }
+
+        impl HasFromIndex for $ty {
+            #[inline(always)]
+            fn from_index(index: usize) -> Self {
+                index as $ty
+            }
+        }
+    };
+}
+
+macro_rules! impl_range_add_fenwick_tree_traits {
+    ($($ty: ty),* $(,)?) => {
+        $( impl_range_add_fenwick_tree_traits_inner!($ty); )*
+    };
+}
+
+impl_range_add_fenwick_tree_traits! {
+    i8, i16, i32, i64, i128, isize,
+    u8, u16, u32, u64, u128, usize,
+}
+
+impl<const P: u64> HasMul for ModInt<P> {
+    #[inline(always)]
+    fn mul(self, rhs: Self) -> Self {
+        self * rhs
+    }
+}
+
+impl<const P: u64> HasFromIndex for ModInt<P> {
+    #[inline(always)]
+    fn from_index(index: usize) -> Self {
+        Self::new(index as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::*;
+    use crate::utils::test_utils::random::get_test_rng;
+
+    #[test]
+    fn test_range_add_range_sum() {
+        let mut ft = RangeAddFenwickTree::<i64>::from(vec![1, 2, 3, 4, 5]);
+        assert_eq!(ft.fold(..), 15);
+        ft.add(1..3, 10);
+        assert_eq!(ft.fold(..), 35);
+        assert_eq!(ft.fold(1..2), 12);
+        assert_eq!(ft.get(2), 13);
+        assert_eq!(ft.get(3), 4);
+        ft.add(.., -1);
+        assert_eq!(ft.fold(..), 30);
+        assert_eq!(ft.prefix(0), 0);
+        assert_eq!(ft.prefix(2), 11);
+    }
+
+    #[test]
+    fn test_modint() {
+        type Mint = ModInt<998_244_353>;
+        let mut ft = RangeAddFenwickTree::<Mint>::new(4);
+        ft.add(0..4, Mint::new(998_244_352));
+        ft.add(1..3, Mint::new(2));
+        assert_eq!(ft.fold(..), Mint::new(0));
+        assert_eq!(ft.get(1), Mint::new(1));
+    }
+
+    #[test]
+    fn test_randomized() {
+        let mut rng = get_test_rng();
+
+        const T: usize = 20;
+        const Q: usize = 10000;
+        const N_MAX: usize = 100;
+
+        for _ in 0..T {
+            let n = rng.random_range(1..=N_MAX);
+            let mut v = (0..n)
+                .map(|_| rng.random_range(-1000000000..=1000000000))
+                .collect::<Vec<i64>>();
+            let mut ft = RangeAddFenwickTree::from(v.clone());
+
+            for _ in 0..Q {
+                let l = rng.random_range(0..=n);
+                let r = rng.random_range(l..=n);
+                if rng.random_ratio(1, 2) {
+                    let x = rng.random_range(-1000000000..=1000000000);
+                    ft.add(l..r, x);
+                    v[l..r].iter_mut().for_each(|vi| *vi += x);
+                } else {
+                    assert_eq!(ft.fold(l..r), v[l..r].iter().sum::<i64>());
+                }
+            }
+        }
+    }
+}
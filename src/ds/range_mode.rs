@@ -0,0 +1,180 @@
+//! 区間最頻値(Range Mode)
+//!
+//! 配列の区間の最頻値とその出現回数を，平方分割によりオンラインで求める．
+//! ブロックの区間ごとに最頻値を前計算しておき，端の要素については値ごとの出現位置のリストを使って
+//! 出現回数が現在の最大値を超えるかを1つずつ確かめる．
+//!
+//! # 計算量
+//! - 構築: O(N √N)
+//! - クエリ: O(√N)
+//!
+//! # 使用例
+//! ```
+//! use reprol::ds::range_mode::RangeMode;
+//!
+//! let rm = RangeMode::new(&[3, 1, 3, 2, 2, 2, 1]);
+//! assert_eq!(rm.mode(0..3), Some((&3, 2)));
+//! assert_eq!(rm.mode(..), Some((&2, 3)));
+//! assert_eq!(rm.mode(1..2), Some((&1, 1)));
+//! assert_eq!(rm.mode(4..4), None);
+//! ```
+
+use std::ops::{Range, RangeBounds};
+
+use crate::{bisect::SliceBoundsExt, utils::normalize_range::normalize_index};
+
+/// 区間最頻値
+pub struct RangeMode<T> {
+    /// 座標圧縮後の値に対応する元の値
+    values: Vec<T>,
+
+    /// 座標圧縮後の配列
+    compressed: Vec<usize>,
+
+    /// 値ごとの出現位置(昇順)
+    positions: Vec<Vec<usize>>,
+
+    /// `index[i]`: `i`番目の要素が，その値の出現位置のリストで何番目か
+    index: Vec<usize>,
+
+    /// ブロックの大きさ
+    block: usize,
+
+    /// `modes[i][j]`: ブロック`i`から`j`までの(最頻値, 出現回数)
+    modes: Vec<Vec<(usize, usize)>>,
+}
+
+impl<T: Ord + Clone> RangeMode<T> {
+    /// 配列`v`から構築する．
+    pub fn new(v: &[T]) -> Self {
+        let n = v.len();
+
+        let mut values = v.to_vec();
+        values.sort_unstable();
+        values.dedup();
+        let compressed = v.iter().map(|x| values.lower_bound(x)).collect::<Vec<_>>();
+
+        let mut positions = vec![vec![]; values.len()];
+        let mut index = vec![0; n];
+        for (i, &x) in compressed.iter().enumerate() {
+            index[i] = positions[x].len();
+            positions[x].push(i);
+        }
+
+        let block = (n as f64).sqrt().ceil().max(1.0) as usize;
+        let num_blocks = n.div_ceil(block);
+        let mut modes = vec![vec![]; num_blocks];
+        let mut count = vec![0; values.len()];
+        for (i, modes) in modes.iter_mut().enumerate() {
+            let mut best = (0, 0);
+            for j in i..num_blocks {
+                for &x in &compressed[j * block..((j + 1) * block).min(n)] {
+                    count[x] += 1;
+                    if count[x] > best.1 {
+                        best = (x, count[x]);
+                    }
+                }
+                modes.push(best);
+            }
+            compressed[i * block..].iter().for_each(|&x| count[x] = 0);
+        }
+
+        Self {
+            values,
+            compressed,
+            positions,
+            index,
+            block,
+            modes,
+        }
+    }
+
+    /// 配列の長さを返す．
+    pub fn len(&self) -> usize {
+        self.compressed.len()
+    }
+
+    /// 配列が空かを返す．
+    pub fn is_empty(&self) -> bool {
+        self.compressed.is_empty()
+    }
+
+    /// 区間`range`の最頻値とその出現回数を返す．
+    /// 最頻値が複数ある場合はそのいずれかを返す．区間が空の場合は`None`を返す．
+    pub fn mode(&self, range: impl RangeBounds<usize>) -> Option<(&T, usize)> {
+        let n = self.len();
+        let Range { start: l, end: r } = normalize_index(range, n);
+        assert!(l <= r && r <= n);
+        if l == r {
+            return None;
+        }
+
+        // 区間に完全に含まれるブロック[bl, br)
+        let bl = l.div_ceil(self.block);
+        let br = r / self.block;
+
+        let (mut mode, mut freq, left, right) = if bl < br {
+            let (mode, freq) = self.modes[bl][br - 1 - bl];
+            (mode, freq, l..bl * self.block, br * self.block..r)
+        } else {
+            (self.compressed[l], 0, l..r, r..r)
+        };
+
+        for i in left {
+            let x = self.compressed[i];
+            let pos = &self.positions[x];
+            while self.index[i] + freq < pos.len() && pos[self.index[i] + freq] < r {
+                mode = x;
+                freq += 1;
+            }
+        }
+
+        for i in right {
+            let x = self.compressed[i];
+            let pos = &self.positions[x];
+            while self.index[i] >= freq && pos[self.index[i] - freq] >= l {
+                mode = x;
+                freq += 1;
+            }
+        }
+
+        Some((&self.values[mode], freq))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::*;
+    use crate::utils::test_utils::random::get_test_rng;
+
+    #[test]
+    fn test_randomized() {
+        let mut rng = get_test_rng();
+
+        for _ in 0..100 {
+            let n = rng.random_range(0..100);
+            let m = rng.random_range(1..20);
+            let v = (0..n).map(|_| rng.random_range(0..m)).collect::<Vec<_>>();
+            let rm = RangeMode::new(&v);
+            assert_eq!(rm.len(), n);
+
+            for _ in 0..100 {
+                let l = rng.random_range(0..=n);
+                let r = rng.random_range(l..=n);
+                let mut count = vec![0; m];
+                v[l..r].iter().for_each(|&x| count[x] += 1);
+                let max = count.iter().copied().max().unwrap();
+
+                match rm.mode(l..r) {
+                    None => assert_eq!(l, r),
+                    Some((&x, freq)) => {
+                        assert_eq!(freq, max);
+                        assert_eq!(count[x], max);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,205 @@
+//! Rollback可能なDisjoint Set Union(Undoable Union-Find)
+//!
+//! 経路圧縮を行わず，union by sizeのみを用いるDSU．
+//! 統合操作を記録しておき，任意の時点の状態に巻き戻すことができる．
+//!
+//! # 計算量
+//! - `find`, `merge`, `connected`, `size`: O(log N)
+//! - `rollback`: 巻き戻す統合操作1回あたりO(1)
+//!
+//! # 使用例
+//! ```
+//! use reprol::ds::rollback_dsu::RollbackDsu;
+//!
+//! let mut dsu = RollbackDsu::new(5);
+//! dsu.merge(0, 1);
+//! let snapshot = dsu.snapshot();
+//! dsu.merge(1, 2);
+//! dsu.merge(3, 4);
+//! assert!(dsu.connected(0, 2));
+//! assert_eq!(dsu.count_components(), 2);
+//!
+//! dsu.rollback(snapshot);
+//! assert!(dsu.connected(0, 1));
+//! assert!(!dsu.connected(0, 2));
+//! assert_eq!(dsu.count_components(), 4);
+//! ```
+
+use std::mem::swap;
+
+pub struct RollbackDsu {
+    parents: Vec<usize>,
+    sizes: Vec<usize>,
+    count_components: usize,
+
+    /// 統合操作の履歴
+    /// `(u, v)`: 代表元`v`を代表元`u`の子にした
+    history: Vec<(usize, usize)>,
+}
+
+impl RollbackDsu {
+    /// 要素数`n`で初期化する．
+    pub fn new(n: usize) -> Self {
+        assert!(n > 0);
+        Self {
+            parents: (0..n).collect(),
+            sizes: vec![1; n],
+            count_components: n,
+            history: vec![],
+        }
+    }
+
+    /// 要素`v`が属する集合の代表元を返す．
+    pub fn find(&self, mut v: usize) -> usize {
+        while self.parents[v] != v {
+            v = self.parents[v];
+        }
+        v
+    }
+
+    /// 要素`u`と`v`が属する集合を統合する．
+    /// 統合が行われた場合は`true`を返す．
+    pub fn merge(&mut self, u: usize, v: usize) -> bool {
+        let mut u = self.find(u);
+        let mut v = self.find(v);
+
+        if u == v {
+            return false;
+        }
+
+        if self.sizes[u] < self.sizes[v] {
+            swap(&mut u, &mut v);
+        }
+
+        self.sizes[u] += self.sizes[v];
+        self.parents[v] = u;
+        self.count_components -= 1;
+        self.history.push((u, v));
+
+        true
+    }
+
+    /// 要素`u`と`v`が同じ集合に属するかを判定する．
+    pub fn connected(&self, u: usize, v: usize) -> bool {
+        self.find(u) == self.find(v)
+    }
+
+    /// 要素`v`が属する集合の要素数を返す．
+    pub fn size(&self, v: usize) -> usize {
+        self.sizes[self.find(v)]
+    }
+
+    /// 連結成分の個数を返す．
+    pub fn count_components(&self) -> usize {
+        self.count_components
+    }
+
+    /// 現在の状態を表すスナップショットを返す．
+    pub fn snapshot(&self) -> usize {
+        self.history.len()
+    }
+
+    /// 直前の統合操作を1回取り消す．
+    /// 取り消す操作がない場合は`false`を返す．
+    pub fn undo(&mut self) -> bool {
+        let Some((u, v)) = self.history.pop() else {
+            return false;
+        };
+        self.parents[v] = v;
+        self.sizes[u] -= self.sizes[v];
+        self.count_components += 1;
+        true
+    }
+
+    /// スナップショット`snapshot`を取得した時点の状態に巻き戻す．
+    pub fn rollback(&mut self, snapshot: usize) {
+        assert!(snapshot <= self.history.len());
+        while self.history.len() > snapshot {
+            self.undo();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::*;
+    use crate::{ds::dsu::Dsu, utils::test_utils::random::get_test_rng};
+
+    #[test]
+    fn test() {
+        let mut dsu = RollbackDsu::new(6);
+
+        assert!(dsu.merge(0, 1));
+        assert!(dsu.merge(2, 3));
+        assert!(!dsu.merge(1, 0));
+        assert_eq!(dsu.count_components(), 4);
+
+        let s = dsu.snapshot();
+        assert!(dsu.merge(1, 2));
+        assert!(dsu.merge(4, 5));
+        assert_eq!(dsu.size(3), 4);
+        assert_eq!(dsu.count_components(), 2);
+
+        assert!(dsu.undo());
+        assert!(!dsu.connected(4, 5));
+        assert!(dsu.connected(0, 3));
+
+        dsu.rollback(s);
+        assert!(!dsu.connected(0, 3));
+        assert_eq!(dsu.size(0), 2);
+        assert_eq!(dsu.count_components(), 4);
+
+        dsu.rollback(0);
+        assert_eq!(dsu.count_components(), 6);
+        assert!(!dsu.undo());
+    }
+
+    #[test]
+    fn test_rollback_randomized() {
+        let mut rng = get_test_rng();
+
+        const T: usize = 50;
+        const N: usize = 30;
+        const Q: usize = 1000;
+
+        for _ in 0..T {
+            let mut dsu = RollbackDsu::new(N);
+
+            // 巻き戻し先の候補と，その時点までの統合操作列
+            let mut stack = vec![(dsu.snapshot(), vec![])];
+            let mut merges = vec![];
+
+            for _ in 0..Q {
+                match rng.random_range(0..4) {
+                    0 => {
+                        stack.push((dsu.snapshot(), merges.clone()));
+                    }
+                    1 => {
+                        if stack.len() > 1 && rng.random_ratio(1, 2) {
+                            let (s, m) = stack.pop().unwrap();
+                            dsu.rollback(s);
+                            merges = m;
+                        }
+                    }
+                    _ => {
+                        let u = rng.random_range(0..N);
+                        let v = rng.random_range(0..N);
+                        dsu.merge(u, v);
+                        merges.push((u, v));
+                    }
+                }
+
+                let mut naive = Dsu::new(N);
+                merges.iter().for_each(|&(u, v)| naive.merge(u, v));
+
+                assert_eq!(dsu.count_components(), naive.count_components());
+                for v in 0..N {
+                    assert_eq!(dsu.size(v), naive.size(v));
+                    assert_eq!(dsu.connected(0, v), naive.connected(0, v));
+                }
+            }
+        }
+    }
+}
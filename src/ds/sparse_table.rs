@@ -13,6 +13,8 @@
 //! let st = SparseTable::<OpMin<i64>>::new(vec![3, 5, 4, 100, 1]);
 //! assert_eq!(st.fold(1..4), 4); // 区間`[1, 4)`の最小値
 //! assert_eq!(st.fold(0..5), 1); // 区間`[0, 5)`の最小値
+//! assert_eq!(st.bisect_right(0, |&x| x >= 3), 4); // 区間`[0, r)`の最小値が3以上となる最大のr
+//! assert_eq!(st.bisect_left(4, |&x| x >= 4), 1); // 区間`[l, 4)`の最小値が4以上となる最小のl
 //! ```
 
 use std::{
@@ -74,6 +76,64 @@ impl<O: IdempotentMonoid> SparseTable<O> {
         self.op.op(&self.nodes[k][l], &self.nodes[k][r - (1 << k)])
     }
 
+    /// Sparse Table上の二分探索(max_right)．
+    ///
+    /// `g(r) = f(fold(l..r))`として，
+    /// 単調な`g`に対して，`g(r) = true`となる最大の`r`を返す．
+    ///
+    /// # 計算量
+    /// - O(log n)
+    ///
+    /// # 制約
+    /// - `0 <= l <= len`
+    /// - `f(identity()) = true`
+    pub fn bisect_right(&self, l: usize, mut f: impl FnMut(&O::Element) -> bool) -> usize {
+        assert!(l <= self.len);
+        debug_assert!(f(&self.op.id()));
+
+        let mut r = l;
+        let mut prod = self.op.id();
+        for k in (0..self.nodes.len()).rev() {
+            if r + (1 << k) <= self.len {
+                let tmp = self.op.op(&prod, &self.nodes[k][r]);
+                if f(&tmp) {
+                    prod = tmp;
+                    r += 1 << k;
+                }
+            }
+        }
+        r
+    }
+
+    /// Sparse Table上の二分探索(min_left)．
+    ///
+    /// `g(l) = f(fold(l..r))`として，
+    /// 単調な`g`に対して，`g(l) = true`となる最小の`l`を返す．
+    ///
+    /// # 計算量
+    /// - O(log n)
+    ///
+    /// # 制約
+    /// - `0 <= r <= len`
+    /// - `f(identity()) = true`
+    pub fn bisect_left(&self, r: usize, mut f: impl FnMut(&O::Element) -> bool) -> usize {
+        assert!(r <= self.len);
+        debug_assert!(f(&self.op.id()));
+
+        let mut l = r;
+        let mut prod = self.op.id();
+        for k in (0..self.nodes.len()).rev() {
+            if l >= 1 << k {
+                let tmp = self.op.op(&self.nodes[k][l - (1 << k)], &prod);
+                if f(&tmp) {
+                    prod = tmp;
+                    l -= 1 << k;
+                }
+            }
+        }
+        l
+    }
+
     pub fn inner(&self, i: usize, j: usize) -> &O::Element {
         &self.nodes[i][j]
     }
@@ -204,4 +264,30 @@ mod tests {
         test_random_gcd_usize,
         usize
     );
+
+    #[test]
+    fn test_bisect_randomized() {
+        let mut rng = get_test_rng();
+
+        for _ in 0..100 {
+            let n = rng.random_range(1..50);
+            let v = (0..n)
+                .map(|_| rng.random_range(0..100))
+                .collect::<Vec<i64>>();
+            let st = SparseTable::<OpMin<i64>>::new(v.clone());
+
+            for _ in 0..50 {
+                let x = rng.random_range(0..100);
+                let f = |&m: &i64| m >= x;
+
+                let l = rng.random_range(0..=n);
+                let expected = (l..=n).rev().find(|&r| f(&st.fold(l..r))).unwrap();
+                assert_eq!(st.bisect_right(l, f), expected);
+
+                let r = rng.random_range(0..=n);
+                let expected = (0..=r).find(|&l| f(&st.fold(l..r))).unwrap();
+                assert_eq!(st.bisect_left(r, f), expected);
+            }
+        }
+    }
 }
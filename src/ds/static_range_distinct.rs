@@ -0,0 +1,133 @@
+//! 静的な区間の種類数(Static Range Distinct)
+//!
+//! 配列の区間に含まれる値の種類数を求めるクエリを，オフラインでまとめて処理する．
+//! クエリを右端の昇順に並べ，各値について最も右にある出現位置だけを[`FenwickTree`]で数える．
+//!
+//! # 計算量
+//! - 配列の長さを N，クエリ数を Q として O((N + Q) log N)
+//!
+//! # 使用例
+//! ```
+//! use reprol::ds::static_range_distinct::StaticRangeDistinct;
+//!
+//! let srd = StaticRangeDistinct::new(&[1, 2, 1, 3, 2, 2]);
+//! assert_eq!(srd.solve([0..3, 1..6, 3..4, 2..2]), vec![2, 3, 1, 0]);
+//! ```
+//!
+//! # 問題例
+//! - [Static Range Count Distinct](https://judge.yosupo.jp/problem/static_range_count_distinct)
+//! - [ABC174 F](https://atcoder.jp/contests/abc174/tasks/abc174_f)
+
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    ops::{Range, RangeBounds},
+};
+
+use crate::{
+    ds::fenwick_tree::FenwickTree, ops::op_add::OpAdd, utils::normalize_range::normalize_index,
+};
+
+/// 静的な区間の種類数
+pub struct StaticRangeDistinct {
+    /// `prev[i]`: `i`より左にある，`i`番目と同じ値の最も右の出現位置
+    prev: Vec<Option<usize>>,
+}
+
+impl StaticRangeDistinct {
+    /// 配列`v`から構築する．
+    pub fn new<T: Eq + Hash>(v: &[T]) -> Self {
+        let mut last = HashMap::new();
+        let prev = v
+            .iter()
+            .enumerate()
+            .map(|(i, x)| last.insert(x, i))
+            .collect();
+        Self { prev }
+    }
+
+    /// 配列の長さを返す．
+    pub fn len(&self) -> usize {
+        self.prev.len()
+    }
+
+    /// 配列が空かを返す．
+    pub fn is_empty(&self) -> bool {
+        self.prev.is_empty()
+    }
+
+    /// 各クエリ区間に含まれる値の種類数を，クエリの順に並べて返す．
+    pub fn solve<R: RangeBounds<usize>>(&self, queries: impl IntoIterator<Item = R>) -> Vec<usize> {
+        let n = self.len();
+        let queries = queries
+            .into_iter()
+            .map(|range| {
+                let Range { start, end } = normalize_index(range, n);
+                assert!(start <= end && end <= n);
+                (start, end)
+            })
+            .collect::<Vec<_>>();
+
+        let mut order = (0..queries.len()).collect::<Vec<_>>();
+        order.sort_unstable_by_key(|&qi| queries[qi].1);
+
+        let mut ft = FenwickTree::<OpAdd<i32>>::new(n);
+        let mut answers = vec![0; queries.len()];
+        let mut r = 0;
+        for qi in order {
+            let (ql, qr) = queries[qi];
+            while r < qr {
+                ft.op(r, &1);
+                if let Some(p) = self.prev[r] {
+                    ft.op(p, &-1);
+                }
+                r += 1;
+            }
+            answers[qi] = ft.fold(ql..qr) as usize;
+        }
+        answers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use rand::Rng;
+
+    use super::*;
+    use crate::utils::test_utils::random::get_test_rng;
+
+    #[test]
+    fn test_empty() {
+        let srd = StaticRangeDistinct::new::<i32>(&[]);
+        assert!(srd.is_empty());
+        assert_eq!(srd.solve([0..0, 0..0]), vec![0, 0]);
+        assert!(srd.solve(Vec::<Range<usize>>::new()).is_empty());
+    }
+
+    #[test]
+    fn test_randomized() {
+        let mut rng = get_test_rng();
+
+        for _ in 0..100 {
+            let n = rng.random_range(0..50);
+            let v = (0..n).map(|_| rng.random_range(0..10)).collect::<Vec<_>>();
+            let srd = StaticRangeDistinct::new(&v);
+            assert_eq!(srd.len(), n);
+
+            let queries = (0..rng.random_range(0..50))
+                .map(|_| {
+                    let l = rng.random_range(0..=n);
+                    let r = rng.random_range(l..=n);
+                    l..r
+                })
+                .collect::<Vec<_>>();
+            let expected = queries
+                .iter()
+                .map(|range| v[range.clone()].iter().collect::<HashSet<_>>().len())
+                .collect::<Vec<_>>();
+            assert_eq!(srd.solve(queries), expected);
+        }
+    }
+}
@@ -0,0 +1,110 @@
+//! 静的な区間の出現回数(Static Range Frequency)
+//!
+//! 配列の区間に含まれる，ある値の個数を求める．
+//! 値ごとに出現位置のリストを持ち，二分探索で数える．
+//!
+//! # 計算量
+//! - 構築: O(N)
+//! - クエリ: O(log N)
+//!
+//! # 使用例
+//! ```
+//! use reprol::ds::static_range_frequency::StaticRangeFrequency;
+//!
+//! let srf = StaticRangeFrequency::new(&[3, 7, 1, 7, 7, 2]);
+//! assert_eq!(srf.frequency(1..5, &7), 3);
+//! assert_eq!(srf.frequency(..2, &7), 1);
+//! assert_eq!(srf.frequency(.., &5), 0);
+//! ```
+//!
+//! # 問題例
+//! - [Static Range Frequency](https://judge.yosupo.jp/problem/static_range_frequency)
+//! - [ABC248 D](https://atcoder.jp/contests/abc248/tasks/abc248_d)
+
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    ops::{Range, RangeBounds},
+};
+
+use crate::{bisect::SliceBoundsExt, utils::normalize_range::normalize_index};
+
+/// 静的な区間の出現回数
+pub struct StaticRangeFrequency<T> {
+    /// 配列の長さ
+    n: usize,
+
+    /// 値ごとの出現位置(昇順)
+    positions: HashMap<T, Vec<usize>>,
+}
+
+impl<T: Eq + Hash + Clone> StaticRangeFrequency<T> {
+    /// 配列`v`から構築する．
+    pub fn new(v: &[T]) -> Self {
+        let mut positions: HashMap<T, Vec<usize>> = HashMap::new();
+        for (i, x) in v.iter().enumerate() {
+            positions.entry(x.clone()).or_default().push(i);
+        }
+        Self {
+            n: v.len(),
+            positions,
+        }
+    }
+
+    /// 配列の長さを返す．
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    /// 配列が空かを返す．
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// 区間`range`に含まれる`value`の個数を返す．
+    pub fn frequency(&self, range: impl RangeBounds<usize>, value: &T) -> usize {
+        let Range { start, end } = normalize_index(range, self.n);
+        assert!(start <= end && end <= self.n);
+        self.positions
+            .get(value)
+            .map_or(0, |pos| pos.lower_bound(&end) - pos.lower_bound(&start))
+    }
+
+    /// `value`の出現位置を昇順に返す．
+    pub fn positions(&self, value: &T) -> &[usize] {
+        self.positions.get(value).map_or(&[], |pos| pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::*;
+    use crate::utils::test_utils::random::get_test_rng;
+
+    #[test]
+    fn test_randomized() {
+        let mut rng = get_test_rng();
+
+        for _ in 0..100 {
+            let n = rng.random_range(0..50);
+            let v = (0..n).map(|_| rng.random_range(0..10)).collect::<Vec<_>>();
+            let srf = StaticRangeFrequency::new(&v);
+            assert_eq!(srf.len(), n);
+
+            for _ in 0..100 {
+                let l = rng.random_range(0..=n);
+                let r = rng.random_range(l..=n);
+                let x = rng.random_range(0..12);
+                let expected = v[l..r].iter().filter(|&&y| y == x).count();
+                assert_eq!(srf.frequency(l..r, &x), expected);
+            }
+
+            for x in 0..12 {
+                let expected = (0..n).filter(|&i| v[i] == x).collect::<Vec<_>>();
+                assert_eq!(srf.positions(&x), expected);
+            }
+        }
+    }
+}
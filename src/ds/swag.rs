@@ -0,0 +1,468 @@
+//! Sliding Window Aggregation(SWAG)
+//!
+//! 要素としてモノイドを持つキュー・両端キューで，全要素の総積を管理するデータ構造．
+//! 逆元や可換性を必要とせず，以下の操作をいずれも償却 O(1) で処理できる．
+//! - 末尾への要素の追加，先頭の要素の削除([`FoldableQueue`])．
+//! - 先頭・末尾への要素の追加と削除([`FoldableDeque`])．
+//! - 全要素の総積の取得．
+//!
+//! # 使用例
+//! ```
+//! use reprol::{
+//!     ds::swag::{FoldableDeque, FoldableQueue},
+//!     ops::{
+//!         act_affine::{ActAffine, ActAffineElement},
+//!         op_min::OpMin,
+//!     },
+//! };
+//!
+//! // 幅3の区間最小値
+//! let v = [5, 3, 4, 1, 6, 2];
+//! let mut que = FoldableQueue::<OpMin<i32>>::new();
+//! let mut res = vec![];
+//! for &x in &v {
+//!     que.push_back(x);
+//!     if que.len() > 3 {
+//!         que.pop_front();
+//!     }
+//!     res.push(que.fold_all());
+//! }
+//! assert_eq!(res, vec![5, 3, 3, 1, 1, 1]);
+//!
+//! // アフィン変換の合成
+//! let mut deq = FoldableDeque::<ActAffine<i64>>::new();
+//! deq.push_back(ActAffineElement { a: 2, b: 1 });
+//! deq.push_front(ActAffineElement { a: 3, b: 0 });
+//! assert_eq!(deq.fold_all(), ActAffineElement { a: 6, b: 3 }); // 3(2x + 1)
+//! assert_eq!(deq.pop_back(), Some(ActAffineElement { a: 2, b: 1 }));
+//! assert_eq!(deq.fold_all(), ActAffineElement { a: 3, b: 0 });
+//! ```
+//!
+//! # 問題例
+//! - [Queue Operate All Composite](https://judge.yosupo.jp/problem/queue_operate_all_composite)
+//! - [Deque Operate All Composite](https://judge.yosupo.jp/problem/deque_operate_all_composite)
+
+use std::iter::FromIterator;
+
+use crate::ops::monoid::Monoid;
+
+/// 総積を取得できるキュー
+pub struct FoldableQueue<O: Monoid> {
+    /// 先頭側のスタック．(要素, その要素から先頭側スタックの末尾までの総積)を，先頭の要素が最後になるように持つ．
+    front: Vec<(O::Element, O::Element)>,
+
+    /// 末尾側のスタック．
+    back: Vec<O::Element>,
+
+    /// 末尾側のスタックの要素の総積
+    back_fold: O::Element,
+
+    /// 演算(モノイド)
+    op: O,
+}
+
+impl<O: Monoid> FoldableQueue<O> {
+    /// 空のキューを生成する．
+    pub fn new() -> Self
+    where
+        O: Default,
+    {
+        Self::with_op(O::default())
+    }
+
+    /// モノイド`op`を指定して空のキューを生成する．
+    pub fn with_op(op: O) -> Self {
+        Self {
+            front: Vec::new(),
+            back: Vec::new(),
+            back_fold: op.id(),
+            op,
+        }
+    }
+
+    /// 要素数を返す．
+    pub fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+
+    /// キューが空かを返す．
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// すべての要素を削除する．
+    pub fn clear(&mut self) {
+        self.front.clear();
+        self.back.clear();
+        self.back_fold = self.op.id();
+    }
+
+    /// 先頭の要素を返す．
+    pub fn front(&self) -> Option<&O::Element> {
+        self.front
+            .last()
+            .map(|(value, _)| value)
+            .or_else(|| self.back.first())
+    }
+
+    /// 末尾に要素を追加する．
+    pub fn push_back(&mut self, value: O::Element) {
+        self.back_fold = self.op.op(&self.back_fold, &value);
+        self.back.push(value);
+    }
+
+    /// 先頭の要素を削除して返す．
+    ///
+    /// # 計算量
+    /// - 償却 O(1)
+    pub fn pop_front(&mut self) -> Option<O::Element> {
+        if self.front.is_empty() {
+            self.back_fold = self.op.id();
+            while let Some(value) = self.back.pop() {
+                let fold = match self.front.last() {
+                    Some((_, fold)) => self.op.op(&value, fold),
+                    None => self.op.op(&value, &self.op.id()),
+                };
+                self.front.push((value, fold));
+            }
+        }
+        self.front.pop().map(|(value, _)| value)
+    }
+
+    /// 全要素の総積を返す．
+    pub fn fold_all(&self) -> O::Element {
+        match self.front.last() {
+            Some((_, fold)) => self.op.op(fold, &self.back_fold),
+            None => self.op.op(&self.op.id(), &self.back_fold),
+        }
+    }
+}
+
+impl<O: Monoid + Default> Default for FoldableQueue<O> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<O: Monoid> Extend<O::Element> for FoldableQueue<O> {
+    fn extend<I: IntoIterator<Item = O::Element>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|value| self.push_back(value));
+    }
+}
+
+impl<O: Monoid + Default> FromIterator<O::Element> for FoldableQueue<O> {
+    fn from_iter<I: IntoIterator<Item = O::Element>>(iter: I) -> Self {
+        let mut res = Self::new();
+        res.extend(iter);
+        res
+    }
+}
+
+/// 総積を取得できる両端キュー
+pub struct FoldableDeque<O: Monoid> {
+    /// 先頭側のスタック．(要素, その要素から先頭側スタックの末尾までの総積)を，先頭の要素が最後になるように持つ．
+    front: Vec<(O::Element, O::Element)>,
+
+    /// 末尾側のスタック．(要素, 末尾側スタックの先頭からその要素までの総積)を，末尾の要素が最後になるように持つ．
+    back: Vec<(O::Element, O::Element)>,
+
+    /// 演算(モノイド)
+    op: O,
+}
+
+impl<O: Monoid> FoldableDeque<O> {
+    /// 空の両端キューを生成する．
+    pub fn new() -> Self
+    where
+        O: Default,
+    {
+        Self::with_op(O::default())
+    }
+
+    /// モノイド`op`を指定して空の両端キューを生成する．
+    pub fn with_op(op: O) -> Self {
+        Self {
+            front: Vec::new(),
+            back: Vec::new(),
+            op,
+        }
+    }
+
+    /// 要素数を返す．
+    pub fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+
+    /// 両端キューが空かを返す．
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// すべての要素を削除する．
+    pub fn clear(&mut self) {
+        self.front.clear();
+        self.back.clear();
+    }
+
+    /// 先頭の要素を返す．
+    pub fn front(&self) -> Option<&O::Element> {
+        self.front
+            .last()
+            .or_else(|| self.back.first())
+            .map(|(value, _)| value)
+    }
+
+    /// 末尾の要素を返す．
+    pub fn back(&self) -> Option<&O::Element> {
+        self.back
+            .last()
+            .or_else(|| self.front.first())
+            .map(|(value, _)| value)
+    }
+
+    /// 先頭に要素を追加する．
+    pub fn push_front(&mut self, value: O::Element) {
+        let fold = match self.front.last() {
+            Some((_, fold)) => self.op.op(&value, fold),
+            None => self.op.op(&value, &self.op.id()),
+        };
+        self.front.push((value, fold));
+    }
+
+    /// 末尾に要素を追加する．
+    pub fn push_back(&mut self, value: O::Element) {
+        let fold = match self.back.last() {
+            Some((_, fold)) => self.op.op(fold, &value),
+            None => self.op.op(&self.op.id(), &value),
+        };
+        self.back.push((value, fold));
+    }
+
+    /// 先頭の要素を削除して返す．
+    ///
+    /// # 計算量
+    /// - 償却 O(1)
+    pub fn pop_front(&mut self) -> Option<O::Element> {
+        if self.front.is_empty() {
+            // 末尾側の要素を先頭から順に並べ，半分を先頭側に移す
+            let mut values = self.take_all();
+            let rest = values.split_off(values.len().div_ceil(2));
+            self.rebuild(values, rest);
+        }
+        self.front.pop().map(|(value, _)| value)
+    }
+
+    /// 末尾の要素を削除して返す．
+    ///
+    /// # 計算量
+    /// - 償却 O(1)
+    pub fn pop_back(&mut self) -> Option<O::Element> {
+        if self.back.is_empty() {
+            // 先頭側の要素を先頭から順に並べ，半分を末尾側に移す
+            let mut values = self.take_all();
+            let rest = values.split_off(values.len() / 2);
+            self.rebuild(values, rest);
+        }
+        self.back.pop().map(|(value, _)| value)
+    }
+
+    /// 全要素の総積を返す．
+    pub fn fold_all(&self) -> O::Element {
+        let id = self.op.id();
+        let front = self.front.last().map_or(&id, |(_, fold)| fold);
+        let back = self.back.last().map_or(&id, |(_, fold)| fold);
+        self.op.op(front, back)
+    }
+
+    /// すべての要素を先頭から順に取り出す．
+    fn take_all(&mut self) -> Vec<O::Element> {
+        let front = std::mem::take(&mut self.front);
+        let back = std::mem::take(&mut self.back);
+        front
+            .into_iter()
+            .rev()
+            .chain(back)
+            .map(|(value, _)| value)
+            .collect()
+    }
+
+    /// 先頭側のスタックを`front`，末尾側のスタックを`back`の要素で構築し直す．
+    fn rebuild(&mut self, front: Vec<O::Element>, back: Vec<O::Element>) {
+        front
+            .into_iter()
+            .rev()
+            .for_each(|value| self.push_front(value));
+        back.into_iter().for_each(|value| self.push_back(value));
+    }
+}
+
+impl<O: Monoid + Default> Default for FoldableDeque<O> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<O: Monoid> Extend<O::Element> for FoldableDeque<O> {
+    fn extend<I: IntoIterator<Item = O::Element>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|value| self.push_back(value));
+    }
+}
+
+impl<O: Monoid + Default> FromIterator<O::Element> for FoldableDeque<O> {
+    fn from_iter<I: IntoIterator<Item = O::Element>>(iter: I) -> Self {
+        let mut res = Self::new();
+        res.extend(iter);
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use rand::Rng;
+
+    use super::*;
+    use crate::{
+        ops::{
+            act_affine::{ActAffine, ActAffineElement},
+            op_add::OpAdd,
+            op_gcd::OpGcd,
+            op_max::OpMax,
+            op_min::OpMin,
+            op_xor::OpXor,
+        },
+        utils::test_utils::random::get_test_rng,
+    };
+
+    const MOD: u64 = 998244353;
+
+    #[derive(Default)]
+    struct OpAffineMod;
+
+    impl Monoid for OpAffineMod {
+        type Element = (u64, u64);
+
+        fn op(&self, f: &Self::Element, g: &Self::Element) -> Self::Element {
+            // fを適用した後にgを適用する
+            (f.0 * g.0 % MOD, (f.1 * g.0 + g.1) % MOD)
+        }
+
+        fn id(&self) -> Self::Element {
+            (1, 0)
+        }
+    }
+
+    #[test]
+    fn test_ops() {
+        let mut que = FoldableQueue::<OpAdd<i64>>::from_iter([1, 2, 3]);
+        assert_eq!(que.fold_all(), 6);
+        assert_eq!(que.pop_front(), Some(1));
+        assert_eq!(que.front(), Some(&2));
+
+        let que = FoldableQueue::<OpMax<i64>>::from_iter([1, 5, 3]);
+        assert_eq!(que.fold_all(), 5);
+
+        let que = FoldableQueue::<OpXor<u32>>::from_iter([1, 2, 3]);
+        assert_eq!(que.fold_all(), 0);
+
+        let que = FoldableQueue::<OpGcd<u64>>::from_iter([12, 18, 30]);
+        assert_eq!(que.fold_all(), 6);
+
+        let mut deq = FoldableDeque::<OpMin<i64>>::from_iter([4, 2, 7]);
+        assert_eq!(deq.fold_all(), 2);
+        assert_eq!(deq.pop_front(), Some(4));
+        assert_eq!(deq.pop_front(), Some(2));
+        assert_eq!(deq.fold_all(), 7);
+        assert_eq!(deq.back(), Some(&7));
+
+        let deq = FoldableDeque::<ActAffine<i64>>::from_iter([
+            ActAffineElement { a: 2, b: 0 },
+            ActAffineElement { a: 1, b: 3 },
+        ]);
+        assert_eq!(deq.fold_all(), ActAffineElement { a: 2, b: 6 });
+    }
+
+    #[test]
+    fn test_empty() {
+        let mut que = FoldableQueue::<OpAdd<i64>>::new();
+        assert_eq!(que.fold_all(), 0);
+        assert_eq!(que.pop_front(), None);
+        assert!(que.is_empty());
+
+        let mut deq = FoldableDeque::<OpAdd<i64>>::new();
+        assert_eq!(deq.fold_all(), 0);
+        assert_eq!(deq.pop_front(), None);
+        assert_eq!(deq.pop_back(), None);
+        assert!(deq.is_empty());
+    }
+
+    #[test]
+    fn test_queue_randomized() {
+        let mut rng = get_test_rng();
+        let op = OpAffineMod;
+
+        for _ in 0..100 {
+            let mut que = FoldableQueue::<OpAffineMod>::new();
+            let mut naive = VecDeque::new();
+
+            for _ in 0..1000 {
+                if rng.random_ratio(3, 5) {
+                    let f = (rng.random_range(0..MOD), rng.random_range(0..MOD));
+                    que.push_back(f);
+                    naive.push_back(f);
+                } else {
+                    assert_eq!(que.pop_front(), naive.pop_front());
+                }
+                assert_eq!(que.len(), naive.len());
+                assert_eq!(que.front(), naive.front());
+                assert_eq!(
+                    que.fold_all(),
+                    naive.iter().fold(op.id(), |acc, f| op.op(&acc, f))
+                );
+            }
+
+            que.clear();
+            assert!(que.is_empty());
+            assert_eq!(que.fold_all(), op.id());
+        }
+    }
+
+    #[test]
+    fn test_deque_randomized() {
+        let mut rng = get_test_rng();
+        let op = OpAffineMod;
+
+        for _ in 0..100 {
+            let mut deq = FoldableDeque::<OpAffineMod>::new();
+            let mut naive = VecDeque::new();
+
+            for _ in 0..1000 {
+                let f = (rng.random_range(0..MOD), rng.random_range(0..MOD));
+                match rng.random_range(0..5) {
+                    0 | 1 if rng.random_bool(0.5) => {
+                        deq.push_front(f);
+                        naive.push_front(f);
+                    }
+                    0 | 1 => {
+                        deq.push_back(f);
+                        naive.push_back(f);
+                    }
+                    2 | 3 => {
+                        assert_eq!(deq.pop_front(), naive.pop_front());
+                    }
+                    _ => {
+                        assert_eq!(deq.pop_back(), naive.pop_back());
+                    }
+                }
+                assert_eq!(deq.len(), naive.len());
+                assert_eq!(deq.front(), naive.front());
+                assert_eq!(deq.back(), naive.back());
+                assert_eq!(
+                    deq.fold_all(),
+                    naive.iter().fold(op.id(), |acc, f| op.op(&acc, f))
+                );
+            }
+        }
+    }
+}
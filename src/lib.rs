@@ -2,6 +2,7 @@ pub mod bellman_ford;
 pub mod bfs;
 pub mod bisect;
 pub mod change_min_max;
+pub mod compress;
 pub mod dijkstra;
 pub mod ds;
 pub mod format;
@@ -10,6 +11,7 @@ pub mod inversion;
 pub mod lowlink;
 pub mod math;
 pub mod nonnan_float;
+pub mod offline;
 pub mod ops;
 mod path_tracker;
 pub mod permutation;
@@ -0,0 +1,110 @@
+//! CDQ分割統治(CDQ divide and conquer)
+//!
+//! 時刻順に並んだ`n`個の要素について，「前の要素から後ろの要素への寄与」をまとめて計算するための分割統治．
+//! 区間`[l, r)`を`[l, m)`と`[m, r)`に分け，左半分の要素から右半分の要素への寄与を`f(l..m, m..r)`で計算する．
+//! すべての`i < j`の組はちょうど1回の`f`の呼び出しで左右に分かれるため，
+//! 各呼び出しで寄与を O(len log len) で計算できれば，全体で O(n log^2 n) となる．
+//!
+//! `f`は「左半分の再帰 → `f(l..m, m..r)` → 右半分の再帰」の順に呼び出される．
+//! したがって`f(l..m, m..r)`の呼び出し時点で，`[l, m)`の各要素へのそれより前の要素からの寄与はすべて計算済みである．
+//! これにより，前の要素の答えに依存して後ろの要素の答えが決まるDPにも使える．
+//!
+//! # 使用例
+//! 各`j`について，`i < j`かつ`a[i] < a[j]`を満たす`i`の個数を求める．
+//! ```
+//! use reprol::offline::cdq::cdq;
+//!
+//! let a = [3, 1, 4, 1, 5, 9, 2, 6];
+//! let mut count = vec![0; a.len()];
+//! cdq(a.len(), |left, right| {
+//!     let mut xs = a[left].to_vec();
+//!     xs.sort_unstable();
+//!     for j in right {
+//!         count[j] += xs.partition_point(|&x| x < a[j]);
+//!     }
+//! });
+//! assert_eq!(count, vec![0, 0, 2, 0, 4, 5, 2, 6]);
+//! ```
+//!
+//! # 問題例
+//! - [Point Add Rectangle Sum](https://judge.yosupo.jp/problem/point_add_rectangle_sum)
+
+use std::ops::Range;
+
+/// 長さ`n`の列に対してCDQ分割統治を行い，各分割で`f(左半分, 右半分)`を呼び出す．
+pub fn cdq(n: usize, mut f: impl FnMut(Range<usize>, Range<usize>)) {
+    fn dfs(l: usize, r: usize, f: &mut impl FnMut(Range<usize>, Range<usize>)) {
+        if r - l <= 1 {
+            return;
+        }
+        let m = l + (r - l) / 2;
+        dfs(l, m, f);
+        f(l..m, m..r);
+        dfs(m, r, f);
+    }
+
+    dfs(0, n, &mut f);
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::*;
+    use crate::utils::test_utils::random::get_test_rng;
+
+    #[test]
+    fn test_each_pair_is_covered_once() {
+        for n in 0..40 {
+            let mut covered = vec![vec![0; n]; n];
+            cdq(n, |left, right| {
+                assert!(!left.is_empty() && !right.is_empty());
+                assert_eq!(left.end, right.start);
+                for i in left {
+                    for j in right.clone() {
+                        covered[i][j] += 1;
+                    }
+                }
+            });
+            for (i, row) in covered.iter().enumerate() {
+                for (j, &c) in row.iter().enumerate() {
+                    assert_eq!(c, (i < j) as usize);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_dp() {
+        // dp[j] = 1 + max{dp[i] | i < j, a[i] < a[j]} (最長増加部分列)
+        let mut rng = get_test_rng();
+
+        for _ in 0..100 {
+            let n = rng.random_range(0..50);
+            let a = (0..n)
+                .map(|_| rng.random_range(0..20))
+                .collect::<Vec<i32>>();
+
+            let mut dp = vec![1; n];
+            cdq(n, |left, right| {
+                for j in right {
+                    for i in left.clone() {
+                        if a[i] < a[j] {
+                            dp[j] = dp[j].max(dp[i] + 1);
+                        }
+                    }
+                }
+            });
+
+            let mut expected = vec![1; n];
+            for j in 0..n {
+                for i in 0..j {
+                    if a[i] < a[j] {
+                        expected[j] = expected[j].max(expected[i] + 1);
+                    }
+                }
+            }
+            assert_eq!(dp, expected);
+        }
+    }
+}
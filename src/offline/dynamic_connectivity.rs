@@ -0,0 +1,259 @@
+//! オフライン動的連結性判定(Offline Dynamic Connectivity)
+//!
+//! 辺の追加・削除とクエリの列を先読みし，各クエリ時点でのグラフの連結性を求める．
+//!
+//! 各辺が存在するクエリ区間を時間軸上のセグメント木のノードに割り当て，
+//! セグメント木を深さ優先で辿りながら[`RollbackDsu`]で辺の統合と巻き戻しを行う．
+//!
+//! # 計算量
+//! - 辺の追加・削除の回数を M，クエリ数を Q として O(M log Q log N + Q)
+//!
+//! # 使用例
+//! ```
+//! use reprol::offline::dynamic_connectivity::DynamicConnectivity;
+//!
+//! let mut dc = DynamicConnectivity::new(4);
+//! dc.add_edge(0, 1);
+//! dc.add_edge(1, 2);
+//! dc.query((0, 2));
+//! dc.remove_edge(0, 1);
+//! dc.query((0, 2));
+//! dc.add_edge(2, 0);
+//! dc.query((0, 1));
+//!
+//! let answers = dc.solve(|&(u, v), dsu| (dsu.connected(u, v), dsu.count_components()));
+//! assert_eq!(answers, vec![(true, 2), (false, 3), (true, 2)]);
+//! ```
+
+use std::collections::HashMap;
+
+use crate::ds::rollback_dsu::RollbackDsu;
+
+/// オフライン動的連結性判定
+///
+/// `Q`はクエリの内容を表す型．
+pub struct DynamicConnectivity<Q> {
+    /// 頂点数
+    n: usize,
+
+    /// 登録されたクエリ
+    queries: Vec<Q>,
+
+    /// 辺`(u, v)`(`u <= v`)が存在し始めたクエリ番号のリスト
+    alive: HashMap<(usize, usize), Vec<usize>>,
+
+    /// 辺`(u, v)`が存在するクエリ区間`[l, r)`のリスト
+    edges: Vec<(usize, usize, usize, usize)>,
+}
+
+impl<Q> DynamicConnectivity<Q> {
+    /// 頂点数`n`の辺のないグラフで初期化する．
+    pub fn new(n: usize) -> Self {
+        assert!(n > 0);
+        Self {
+            n,
+            queries: vec![],
+            alive: HashMap::new(),
+            edges: vec![],
+        }
+    }
+
+    /// 辺`(u, v)`を追加する．
+    /// 多重辺も扱える．
+    pub fn add_edge(&mut self, u: usize, v: usize) {
+        assert!(u < self.n && v < self.n);
+        let key = (u.min(v), u.max(v));
+        self.alive.entry(key).or_default().push(self.queries.len());
+    }
+
+    /// 辺`(u, v)`を1本削除する．
+    ///
+    /// # Panics
+    ///
+    /// 辺`(u, v)`が存在しない場合はパニックする．
+    pub fn remove_edge(&mut self, u: usize, v: usize) {
+        let key = (u.min(v), u.max(v));
+        let l = self
+            .alive
+            .get_mut(&key)
+            .and_then(|ls| ls.pop())
+            .expect("edge not found in DynamicConnectivity");
+        let r = self.queries.len();
+        if l < r {
+            self.edges.push((key.0, key.1, l, r));
+        }
+    }
+
+    /// 現時点のグラフに対するクエリ`query`を登録する．
+    /// クエリ番号(登録順に0から振られる)を返す．
+    pub fn query(&mut self, query: Q) -> usize {
+        self.queries.push(query);
+        self.queries.len() - 1
+    }
+
+    /// 各クエリ時点でのグラフの連結性を表すDSUを`f`に渡し，
+    /// その戻り値をクエリ番号順に並べて返す．
+    pub fn solve<R>(mut self, mut f: impl FnMut(&Q, &RollbackDsu) -> R) -> Vec<R> {
+        let q = self.queries.len();
+        if q == 0 {
+            return vec![];
+        }
+
+        for ((u, v), ls) in self.alive.drain() {
+            for l in ls {
+                if l < q {
+                    self.edges.push((u, v, l, q));
+                }
+            }
+        }
+
+        let offset = q.next_power_of_two();
+        let mut nodes = vec![vec![]; 2 * offset];
+        for &(u, v, l, r) in &self.edges {
+            let mut l = l + offset;
+            let mut r = r + offset;
+            while l < r {
+                if l % 2 == 1 {
+                    nodes[l].push((u, v));
+                    l += 1;
+                }
+                if r % 2 == 1 {
+                    r -= 1;
+                    nodes[r].push((u, v));
+                }
+                l /= 2;
+                r /= 2;
+            }
+        }
+
+        let mut dsu = RollbackDsu::new(self.n);
+        let mut answers = Vec::with_capacity(q);
+        dfs(
+            1,
+            offset,
+            &nodes,
+            &self.queries,
+            &mut dsu,
+            &mut f,
+            &mut answers,
+        );
+        answers
+    }
+}
+
+/// 時間軸上のセグメント木のノード`index`以下を深さ優先で辿る．
+fn dfs<Q, R>(
+    index: usize,
+    offset: usize,
+    nodes: &[Vec<(usize, usize)>],
+    queries: &[Q],
+    dsu: &mut RollbackDsu,
+    f: &mut impl FnMut(&Q, &RollbackDsu) -> R,
+    answers: &mut Vec<R>,
+) {
+    if index >= offset && index - offset >= queries.len() {
+        return;
+    }
+
+    let snapshot = dsu.snapshot();
+    for &(u, v) in &nodes[index] {
+        dsu.merge(u, v);
+    }
+
+    if index >= offset {
+        answers.push(f(&queries[index - offset], dsu));
+    } else {
+        dfs(2 * index, offset, nodes, queries, dsu, f, answers);
+        dfs(2 * index + 1, offset, nodes, queries, dsu, f, answers);
+    }
+
+    dsu.rollback(snapshot);
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::*;
+    use crate::{ds::dsu::Dsu, utils::test_utils::random::get_test_rng};
+
+    #[test]
+    fn test() {
+        let mut dc = DynamicConnectivity::new(5);
+        dc.query((0, 1));
+        dc.add_edge(0, 1);
+        dc.add_edge(0, 1);
+        dc.query((0, 1));
+        dc.remove_edge(1, 0);
+        dc.query((0, 1));
+        dc.remove_edge(0, 1);
+        dc.query((0, 1));
+        dc.add_edge(3, 3);
+        dc.add_edge(3, 4);
+        dc.query((3, 4));
+
+        let answers = dc.solve(|&(u, v), dsu| (dsu.connected(u, v), dsu.count_components()));
+        assert_eq!(
+            answers,
+            vec![(false, 5), (true, 4), (true, 4), (false, 5), (true, 4)]
+        );
+    }
+
+    #[test]
+    fn test_no_query() {
+        let mut dc = DynamicConnectivity::<()>::new(3);
+        dc.add_edge(0, 1);
+        assert!(dc.solve(|_, dsu| dsu.count_components()).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_remove_missing_edge() {
+        let mut dc = DynamicConnectivity::<()>::new(3);
+        dc.add_edge(0, 1);
+        dc.remove_edge(1, 2);
+    }
+
+    #[test]
+    fn test_randomized() {
+        let mut rng = get_test_rng();
+
+        const T: usize = 50;
+        const N_MAX: usize = 15;
+        const Q: usize = 300;
+
+        for _ in 0..T {
+            let n = rng.random_range(1..=N_MAX);
+            let mut dc = DynamicConnectivity::new(n);
+            let mut edges = vec![];
+            let mut expected = vec![];
+
+            for _ in 0..Q {
+                match rng.random_range(0..3) {
+                    0 => {
+                        let u = rng.random_range(0..n);
+                        let v = rng.random_range(0..n);
+                        dc.add_edge(u, v);
+                        edges.push((u, v));
+                    }
+                    1 if !edges.is_empty() => {
+                        let (u, v) = edges.swap_remove(rng.random_range(0..edges.len()));
+                        dc.remove_edge(v, u);
+                    }
+                    _ => {
+                        let u = rng.random_range(0..n);
+                        let v = rng.random_range(0..n);
+                        dc.query((u, v));
+
+                        let mut naive = Dsu::new(n);
+                        edges.iter().for_each(|&(u, v)| naive.merge(u, v));
+                        expected.push((naive.connected(u, v), naive.count_components()));
+                    }
+                }
+            }
+
+            let answers = dc.solve(|&(u, v), dsu| (dsu.connected(u, v), dsu.count_components()));
+            assert_eq!(answers, expected);
+        }
+    }
+}
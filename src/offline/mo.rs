@@ -0,0 +1,637 @@
+//! Mo's algorithm
+//!
+//! 区間クエリを先読みし，区間の端点を1つずつ伸縮させながら順に答える．
+//! 区間の端点に要素を追加・削除する操作さえできれば，[`Monoid`](crate::ops::monoid::Monoid)で
+//! 表せない量(種類数，最頻値など)も求められる．
+//!
+//! - [`Mo`]は区間クエリを扱う．クエリはHilbert曲線の順に並べ替えて処理する．
+//! - [`MoWithUpdates`]は1点更新を含む区間クエリを扱う．時間軸を加えた3次元で並べ替える．
+//! - [`TreeMo`]は木のパス上の頂点集合に関するクエリを扱う．オイラーツアー上の区間クエリに帰着する．
+//!
+//! 各操作は状態`state`の可変参照を受け取るコールバックとして与える．
+//! 端点の左右で処理が同じ場合は，同じクロージャを両方に渡せばよい．
+//!
+//! # 計算量
+//! - `Mo`: 列の長さを N，クエリ数を Q として O(N √Q) 回の追加・削除
+//! - `MoWithUpdates`: 更新の回数を U として O(N^(2/3) (Q + U)) 回程度の追加・削除・更新
+//! - `TreeMo`: 頂点数を N として O(N √Q) 回の追加・削除
+//!
+//! # 使用例
+//! ```
+//! use reprol::offline::mo::Mo;
+//!
+//! // 区間の種類数
+//! let a = vec![1, 2, 1, 3, 2, 2];
+//! let mut mo = Mo::new(a.len());
+//! mo.query(0..3);
+//! mo.query(1..6);
+//! mo.query(3..4);
+//!
+//! // (各値の出現回数, 種類数)
+//! let mut state = (vec![0; 4], 0);
+//! let add = |(cnt, kinds): &mut (Vec<usize>, usize), i: usize| {
+//!     cnt[a[i]] += 1;
+//!     if cnt[a[i]] == 1 {
+//!         *kinds += 1;
+//!     }
+//! };
+//! let remove = |(cnt, kinds): &mut (Vec<usize>, usize), i: usize| {
+//!     cnt[a[i]] -= 1;
+//!     if cnt[a[i]] == 0 {
+//!         *kinds -= 1;
+//!     }
+//! };
+//! // 左右の端点で同じ処理をする
+//! let answers = mo.solve(&mut state, add, add, remove, remove, |(_, kinds), _| *kinds);
+//! assert_eq!(answers, vec![2, 3, 1]);
+//! ```
+//!
+//! # 問題例
+//! - [Static Range Count Distinct](https://judge.yosupo.jp/problem/static_range_count_distinct)
+//! - [ABC174 F](https://atcoder.jp/contests/abc174/tasks/abc174_f)
+
+use std::ops::{Range, RangeBounds};
+
+use crate::utils::normalize_range::normalize_index;
+
+/// Mo's algorithm
+pub struct Mo {
+    /// 列の長さ
+    n: usize,
+
+    /// 登録されたクエリ区間
+    queries: Vec<(usize, usize)>,
+}
+
+impl Mo {
+    /// 長さ`n`の列に対するクエリを受け付ける．
+    pub fn new(n: usize) -> Self {
+        Self { n, queries: vec![] }
+    }
+
+    /// クエリ区間`range`を登録する．
+    /// クエリ番号(登録順に0から振られる)を返す．
+    pub fn query(&mut self, range: impl RangeBounds<usize>) -> usize {
+        let Range { start, end } = normalize_index(range, self.n);
+        assert!(start <= end && end <= self.n);
+        self.queries.push((start, end));
+        self.queries.len() - 1
+    }
+
+    /// 各クエリについて，状態を区間に合わせたうえで`answer(state, クエリ番号)`を呼び，
+    /// その戻り値をクエリ番号順に並べて返す．
+    ///
+    /// - `add_left(state, i)`, `add_right(state, i)`: 区間の左端・右端に`i`番目の要素を追加する．
+    /// - `remove_left(state, i)`, `remove_right(state, i)`: 区間の左端・右端から`i`番目の要素を削除する．
+    ///
+    /// 空区間から始まり，追加を削除より先に行うため，区間が負の長さになることはない．
+    pub fn solve<S, R>(
+        &self,
+        state: &mut S,
+        mut add_left: impl FnMut(&mut S, usize),
+        mut add_right: impl FnMut(&mut S, usize),
+        mut remove_left: impl FnMut(&mut S, usize),
+        mut remove_right: impl FnMut(&mut S, usize),
+        mut answer: impl FnMut(&mut S, usize) -> R,
+    ) -> Vec<R> {
+        let mut answers = (0..self.queries.len()).map(|_| None).collect::<Vec<_>>();
+        let (mut l, mut r) = (0, 0);
+
+        for qi in hilbert_order(self.n, &self.queries) {
+            let (ql, qr) = self.queries[qi];
+            while l > ql {
+                l -= 1;
+                add_left(state, l);
+            }
+            while r < qr {
+                add_right(state, r);
+                r += 1;
+            }
+            while l < ql {
+                remove_left(state, l);
+                l += 1;
+            }
+            while r > qr {
+                r -= 1;
+                remove_right(state, r);
+            }
+            answers[qi] = Some(answer(state, qi));
+        }
+
+        answers.into_iter().map(Option::unwrap).collect()
+    }
+}
+
+/// 1点更新を含む区間クエリに対するMo's algorithm
+pub struct MoWithUpdates {
+    /// 列の長さ
+    n: usize,
+
+    /// 登録された更新の個数
+    updates: usize,
+
+    /// 登録されたクエリ区間と，それまでに登録された更新の個数
+    queries: Vec<(usize, usize, usize)>,
+}
+
+impl MoWithUpdates {
+    /// 長さ`n`の列に対するクエリを受け付ける．
+    pub fn new(n: usize) -> Self {
+        Self {
+            n,
+            updates: 0,
+            queries: vec![],
+        }
+    }
+
+    /// 更新を登録する．
+    /// 更新番号(登録順に0から振られる)を返す．更新の内容は呼び出し側で管理する．
+    pub fn update(&mut self) -> usize {
+        self.updates += 1;
+        self.updates - 1
+    }
+
+    /// 現時点の列に対するクエリ区間`range`を登録する．
+    /// クエリ番号(登録順に0から振られる)を返す．
+    pub fn query(&mut self, range: impl RangeBounds<usize>) -> usize {
+        let Range { start, end } = normalize_index(range, self.n);
+        assert!(start <= end && end <= self.n);
+        self.queries.push((start, end, self.updates));
+        self.queries.len() - 1
+    }
+
+    /// 各クエリについて，状態を区間と時刻に合わせたうえで`answer(state, クエリ番号)`を呼び，
+    /// その戻り値をクエリ番号順に並べて返す．
+    ///
+    /// - `add(state, i)`, `remove(state, i)`: 区間に`i`番目の要素を追加・削除する．
+    /// - `apply(state, j, l..r)`: 区間が`l..r`である状態で`j`番目の更新を適用する．
+    /// - `undo(state, j, l..r)`: 区間が`l..r`である状態で`j`番目の更新を取り消す．
+    pub fn solve<S, R>(
+        &self,
+        state: &mut S,
+        mut add: impl FnMut(&mut S, usize),
+        mut remove: impl FnMut(&mut S, usize),
+        mut apply: impl FnMut(&mut S, usize, Range<usize>),
+        mut undo: impl FnMut(&mut S, usize, Range<usize>),
+        mut answer: impl FnMut(&mut S, usize) -> R,
+    ) -> Vec<R> {
+        let mut answers = (0..self.queries.len()).map(|_| None).collect::<Vec<_>>();
+
+        let block = ((self.n.max(1) as f64).powf(2.0 / 3.0).ceil() as usize).max(1);
+        let mut order = (0..self.queries.len()).collect::<Vec<_>>();
+        order.sort_unstable_by_key(|&qi| {
+            let (l, r, t) = self.queries[qi];
+            let (bl, br) = (l / block, r / block);
+            // ブロックの偶奇で向きを変えて移動量を減らす
+            let r_key = if bl % 2 == 0 { br } else { !br };
+            let t_key = if br % 2 == 0 { t } else { !t };
+            (bl, r_key, t_key)
+        });
+
+        let (mut l, mut r, mut t) = (0, 0, 0);
+        for qi in order {
+            let (ql, qr, qt) = self.queries[qi];
+            while l > ql {
+                l -= 1;
+                add(state, l);
+            }
+            while r < qr {
+                add(state, r);
+                r += 1;
+            }
+            while l < ql {
+                remove(state, l);
+                l += 1;
+            }
+            while r > qr {
+                r -= 1;
+                remove(state, r);
+            }
+            while t < qt {
+                apply(state, t, l..r);
+                t += 1;
+            }
+            while t > qt {
+                t -= 1;
+                undo(state, t, l..r);
+            }
+            answers[qi] = Some(answer(state, qi));
+        }
+
+        answers.into_iter().map(Option::unwrap).collect()
+    }
+}
+
+/// 木のパス上の頂点集合に関するクエリに対するMo's algorithm
+pub struct TreeMo {
+    /// オイラーツアーで訪れた頂点の列(各頂点が入るときと出るときの2回現れる)
+    tour: Vec<usize>,
+
+    /// 各頂点に入る時刻
+    tin: Vec<usize>,
+
+    /// 各頂点から出る時刻
+    tout: Vec<usize>,
+
+    /// ダブリングによる祖先．`ancestors[k][v]`は`v`の`2^k`個上の祖先
+    ancestors: Vec<Vec<usize>>,
+
+    /// 各頂点の深さ
+    depth: Vec<usize>,
+
+    /// 登録されたクエリ(オイラーツアー上の区間と，区間に含まれないLCA)
+    queries: Vec<(usize, usize, Option<usize>)>,
+}
+
+impl TreeMo {
+    /// 頂点`0`を根とする木`graph`(隣接リスト)に対するクエリを受け付ける．
+    pub fn new(graph: &[Vec<usize>]) -> Self {
+        let n = graph.len();
+        assert!(n > 0);
+
+        let mut tour = Vec::with_capacity(2 * n);
+        let mut tin = vec![0; n];
+        let mut tout = vec![0; n];
+        let mut parent = vec![usize::MAX; n];
+        let mut depth = vec![0; n];
+
+        let mut stack = vec![(0, false)];
+        while let Some((v, exit)) = stack.pop() {
+            if exit {
+                tout[v] = tour.len();
+                tour.push(v);
+                continue;
+            }
+            tin[v] = tour.len();
+            tour.push(v);
+            stack.push((v, true));
+            for &nv in &graph[v] {
+                if nv != parent[v] {
+                    parent[nv] = v;
+                    depth[nv] = depth[v] + 1;
+                    stack.push((nv, false));
+                }
+            }
+        }
+        assert_eq!(tour.len(), 2 * n, "graph must be a tree");
+        parent[0] = 0;
+
+        let log = (usize::BITS - n.leading_zeros()) as usize;
+        let mut ancestors = vec![parent];
+        for k in 1..log.max(1) {
+            let prev = &ancestors[k - 1];
+            let next = (0..n).map(|v| prev[prev[v]]).collect();
+            ancestors.push(next);
+        }
+
+        Self {
+            tour,
+            tin,
+            tout,
+            ancestors,
+            depth,
+            queries: vec![],
+        }
+    }
+
+    /// 頂点`u`と`v`を結ぶパスに対するクエリを登録する．
+    /// クエリ番号(登録順に0から振られる)を返す．
+    pub fn query(&mut self, u: usize, v: usize) -> usize {
+        let (u, v) = if self.tin[u] <= self.tin[v] {
+            (u, v)
+        } else {
+            (v, u)
+        };
+        let lca = self.lca(u, v);
+        let query = if lca == u {
+            (self.tin[u], self.tin[v] + 1, None)
+        } else {
+            (self.tout[u], self.tin[v] + 1, Some(lca))
+        };
+        self.queries.push(query);
+        self.queries.len() - 1
+    }
+
+    /// 各クエリについて，状態をパス上の頂点集合に合わせたうえで`answer(state, クエリ番号)`を呼び，
+    /// その戻り値をクエリ番号順に並べて返す．
+    ///
+    /// - `add(state, v)`, `remove(state, v)`: 頂点`v`を集合に追加・集合から削除する．
+    pub fn solve<S, R>(
+        &self,
+        state: &mut S,
+        mut add: impl FnMut(&mut S, usize),
+        mut remove: impl FnMut(&mut S, usize),
+        mut answer: impl FnMut(&mut S, usize) -> R,
+    ) -> Vec<R> {
+        let n = self.tin.len();
+        let mut in_set = vec![false; n];
+        let mut toggle = |state: &mut S, in_set: &mut Vec<bool>, v: usize| {
+            if in_set[v] {
+                remove(state, v);
+            } else {
+                add(state, v);
+            }
+            in_set[v] = !in_set[v];
+        };
+
+        let ranges = self
+            .queries
+            .iter()
+            .map(|&(l, r, _)| (l, r))
+            .collect::<Vec<_>>();
+        let mut answers = (0..self.queries.len()).map(|_| None).collect::<Vec<_>>();
+        let (mut l, mut r) = (0, 0);
+
+        for qi in hilbert_order(self.tour.len(), &ranges) {
+            let (ql, qr, lca) = self.queries[qi];
+            while l > ql {
+                l -= 1;
+                toggle(state, &mut in_set, self.tour[l]);
+            }
+            while r < qr {
+                toggle(state, &mut in_set, self.tour[r]);
+                r += 1;
+            }
+            while l < ql {
+                toggle(state, &mut in_set, self.tour[l]);
+                l += 1;
+            }
+            while r > qr {
+                r -= 1;
+                toggle(state, &mut in_set, self.tour[r]);
+            }
+
+            if let Some(lca) = lca {
+                toggle(state, &mut in_set, lca);
+            }
+            answers[qi] = Some(answer(state, qi));
+            if let Some(lca) = lca {
+                toggle(state, &mut in_set, lca);
+            }
+        }
+
+        answers.into_iter().map(Option::unwrap).collect()
+    }
+
+    fn lca(&self, mut u: usize, mut v: usize) -> usize {
+        if self.depth[u] < self.depth[v] {
+            std::mem::swap(&mut u, &mut v);
+        }
+        let diff = self.depth[u] - self.depth[v];
+        for (k, ancestors) in self.ancestors.iter().enumerate() {
+            if (diff >> k) & 1 == 1 {
+                u = ancestors[u];
+            }
+        }
+        if u == v {
+            return u;
+        }
+        for ancestors in self.ancestors.iter().rev() {
+            if ancestors[u] != ancestors[v] {
+                u = ancestors[u];
+                v = ancestors[v];
+            }
+        }
+        self.ancestors[0][u]
+    }
+}
+
+/// クエリ区間`[l, r)`をHilbert曲線上の順序で並べたときの，クエリ番号の列を返す．
+fn hilbert_order(n: usize, queries: &[(usize, usize)]) -> Vec<usize> {
+    let size = (n + 1).next_power_of_two();
+    let keys = queries
+        .iter()
+        .map(|&(l, r)| hilbert_index(size, l, r))
+        .collect::<Vec<_>>();
+    let mut order = (0..queries.len()).collect::<Vec<_>>();
+    order.sort_unstable_by_key(|&qi| keys[qi]);
+    order
+}
+
+/// 一辺`size`(2冪)の格子上の点`(x, y)`の，Hilbert曲線上の位置を返す．
+fn hilbert_index(size: usize, mut x: usize, mut y: usize) -> u64 {
+    let mut d = 0;
+    let mut s = size / 2;
+    while s > 0 {
+        let rx = (x & s > 0) as u64;
+        let ry = (y & s > 0) as u64;
+        d += (s * s) as u64 * ((3 * rx) ^ ry);
+        if ry == 0 {
+            if rx == 1 {
+                x = size - 1 - x;
+                y = size - 1 - y;
+            }
+            std::mem::swap(&mut x, &mut y);
+        }
+        s /= 2;
+    }
+    d
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashSet, VecDeque};
+
+    use rand::Rng;
+
+    use super::*;
+    use crate::utils::test_utils::random::get_test_rng;
+
+    /// (各値の出現回数, 種類数)
+    type State = (Vec<usize>, usize);
+
+    fn add(state: &mut State, x: usize) {
+        state.0[x] += 1;
+        if state.0[x] == 1 {
+            state.1 += 1;
+        }
+    }
+
+    fn remove(state: &mut State, x: usize) {
+        state.0[x] -= 1;
+        if state.0[x] == 0 {
+            state.1 -= 1;
+        }
+    }
+
+    #[test]
+    fn test_hilbert_index_is_bijective() {
+        for size in [1, 2, 4, 8, 16] {
+            let indices = (0..size)
+                .flat_map(|x| (0..size).map(move |y| hilbert_index(size, x, y)))
+                .collect::<HashSet<_>>();
+            assert_eq!(indices.len(), size * size);
+            assert!(indices.iter().all(|&d| d < (size * size) as u64));
+        }
+    }
+
+    #[test]
+    fn test_mo_randomized() {
+        let mut rng = get_test_rng();
+
+        for _ in 0..100 {
+            let n = rng.random_range(0..50);
+            let a = (0..n).map(|_| rng.random_range(0..10)).collect::<Vec<_>>();
+            let mut mo = Mo::new(n);
+            let mut expected = vec![];
+            for _ in 0..rng.random_range(0..50) {
+                let l = rng.random_range(0..=n);
+                let r = rng.random_range(l..=n);
+                mo.query(l..r);
+                expected.push(a[l..r].iter().collect::<HashSet<_>>().len());
+            }
+
+            let mut state = (vec![0; 10], 0);
+            let add_a = |state: &mut State, i: usize| add(state, a[i]);
+            let remove_a = |state: &mut State, i: usize| remove(state, a[i]);
+            let answers = mo.solve(&mut state, add_a, add_a, remove_a, remove_a, |state, _| {
+                state.1
+            });
+            assert_eq!(answers, expected);
+
+            // 左右を区別する場合: 区間の列をそのまま復元する
+            let mut deque = VecDeque::new();
+            let answers = mo.solve(
+                &mut deque,
+                |deque, i| deque.push_front(a[i]),
+                |deque, i| deque.push_back(a[i]),
+                |deque, _| {
+                    deque.pop_front();
+                },
+                |deque, _| {
+                    deque.pop_back();
+                },
+                |deque, qi| (deque.iter().copied().collect::<Vec<_>>(), qi),
+            );
+            for (qi, (seq, i)) in answers.into_iter().enumerate() {
+                let (l, r) = mo.queries[qi];
+                assert_eq!(i, qi);
+                assert_eq!(seq, a[l..r]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_mo_with_updates_randomized() {
+        let mut rng = get_test_rng();
+
+        for _ in 0..100 {
+            let n = rng.random_range(1..40);
+            let mut a = (0..n).map(|_| rng.random_range(0..10)).collect::<Vec<_>>();
+            let initial = a.clone();
+            let mut mo = MoWithUpdates::new(n);
+            let mut updates = vec![];
+            let mut expected = vec![];
+
+            for _ in 0..rng.random_range(0..60) {
+                if rng.random_bool(0.5) {
+                    let i = rng.random_range(0..n);
+                    let x = rng.random_range(0..10);
+                    assert_eq!(mo.update(), updates.len());
+                    updates.push((i, a[i], x));
+                    a[i] = x;
+                } else {
+                    let l = rng.random_range(0..=n);
+                    let r = rng.random_range(l..=n);
+                    mo.query(l..r);
+                    expected.push(a[l..r].iter().collect::<HashSet<_>>().len());
+                }
+            }
+
+            let mut state = ((vec![0; 10], 0), initial);
+            let answers = mo.solve(
+                &mut state,
+                |(state, a), i| add(state, a[i]),
+                |(state, a), i| remove(state, a[i]),
+                |(state, a), j, range| {
+                    let (i, old, new) = updates[j];
+                    if range.contains(&i) {
+                        remove(state, old);
+                        add(state, new);
+                    }
+                    a[i] = new;
+                },
+                |(state, a), j, range| {
+                    let (i, old, new) = updates[j];
+                    if range.contains(&i) {
+                        remove(state, new);
+                        add(state, old);
+                    }
+                    a[i] = old;
+                },
+                |(state, _), _| state.1,
+            );
+            assert_eq!(answers, expected);
+        }
+    }
+
+    #[test]
+    fn test_tree_mo_randomized() {
+        let mut rng = get_test_rng();
+
+        for _ in 0..100 {
+            let n = rng.random_range(1..40);
+            let mut graph = vec![vec![]; n];
+            let mut parent = vec![usize::MAX; n];
+            for v in 1..n {
+                let p = rng.random_range(0..v);
+                graph[p].push(v);
+                graph[v].push(p);
+                parent[v] = p;
+            }
+            let c = (0..n).map(|_| rng.random_range(0..10)).collect::<Vec<_>>();
+
+            let path = |mut u: usize, mut v: usize| {
+                let ancestors = |mut v: usize| {
+                    let mut res = vec![v];
+                    while parent[v] != usize::MAX {
+                        v = parent[v];
+                        res.push(v);
+                    }
+                    res
+                };
+                let au = ancestors(u);
+                let av = ancestors(v);
+                let lca = *au.iter().find(|x| av.contains(x)).unwrap();
+                let mut res = vec![lca];
+                while u != lca {
+                    res.push(u);
+                    u = parent[u];
+                }
+                while v != lca {
+                    res.push(v);
+                    v = parent[v];
+                }
+                res
+            };
+
+            let mut mo = TreeMo::new(&graph);
+            let mut expected = vec![];
+            for _ in 0..rng.random_range(0..50) {
+                let u = rng.random_range(0..n);
+                let v = rng.random_range(0..n);
+                mo.query(u, v);
+                let vertices = path(u, v);
+                expected.push((
+                    vertices.iter().map(|&v| c[v]).collect::<HashSet<_>>().len(),
+                    vertices.len(),
+                ));
+            }
+
+            let mut state = ((vec![0; 10], 0), 0);
+            let answers = mo.solve(
+                &mut state,
+                |(state, len), v| {
+                    add(state, c[v]);
+                    *len += 1;
+                },
+                |(state, len), v| {
+                    remove(state, c[v]);
+                    *len -= 1;
+                },
+                |(state, len), _| (state.1, *len),
+            );
+            assert_eq!(answers, expected);
+        }
+    }
+}
@@ -0,0 +1,7 @@
+//! オフラインアルゴリズム(offline algorithms)
+
+pub mod cdq;
+pub mod dynamic_connectivity;
+pub mod mo;
+pub mod parallel_binary_search;
+pub mod rectangle_sum;
@@ -0,0 +1,156 @@
+//! 並列二分探索(Parallel Binary Search)
+//!
+//! `T`回の操作からなる単調な過程について，複数のクエリそれぞれの
+//! 「条件を初めて満たすまでに必要な操作回数」をまとめて二分探索する．
+//! すべてのクエリの二分探索を1段階ずつ同時に進め，各段階で過程を最初から1回だけシミュレーションする．
+//! 単一の値に対する二分探索[`Bisect`](crate::bisect::Bisect)をクエリの集合に拡張したもの．
+//!
+//! # 計算量
+//! - 過程のシミュレーション O(log T) 回と，`check`の呼び出し O(Q log T) 回
+//!
+//! # 使用例
+//! 辺を順に追加していくとき，各頂点対が初めて連結になるのは何本目の辺を追加したときかを求める．
+//! ```
+//! use reprol::{ds::dsu::Dsu, offline::parallel_binary_search::parallel_binary_search};
+//!
+//! let edges = [(0, 1), (2, 3), (1, 2), (3, 4)];
+//! let queries = [(0, 3), (2, 3), (0, 0), (1, 4), (0, 5)];
+//!
+//! let mut dsu = Dsu::new(6);
+//! let answers = parallel_binary_search(
+//!     queries.len(),
+//!     edges.len(),
+//!     &mut dsu,
+//!     |dsu| *dsu = Dsu::new(6),
+//!     |dsu, t| dsu.merge(edges[t].0, edges[t].1),
+//!     |dsu, i| dsu.connected(queries[i].0, queries[i].1),
+//! );
+//! assert_eq!(answers, vec![Some(3), Some(2), Some(0), Some(4), None]);
+//! ```
+//!
+//! # 問題例
+//! - [AGC002 D](https://atcoder.jp/contests/agc002/tasks/agc002_d)
+
+/// `q`個のクエリについて，`check(state, i)`が`true`となる最小の操作回数`k`(`0 <= k <= t`)を返す．
+/// `t`回の操作後も`false`の場合は`None`を返す．
+///
+/// - `reset(state)`: 状態を操作を1回も行っていない状態に戻す．
+/// - `step(state, j)`: `j`番目(0-indexed)の操作を行う．
+/// - `check(state, i)`: 現在の状態でクエリ`i`の条件を満たすかを返す．
+///
+/// # 制約
+/// - 各クエリについて，`check`は操作回数に関して単調(一度`true`になれば以降も`true`)
+pub fn parallel_binary_search<S>(
+    q: usize,
+    t: usize,
+    state: &mut S,
+    mut reset: impl FnMut(&mut S),
+    mut step: impl FnMut(&mut S, usize),
+    mut check: impl FnMut(&mut S, usize) -> bool,
+) -> Vec<Option<usize>> {
+    // 答えは[lo, hi]に含まれる．hi = t + 1 は条件を満たさないことを表す．
+    let mut lo = vec![0; q];
+    let mut hi = vec![t + 1; q];
+
+    loop {
+        let mut buckets = vec![vec![]; t + 1];
+        let mut is_done = true;
+        for i in 0..q {
+            if lo[i] < hi[i] {
+                buckets[(lo[i] + hi[i]) / 2].push(i);
+                is_done = false;
+            }
+        }
+        if is_done {
+            break;
+        }
+
+        reset(state);
+        for (k, bucket) in buckets.iter().enumerate() {
+            if k > 0 {
+                step(state, k - 1);
+            }
+            for &i in bucket {
+                if check(state, i) {
+                    hi[i] = k;
+                } else {
+                    lo[i] = k + 1;
+                }
+            }
+        }
+    }
+
+    lo.into_iter().map(|k| (k <= t).then_some(k)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::*;
+    use crate::{ds::dsu::Dsu, utils::test_utils::random::get_test_rng};
+
+    #[test]
+    fn test_prefix_sum() {
+        // 接頭辞和が初めてx以上になる長さ
+        let v = [3, 1, 4, 1, 5];
+        let queries = [0, 1, 3, 4, 8, 14, 15];
+        let mut steps = 0;
+        let answers = parallel_binary_search(
+            queries.len(),
+            v.len(),
+            &mut 0,
+            |sum| {
+                *sum = 0;
+                steps += 1;
+            },
+            |sum, j| *sum += v[j],
+            |sum, i| *sum >= queries[i],
+        );
+        assert_eq!(
+            answers,
+            vec![Some(0), Some(1), Some(1), Some(2), Some(3), Some(5), None]
+        );
+        assert!(steps <= 3);
+    }
+
+    #[test]
+    fn test_randomized() {
+        let mut rng = get_test_rng();
+
+        for _ in 0..50 {
+            let n = rng.random_range(1..20);
+            let m = rng.random_range(0..30);
+            let q = rng.random_range(0..30);
+            let edges = (0..m)
+                .map(|_| (rng.random_range(0..n), rng.random_range(0..n)))
+                .collect::<Vec<_>>();
+            let queries = (0..q)
+                .map(|_| (rng.random_range(0..n), rng.random_range(0..n)))
+                .collect::<Vec<_>>();
+
+            let answers = parallel_binary_search(
+                q,
+                m,
+                &mut Dsu::new(n),
+                |dsu| *dsu = Dsu::new(n),
+                |dsu, j| dsu.merge(edges[j].0, edges[j].1),
+                |dsu, i| dsu.connected(queries[i].0, queries[i].1),
+            );
+
+            let expected = queries
+                .iter()
+                .map(|&(u, v)| {
+                    let mut dsu = Dsu::new(n);
+                    (0..=m).find(|&k| {
+                        if k > 0 {
+                            dsu.merge(edges[k - 1].0, edges[k - 1].1);
+                        }
+                        dsu.connected(u, v)
+                    })
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(answers, expected);
+        }
+    }
+}
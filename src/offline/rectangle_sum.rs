@@ -0,0 +1,291 @@
+//! オフライン矩形和(Offline Rectangle Sum)
+//!
+//! 2次元平面上への点の追加と矩形領域に含まれる点の重みの総和を求めるクエリの列を先読みして処理する．
+//! 各クエリは，そのクエリより前に追加された点のみを対象とする．
+//!
+//! すべての点の追加がクエリより前にある場合は，x座標の平面走査と[`FenwickTree`]で処理する．
+//! 点の追加とクエリが混在する場合は，[`cdq`]で時刻について分割統治し，
+//! 前半の点から後半のクエリへの寄与を平面走査で計算する．
+//! 重みは可換群の要素である必要がある．
+//!
+//! # 計算量
+//! 点の数を N，クエリ数を Q として，
+//! - 点の追加がすべてクエリより前の場合: O((N + Q) log N)
+//! - 点の追加とクエリが混在する場合: O((N + Q) log (N + Q) log N)
+//!
+//! # 使用例
+//! ```
+//! use reprol::{offline::rectangle_sum::RectangleSum, ops::op_add::OpAdd};
+//!
+//! let mut rs = RectangleSum::<i32, OpAdd<i64>>::new();
+//! rs.add_point(1, 1, 3);
+//! rs.add_point(2, 5, 4);
+//! rs.query(0..3, 0..3); // 点(1, 1)のみ
+//! rs.add_point(1, 2, 10);
+//! rs.query(0..3, 0..10); // すべての点
+//! rs.query(2..3, 0..10); // 点(2, 5)のみ
+//! assert_eq!(rs.solve(), vec![3, 17, 4]);
+//! ```
+//!
+//! # 問題例
+//! - [Rectangle Sum](https://judge.yosupo.jp/problem/rectangle_sum)
+//! - [Point Add Rectangle Sum](https://judge.yosupo.jp/problem/point_add_rectangle_sum)
+
+use std::ops::Range;
+
+use crate::{
+    bisect::SliceBoundsExt, ds::fenwick_tree::FenwickTree, offline::cdq::cdq, ops::group::Group,
+};
+
+/// 点の追加とクエリ
+enum Event {
+    /// `points`の添字
+    Point(usize),
+
+    /// `queries`の添字
+    Query(usize),
+}
+
+/// オフライン矩形和
+pub struct RectangleSum<K, O: Group> {
+    /// 追加された点`(x, y, 重み)`
+    points: Vec<(K, K, O::Element)>,
+
+    /// クエリの矩形領域`[xl, xr) x [yl, yr)`
+    queries: Vec<(Range<K>, Range<K>)>,
+
+    /// 点の追加とクエリを時刻順に並べたもの
+    events: Vec<Event>,
+
+    /// 演算(群)
+    op: O,
+}
+
+impl<K: Ord + Clone, O: Group> RectangleSum<K, O> {
+    /// 点もクエリもない状態で初期化する．
+    pub fn new() -> Self
+    where
+        O: Default,
+    {
+        Self::with_op(O::default())
+    }
+
+    /// 演算`op`を指定して，点もクエリもない状態で初期化する．
+    pub fn with_op(op: O) -> Self {
+        Self {
+            points: vec![],
+            queries: vec![],
+            events: vec![],
+            op,
+        }
+    }
+
+    /// 点`(x, y)`に重み`w`を追加する．同じ点に複数回追加してもよい．
+    pub fn add_point(&mut self, x: K, y: K, w: O::Element) {
+        self.events.push(Event::Point(self.points.len()));
+        self.points.push((x, y, w));
+    }
+
+    /// 矩形領域`x_range x y_range`に含まれる点の重みの総和を求めるクエリを追加し，クエリ番号を返す．
+    pub fn query(&mut self, x_range: Range<K>, y_range: Range<K>) -> usize {
+        let index = self.queries.len();
+        self.events.push(Event::Query(index));
+        self.queries.push((x_range, y_range));
+        index
+    }
+
+    /// すべてのクエリを処理し，クエリ番号順に答えを返す．
+    pub fn solve(self) -> Vec<O::Element>
+    where
+        O: Clone,
+    {
+        let mut ys = self
+            .points
+            .iter()
+            .map(|(_, y, _)| y.clone())
+            .collect::<Vec<_>>();
+        ys.sort_unstable();
+        ys.dedup();
+
+        let solver = Solver {
+            points: self
+                .points
+                .iter()
+                .map(|(x, y, w)| (x, ys.lower_bound(y), w))
+                .collect(),
+            queries: self
+                .queries
+                .iter()
+                .map(|(x, y)| (x, ys.lower_bound(&y.start), ys.lower_bound(&y.end)))
+                .collect(),
+            op: &self.op,
+        };
+
+        let mut ft = FenwickTree::with_op(ys.len(), self.op.clone());
+        let mut res = (0..self.queries.len())
+            .map(|_| self.op.id())
+            .collect::<Vec<_>>();
+
+        let first_query = self
+            .events
+            .iter()
+            .position(|e| matches!(e, Event::Query(_)))
+            .unwrap_or(self.events.len());
+        let is_static = self.events[first_query..]
+            .iter()
+            .all(|e| matches!(e, Event::Query(_)));
+
+        if is_static {
+            let points = (0..self.points.len()).collect::<Vec<_>>();
+            let queries = (0..self.queries.len()).collect::<Vec<_>>();
+            solver.sweep(&mut ft, &points, &queries, &mut res);
+        } else {
+            cdq(self.events.len(), |left, right| {
+                let points = self.events[left]
+                    .iter()
+                    .filter_map(|e| match *e {
+                        Event::Point(i) => Some(i),
+                        Event::Query(_) => None,
+                    })
+                    .collect::<Vec<_>>();
+                let queries = self.events[right]
+                    .iter()
+                    .filter_map(|e| match *e {
+                        Event::Point(_) => None,
+                        Event::Query(i) => Some(i),
+                    })
+                    .collect::<Vec<_>>();
+                if !points.is_empty() && !queries.is_empty() {
+                    solver.sweep(&mut ft, &points, &queries, &mut res);
+                }
+            });
+        }
+
+        res
+    }
+}
+
+impl<K: Ord + Clone, O: Group + Default> Default for RectangleSum<K, O> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// y座標を圧縮した点とクエリ
+struct Solver<'a, K, O: Group> {
+    /// `(x, 圧縮したy, 重み)`
+    points: Vec<(&'a K, usize, &'a O::Element)>,
+
+    /// `(xの範囲, 圧縮したyの範囲の左端, 右端)`
+    queries: Vec<(&'a Range<K>, usize, usize)>,
+
+    op: &'a O,
+}
+
+impl<K: Ord, O: Group> Solver<'_, K, O> {
+    /// 点`points`からクエリ`queries`への寄与を平面走査で計算し，`res`に加える．
+    /// `ft`は単位元で初期化されている必要があり，呼び出し後も単位元に戻される．
+    fn sweep(
+        &self,
+        ft: &mut FenwickTree<O>,
+        points: &[usize],
+        queries: &[usize],
+        res: &mut [O::Element],
+    ) {
+        let mut points = points.to_vec();
+        points.sort_unstable_by(|&i, &j| self.points[i].0.cmp(self.points[j].0));
+
+        // (x座標, クエリ番号, 右端か)
+        let mut events = Vec::with_capacity(2 * queries.len());
+        for &i in queries {
+            let (x_range, _, _) = &self.queries[i];
+            if x_range.start < x_range.end {
+                events.push((&x_range.start, i, false));
+                events.push((&x_range.end, i, true));
+            }
+        }
+        events.sort_unstable_by(|lhs, rhs| lhs.0.cmp(rhs.0));
+
+        let mut p = 0;
+        for (x, i, is_end) in events {
+            while p < points.len() && self.points[points[p]].0 < x {
+                let (_, y, w) = self.points[points[p]];
+                ft.op(y, w);
+                p += 1;
+            }
+            let (_, yl, yr) = self.queries[i];
+            if yl < yr {
+                let sum = ft.fold(yl..yr);
+                res[i] = if is_end {
+                    self.op.op(&res[i], &sum)
+                } else {
+                    self.op.op(&res[i], &self.op.inv(&sum))
+                };
+            }
+        }
+
+        for &i in &points[..p] {
+            let (_, y, w) = self.points[i];
+            ft.op(y, &self.op.inv(w));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{Rng, seq::SliceRandom};
+
+    use super::*;
+    use crate::{
+        ops::{op_add::OpAdd, op_xor::OpXor},
+        utils::test_utils::random::get_test_rng,
+    };
+
+    #[test]
+    fn test_randomized() {
+        let mut rng = get_test_rng();
+
+        for is_static in [true, false] {
+            for _ in 0..100 {
+                let mut sum = RectangleSum::<i32, OpAdd<i64>>::new();
+                let mut xor = RectangleSum::<i32, OpXor<u32>>::new();
+                let mut points = vec![];
+                let mut expected = vec![];
+
+                let n = rng.random_range(0..50);
+                let q = rng.random_range(0..50);
+                let mut events = (0..n + q).map(|i| i < n).collect::<Vec<_>>();
+                if !is_static {
+                    events.shuffle(&mut rng);
+                }
+
+                for is_point in events {
+                    if is_point {
+                        let (x, y) = (rng.random_range(-20..=20), rng.random_range(-20..=20));
+                        let w = rng.random_range(-1000..=1000);
+                        sum.add_point(x, y, w as i64);
+                        xor.add_point(x, y, w as u32);
+                        points.push((x, y, w));
+                    } else {
+                        let xl = rng.random_range(-25..=25);
+                        let xr = rng.random_range(xl..=25);
+                        let yl = rng.random_range(-25..=25);
+                        let yr = rng.random_range(yl..=25);
+                        sum.query(xl..xr, yl..yr);
+                        xor.query(xl..xr, yl..yr);
+                        let inside = points
+                            .iter()
+                            .filter(|&&(x, y, _)| (xl..xr).contains(&x) && (yl..yr).contains(&y));
+                        expected.push((
+                            inside.clone().map(|&(_, _, w)| w as i64).sum::<i64>(),
+                            inside.fold(0, |acc, &(_, _, w)| acc ^ w as u32),
+                        ));
+                    }
+                }
+
+                let actual = sum.solve().into_iter().zip(xor.solve()).collect::<Vec<_>>();
+                assert_eq!(actual, expected);
+            }
+        }
+    }
+}
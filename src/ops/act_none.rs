@@ -0,0 +1,62 @@
+//! 自明な作用
+//!
+//! 恒等作用のみからなる作用を表すモノイド．
+//! 作用素の型は`()`で，任意のモノイドに対して値を変えずに作用する．
+//!
+//! 作用をとるデータ構造を，作用を使わずにモノイドのみで利用する場合に使用する．
+//!
+//! # Examples
+//!
+//! ```
+//! use reprol::ops::monoid::Monoid;
+//! use reprol::ops::action::Action;
+//! use reprol::ops::act_none::ActNone;
+//! use reprol::ops::op_max::OpMax;
+//!
+//! let act = ActNone;
+//! assert_eq!(act.op(&(), &()), ());
+//! assert_eq!(Action::<OpMax<i64>>::act(&act, &act.id(), &3), 3);
+//! ```
+
+use crate::ops::{action::Action, monoid::Monoid};
+
+/// 自明な作用
+///
+/// 恒等作用のみからなる作用を表すモノイド．
+#[derive(Default, Clone, Copy)]
+pub struct ActNone;
+
+impl Monoid for ActNone {
+    type Element = ();
+
+    #[inline]
+    fn op(&self, _: &Self::Element, _: &Self::Element) -> Self::Element {}
+
+    #[inline]
+    fn id(&self) -> Self::Element {}
+}
+
+impl<O: Monoid> Action<O> for ActNone
+where
+    O::Element: Clone,
+{
+    #[inline]
+    fn act(&self, _: &Self::Element, x: &O::Element) -> O::Element {
+        x.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::{op_add::OpAdd, op_min::OpMin};
+
+    #[test]
+    fn test_act_identity() {
+        let act = ActNone;
+        for x in [0, 1, -1, 1030, i64::MAX, i64::MIN] {
+            assert_eq!(Action::<OpAdd<i64>>::act(&act, &act.id(), &x), x);
+            assert_eq!(Action::<OpMin<i64>>::act(&act, &act.op(&(), &()), &x), x);
+        }
+    }
+}
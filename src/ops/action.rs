@@ -42,6 +42,9 @@ use crate::ops::monoid::Monoid;
 /// 以下の条件を満たす:
 /// - 恒等作用: `act(id(), x) = x`
 /// - 合成との整合: `act(op(g, f), x) = act(g, act(f, x))`
+/// - 演算との整合: `act(f, x * y) = act(f, x) * act(f, y)` (遅延セグメント木で用いる場合)
+///
+/// これらの法則は[`check_action`](crate::ops::laws::check_action)で検査できる．
 ///
 /// # Notes
 ///
@@ -0,0 +1,556 @@
+//! 代数的法則の検査(law checking)
+//!
+//! 自作の[`Monoid`]・[`Group`]・[`Action`]の実装が満たすべき法則を検査する関数群．
+//! 生成関数`generate(i)`(`0 <= i < n`)で作った`n`個の要素のすべての組について法則を確かめる．
+//! 乱数を用いないため結果は決定的で，違反があれば[`LawViolation`]を返す．
+//!
+//! 組は「使う要素の添字の最大値」が小さい順(同じなら辞書順)に調べ，最初に見つかった違反を返す．
+//! したがって，生成関数が単純な要素から順に返すようにすれば，最小の反例が得られる．
+//!
+//! - [`check_monoid`][]: 結合律，単位元
+//! - [`check_commutative_monoid`][]: 結合律・単位元に加えて可換律
+//! - [`check_idempotent_monoid`][]: 結合律・単位元に加えて冪等律
+//! - [`check_group`][]: 結合律・単位元に加えて逆元
+//! - [`check_action`][]: 作用素のモノイドの法則，恒等作用，演算との整合，合成との整合
+//!
+//! # 計算量
+//! - 3つ組を調べるため O(n^3)
+//!
+//! # 使用例
+//! ```
+//! use reprol::ops::{
+//!     laws::{Law, check_action, check_group, check_monoid},
+//!     monoid::Monoid,
+//!     op_add::OpAdd,
+//!     op_max::OpMax,
+//!     act_add::ActAdd,
+//! };
+//!
+//! assert!(check_group(&OpAdd::<i64>::default(), 10, |i| i as i64 - 5).is_ok());
+//! assert!(check_action(&OpMax::<i64>::default(), &ActAdd::default(), 10, |i| i as i64 - 5, |i| i as i64 - 5).is_ok());
+//!
+//! // 減算は左単位元の法則を満たさない
+//! struct OpSub;
+//! impl Monoid for OpSub {
+//!     type Element = i64;
+//!     fn op(&self, lhs: &i64, rhs: &i64) -> i64 { lhs - rhs }
+//!     fn id(&self) -> i64 { 0 }
+//! }
+//!
+//! let violation = check_monoid(&OpSub, 10, |i| i as i64).unwrap_err();
+//! assert_eq!(violation.law, Law::LeftIdentity);
+//! assert_eq!(violation.inputs, vec!["1"]); // 0 - 1 != 1
+//! ```
+
+use std::{error::Error, fmt};
+
+use crate::ops::{
+    action::Action,
+    group::Group,
+    monoid::{CommutativeMonoid, IdempotentMonoid, Monoid},
+};
+
+/// 検査する法則
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Law {
+    /// `(x * y) * z = x * (y * z)`
+    Associativity,
+
+    /// `e * x = x`
+    LeftIdentity,
+
+    /// `x * e = x`
+    RightIdentity,
+
+    /// `x * x = x`
+    Idempotency,
+
+    /// `x * y = y * x`
+    Commutativity,
+
+    /// `inv(x) * x = e`
+    LeftInverse,
+
+    /// `x * inv(x) = e`
+    RightInverse,
+
+    /// `act(id(), x) = x`
+    ActionIdentity,
+
+    /// `act(f, x * y) = act(f, x) * act(f, y)`
+    ActionHomomorphism,
+
+    /// `act(op(g, f), x) = act(g, act(f, x))`
+    ActionComposition,
+}
+
+impl fmt::Display for Law {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Law::Associativity => "結合律 (x * y) * z = x * (y * z)",
+            Law::LeftIdentity => "左単位元 e * x = x",
+            Law::RightIdentity => "右単位元 x * e = x",
+            Law::Idempotency => "冪等律 x * x = x",
+            Law::Commutativity => "可換律 x * y = y * x",
+            Law::LeftInverse => "左逆元 inv(x) * x = e",
+            Law::RightInverse => "右逆元 x * inv(x) = e",
+            Law::ActionIdentity => "恒等作用 act(id(), x) = x",
+            Law::ActionHomomorphism => "演算との整合 act(f, x * y) = act(f, x) * act(f, y)",
+            Law::ActionComposition => "合成との整合 act(op(g, f), x) = act(g, act(f, x))",
+        };
+        f.write_str(s)
+    }
+}
+
+/// 法則の違反(反例)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LawViolation {
+    /// 違反した法則
+    pub law: Law,
+
+    /// 反例となる入力(`Debug`表現)．順番は[`Law`]の式に現れる変数の順．
+    pub inputs: Vec<String>,
+
+    /// 法則の左辺の値(`Debug`表現)
+    pub lhs: String,
+
+    /// 法則の右辺の値(`Debug`表現)
+    pub rhs: String,
+}
+
+impl fmt::Display for LawViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} に違反: 入力 [{}] に対して左辺 = {}，右辺 = {}",
+            self.law,
+            self.inputs.join(", "),
+            self.lhs,
+            self.rhs
+        )
+    }
+}
+
+impl Error for LawViolation {}
+
+/// モノイドの結合律と単位元を検査する．
+pub fn check_monoid<O>(
+    op: &O,
+    n: usize,
+    generate: impl FnMut(usize) -> O::Element,
+) -> Result<(), LawViolation>
+where
+    O: Monoid,
+    O::Element: PartialEq + fmt::Debug,
+{
+    let xs = (0..n).map(generate).collect::<Vec<_>>();
+    check_monoid_samples(op, &xs)
+}
+
+/// 可換モノイドの結合律，単位元，可換律を検査する．
+pub fn check_commutative_monoid<O>(
+    op: &O,
+    n: usize,
+    generate: impl FnMut(usize) -> O::Element,
+) -> Result<(), LawViolation>
+where
+    O: CommutativeMonoid,
+    O::Element: PartialEq + fmt::Debug,
+{
+    let xs = (0..n).map(generate).collect::<Vec<_>>();
+    check_commutativity(op, &xs)?;
+    check_monoid_samples(op, &xs)
+}
+
+/// 冪等モノイドの結合律，単位元，冪等律を検査する．
+pub fn check_idempotent_monoid<O>(
+    op: &O,
+    n: usize,
+    generate: impl FnMut(usize) -> O::Element,
+) -> Result<(), LawViolation>
+where
+    O: IdempotentMonoid,
+    O::Element: PartialEq + fmt::Debug,
+{
+    let xs = (0..n).map(generate).collect::<Vec<_>>();
+    check_laws(Law::Idempotency, xs.len(), |[i]| {
+        let lhs = op.op(&xs[i], &xs[i]);
+        compare(&lhs, &xs[i], &[&xs[i]])
+    })?;
+    check_monoid_samples(op, &xs)
+}
+
+/// 群の結合律，単位元，逆元を検査する．
+pub fn check_group<O>(
+    op: &O,
+    n: usize,
+    generate: impl FnMut(usize) -> O::Element,
+) -> Result<(), LawViolation>
+where
+    O: Group,
+    O::Element: PartialEq + fmt::Debug,
+{
+    let xs = (0..n).map(generate).collect::<Vec<_>>();
+    let id = op.id();
+    check_laws(Law::LeftInverse, xs.len(), |[i]| {
+        let lhs = op.op(&op.inv(&xs[i]), &xs[i]);
+        compare(&lhs, &id, &[&xs[i]])
+    })?;
+    check_laws(Law::RightInverse, xs.len(), |[i]| {
+        let lhs = op.op(&xs[i], &op.inv(&xs[i]));
+        compare(&lhs, &id, &[&xs[i]])
+    })?;
+    check_monoid_samples(op, &xs)
+}
+
+/// モノイド`op`への作用`act`の法則を検査する．
+///
+/// 作用素を`generate_action(i)`で，作用される要素を`generate(i)`で生成し，
+/// 作用素のモノイドの法則，恒等作用，演算との整合，合成との整合の順に検査する．
+pub fn check_action<O, A>(
+    op: &O,
+    act: &A,
+    n: usize,
+    generate: impl FnMut(usize) -> O::Element,
+    generate_action: impl FnMut(usize) -> A::Element,
+) -> Result<(), LawViolation>
+where
+    O: Monoid,
+    A: Action<O>,
+    O::Element: PartialEq + fmt::Debug,
+    A::Element: PartialEq + fmt::Debug,
+{
+    let xs = (0..n).map(generate).collect::<Vec<_>>();
+    let fs = (0..n).map(generate_action).collect::<Vec<_>>();
+
+    check_monoid_samples(act, &fs)?;
+
+    let id = act.id();
+    check_laws(Law::ActionIdentity, n, |[i]| {
+        let lhs = act.act(&id, &xs[i]);
+        compare(&lhs, &xs[i], &[&xs[i]])
+    })?;
+
+    check_laws(Law::ActionHomomorphism, n, |[i, j, k]| {
+        let lhs = act.act(&fs[i], &op.op(&xs[j], &xs[k]));
+        let rhs = op.op(&act.act(&fs[i], &xs[j]), &act.act(&fs[i], &xs[k]));
+        compare(&lhs, &rhs, &[&fs[i], &xs[j], &xs[k]])
+    })?;
+
+    check_laws(Law::ActionComposition, n, |[i, j, k]| {
+        let lhs = act.act(&act.op(&fs[i], &fs[j]), &xs[k]);
+        let rhs = act.act(&fs[i], &act.act(&fs[j], &xs[k]));
+        compare(&lhs, &rhs, &[&fs[i], &fs[j], &xs[k]])
+    })
+}
+
+fn check_monoid_samples<O>(op: &O, xs: &[O::Element]) -> Result<(), LawViolation>
+where
+    O: Monoid,
+    O::Element: PartialEq + fmt::Debug,
+{
+    let id = op.id();
+    check_laws(Law::LeftIdentity, xs.len(), |[i]| {
+        compare(&op.op(&id, &xs[i]), &xs[i], &[&xs[i]])
+    })?;
+    check_laws(Law::RightIdentity, xs.len(), |[i]| {
+        compare(&op.op(&xs[i], &id), &xs[i], &[&xs[i]])
+    })?;
+    check_laws(Law::Associativity, xs.len(), |[i, j, k]| {
+        let lhs = op.op(&op.op(&xs[i], &xs[j]), &xs[k]);
+        let rhs = op.op(&xs[i], &op.op(&xs[j], &xs[k]));
+        compare(&lhs, &rhs, &[&xs[i], &xs[j], &xs[k]])
+    })
+}
+
+fn check_commutativity<O>(op: &O, xs: &[O::Element]) -> Result<(), LawViolation>
+where
+    O: Monoid,
+    O::Element: PartialEq + fmt::Debug,
+{
+    check_laws(Law::Commutativity, xs.len(), |[i, j]| {
+        let lhs = op.op(&xs[i], &xs[j]);
+        let rhs = op.op(&xs[j], &xs[i]);
+        compare(&lhs, &rhs, &[&xs[i], &xs[j]])
+    })
+}
+
+/// 違反の詳細(入力，左辺，右辺)
+type Detail = (Vec<String>, String, String);
+
+/// `lhs != rhs`の場合に違反の詳細を返す．
+fn compare<T>(lhs: &T, rhs: &T, inputs: &[&dyn fmt::Debug]) -> Option<Detail>
+where
+    T: PartialEq + fmt::Debug,
+{
+    (lhs != rhs).then(|| {
+        let inputs = inputs.iter().map(|x| format!("{:?}", x)).collect();
+        (inputs, format!("{:?}", lhs), format!("{:?}", rhs))
+    })
+}
+
+/// `[0, n)`の添字の`K`個組を，最大値の昇順，同じなら辞書順に調べ，最初の違反を返す．
+fn check_laws<const K: usize>(
+    law: Law,
+    n: usize,
+    mut test: impl FnMut([usize; K]) -> Option<Detail>,
+) -> Result<(), LawViolation> {
+    for m in 0..n {
+        if let Some((inputs, lhs, rhs)) = visit(&mut [0; K], 0, m, false, &mut test) {
+            return Err(LawViolation {
+                law,
+                inputs,
+                lhs,
+                rhs,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// 最大値がちょうど`m`の組を辞書順に列挙する．
+/// `t[..d]`は決定済みで，`has_max`はその中に`m`が含まれるかを表す．
+fn visit<const K: usize>(
+    t: &mut [usize; K],
+    d: usize,
+    m: usize,
+    has_max: bool,
+    test: &mut impl FnMut([usize; K]) -> Option<Detail>,
+) -> Option<Detail> {
+    if d == K {
+        return test(*t);
+    }
+
+    // 最後の位置までに m が現れていなければ，最後の位置は m に限る
+    let start = if has_max || d + 1 < K { 0 } else { m };
+    for v in start..=m {
+        t[d] = v;
+        if let Some(detail) = visit(t, d + 1, m, has_max || v == m, test) {
+            return Some(detail);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::{
+        act_add::ActAdd,
+        act_affine::{ActAffine, ActAffineElement},
+        act_none::ActNone,
+        act_range_add::ActRangeAdd,
+        act_range_set::ActRangeSet,
+        act_set::ActSet,
+        op_add::OpAdd,
+        op_gcd::OpGcd,
+        op_max::OpMax,
+        op_min::OpMin,
+        op_range_sum::{OpRangeSum, OpRangeSumElement},
+        op_xor::OpXor,
+    };
+
+    struct OpSub;
+
+    impl Monoid for OpSub {
+        type Element = i64;
+        fn op(&self, lhs: &i64, rhs: &i64) -> i64 {
+            lhs - rhs
+        }
+        fn id(&self) -> i64 {
+            0
+        }
+    }
+
+    /// 右単位元は満たすが結合律を満たさない
+    struct OpAverage;
+
+    impl Monoid for OpAverage {
+        type Element = i64;
+        fn op(&self, lhs: &i64, rhs: &i64) -> i64 {
+            if *lhs == i64::MIN {
+                *rhs
+            } else if *rhs == i64::MIN {
+                *lhs
+            } else {
+                (lhs + rhs) / 2
+            }
+        }
+        fn id(&self) -> i64 {
+            i64::MIN
+        }
+    }
+
+    impl CommutativeMonoid for OpAverage {}
+    impl IdempotentMonoid for OpAverage {}
+
+    #[test]
+    fn test_builtin_ops() {
+        let small = |i: usize| i as i64 - 4;
+        assert_eq!(check_group(&OpAdd::<i64>::default(), 9, small), Ok(()));
+        assert_eq!(
+            check_group(&OpXor::<u32>::default(), 9, |i| i as u32),
+            Ok(())
+        );
+        assert_eq!(
+            check_commutative_monoid(&OpMin::<i64>::default(), 9, small),
+            Ok(())
+        );
+        assert_eq!(
+            check_idempotent_monoid(&OpMax::<i64>::default(), 9, small),
+            Ok(())
+        );
+        assert_eq!(
+            check_idempotent_monoid(&OpGcd::<u64>::default(), 9, |i| i as u64 * 2),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_builtin_actions() {
+        let small = |i: usize| i as i64 - 3;
+        let leaf = |i: usize| OpRangeSumElement::leaf(i as i64 - 3);
+
+        assert_eq!(
+            check_action(
+                &OpMin::<i64>::default(),
+                &ActAdd::default(),
+                7,
+                small,
+                small
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            check_action(
+                &OpMax::<i64>::default(),
+                &ActSet::default(),
+                7,
+                small,
+                |i| { (i > 0).then(|| small(i)) }
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            check_action(&OpMin::<i64>::default(), &ActNone, 7, small, |_| ()),
+            Ok(())
+        );
+
+        // 長さの異なる区間も含める
+        let range = |i: usize| {
+            let x = leaf(i);
+            OpRangeSum::<i64>::default().op(&x, &leaf(i / 2))
+        };
+        assert_eq!(
+            check_action(
+                &OpRangeSum::<i64>::default(),
+                &ActRangeAdd::default(),
+                7,
+                range,
+                small
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            check_action(
+                &OpRangeSum::<i64>::default(),
+                &ActRangeSet::default(),
+                7,
+                range,
+                |i| (i > 0).then(|| small(i))
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            check_action(
+                &OpRangeSum::<i64>::default(),
+                &ActAffine::default(),
+                7,
+                range,
+                |i| ActAffineElement {
+                    a: i as i64 % 3 - 1,
+                    b: i as i64 / 3,
+                }
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_minimal_counterexample() {
+        let violation = check_monoid(&OpSub, 5, |i| i as i64).unwrap_err();
+        assert_eq!(violation.law, Law::LeftIdentity);
+        assert_eq!(violation.inputs, vec!["1"]);
+        assert_eq!(violation.lhs, "-1");
+        assert_eq!(violation.rhs, "1");
+
+        let violation = check_idempotent_monoid(&OpAverage, 5, |i| i as i64 * 2).unwrap_err();
+        assert_eq!(violation.law, Law::Associativity);
+        assert_eq!(violation.inputs, vec!["0", "0", "2"]);
+        assert_eq!(
+            violation.to_string(),
+            "結合律 (x * y) * z = x * (y * z) に違反: 入力 [0, 0, 2] に対して左辺 = 1，右辺 = 0"
+        );
+    }
+
+    #[test]
+    fn test_broken_action() {
+        // 区間の長さを考慮しない区間加算は演算との整合を満たさない
+        struct ActBrokenAdd;
+
+        impl Monoid for ActBrokenAdd {
+            type Element = i64;
+            fn op(&self, g: &i64, f: &i64) -> i64 {
+                g + f
+            }
+            fn id(&self) -> i64 {
+                0
+            }
+        }
+
+        impl Action<OpAdd<i64>> for ActBrokenAdd {
+            fn act(&self, f: &i64, x: &i64) -> i64 {
+                x + f
+            }
+        }
+
+        let violation = check_action(
+            &OpAdd::<i64>::default(),
+            &ActBrokenAdd,
+            3,
+            |i| i as i64,
+            |i| i as i64,
+        )
+        .unwrap_err();
+        assert_eq!(violation.law, Law::ActionHomomorphism);
+        assert_eq!(violation.inputs, vec!["1", "0", "0"]);
+    }
+
+    #[test]
+    fn test_enumeration_order() {
+        for n in 0..6 {
+            let mut visited = vec![];
+            let result = check_laws::<3>(Law::Associativity, n, |t| {
+                visited.push(t);
+                None
+            });
+            assert_eq!(result, Ok(()));
+
+            let mut expected = vec![];
+            for i in 0..n {
+                for j in 0..n {
+                    for k in 0..n {
+                        expected.push([i, j, k]);
+                    }
+                }
+            }
+            expected.sort_by_key(|t| (*t.iter().max().unwrap(), *t));
+            assert_eq!(visited, expected);
+        }
+    }
+
+    #[test]
+    fn test_empty_samples() {
+        assert_eq!(check_monoid(&OpSub, 0, |i| i as i64), Ok(()));
+    }
+}
@@ -0,0 +1,163 @@
+//! 写像による変換(Mapped)
+//!
+//! 演算`O`を，全単射`forward: T -> O::Element`と，その逆写像`backward: O::Element -> T`で
+//! 別の型`T`上の演算`x * y = backward(op(forward(x), forward(y)))`に移す．
+//! 単位元は`backward(id())`，逆元は`backward(inv(forward(x)))`．
+//! 要素を全単射で付け替えるだけなので，`O`が冪等・可換・群であれば`T`上の演算も同じ性質を持つ．
+//!
+//! # Examples
+//!
+//! ```
+//! use reprol::ops::mapped::Mapped;
+//! use reprol::ops::monoid::Monoid;
+//! use reprol::ops::op_min::OpMin;
+//!
+//! // 符号を反転して最小値を最大値として扱う
+//! let m = Mapped::new(OpMin::<i64>::default(), |&x: &i64| -x, |x| -x);
+//! assert_eq!(m.op(&3, &5), 5);
+//! assert_eq!(m.op(&m.id(), &-42), -42);
+//! ```
+//!
+//! # Notes
+//!
+//! `forward`と`backward`が互いに逆写像でない場合，法則を満たすとは限らない．
+
+use std::marker::PhantomData;
+
+use crate::ops::{
+    group::{AbelianGroup, Group},
+    monoid::{CommutativeMonoid, IdempotentMonoid, Monoid},
+};
+
+/// 写像による変換
+///
+/// 演算`O`を，写像`forward`と`backward`で型`T`上の演算に移したもの．
+#[derive(Clone, Copy)]
+pub struct Mapped<O, T, F, G> {
+    op: O,
+    forward: F,
+    backward: G,
+    phantom: PhantomData<fn(T) -> T>,
+}
+
+impl<O, T, F, G> Mapped<O, T, F, G>
+where
+    O: Monoid,
+    F: Fn(&T) -> O::Element,
+    G: Fn(O::Element) -> T,
+{
+    /// 演算`op`と，全単射`forward`およびその逆写像`backward`から構築する．
+    pub fn new(op: O, forward: F, backward: G) -> Self {
+        Self {
+            op,
+            forward,
+            backward,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<O, T, F, G> Monoid for Mapped<O, T, F, G>
+where
+    O: Monoid,
+    F: Fn(&T) -> O::Element,
+    G: Fn(O::Element) -> T,
+{
+    type Element = T;
+
+    #[inline]
+    fn op(&self, lhs: &Self::Element, rhs: &Self::Element) -> Self::Element {
+        (self.backward)(self.op.op(&(self.forward)(lhs), &(self.forward)(rhs)))
+    }
+
+    #[inline]
+    fn id(&self) -> Self::Element {
+        (self.backward)(self.op.id())
+    }
+}
+
+impl<O, T, F, G> IdempotentMonoid for Mapped<O, T, F, G>
+where
+    O: IdempotentMonoid,
+    F: Fn(&T) -> O::Element,
+    G: Fn(O::Element) -> T,
+{
+}
+
+impl<O, T, F, G> CommutativeMonoid for Mapped<O, T, F, G>
+where
+    O: CommutativeMonoid,
+    F: Fn(&T) -> O::Element,
+    G: Fn(O::Element) -> T,
+{
+}
+
+impl<O, T, F, G> Group for Mapped<O, T, F, G>
+where
+    O: Group,
+    F: Fn(&T) -> O::Element,
+    G: Fn(O::Element) -> T,
+{
+    #[inline]
+    fn inv(&self, x: &Self::Element) -> Self::Element {
+        (self.backward)(self.op.inv(&(self.forward)(x)))
+    }
+}
+
+impl<O, T, F, G> AbelianGroup for Mapped<O, T, F, G>
+where
+    O: AbelianGroup,
+    F: Fn(&T) -> O::Element,
+    G: Fn(O::Element) -> T,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::Wrapping;
+
+    use super::*;
+    use crate::{
+        ops::{
+            laws::{check_group, check_idempotent_monoid},
+            op_add::OpAdd,
+            op_max::OpMax,
+            op_xor::OpXor,
+        },
+        utils::test_utils::ops::{assert_abelian_group, assert_idempotent_monoid},
+    };
+
+    #[test]
+    fn test_mapped() {
+        // 文字の最大値
+        let m = Mapped::new(
+            OpMax::<u32>::default(),
+            |&c: &char| c as u32,
+            |x| char::from_u32(x).unwrap_or('\0'),
+        );
+        assert_idempotent_monoid(&m);
+        assert_eq!(m.op(&'a', &'c'), 'c');
+        assert_eq!(m.id(), '\0');
+        assert_eq!(
+            check_idempotent_monoid(&m, 5, |i| (b'a' + i as u8) as char),
+            Ok(())
+        );
+
+        // bool の排他的論理和
+        let m = Mapped::new(OpXor::<u8>::default(), |&b: &bool| b as u8, |x| x == 1);
+        assert_abelian_group(&m);
+        assert!(m.op(&true, &false));
+        assert!(!m.op(&true, &true));
+        assert_eq!(check_group(&m, 2, |i| i == 1), Ok(()));
+
+        // Wrapping<i64> の加算
+        let m = Mapped::new(OpAdd::<i64>::default(), |x: &Wrapping<i64>| x.0, Wrapping);
+        assert_abelian_group(&m);
+        assert_eq!(m.op(&Wrapping(i64::MAX), &Wrapping(1)), Wrapping(i64::MIN));
+        assert_eq!(m.inv(&Wrapping(i64::MIN)), Wrapping(i64::MIN));
+        assert_eq!(
+            check_group(&m, 6, |i| Wrapping(i64::MAX - 2) + Wrapping(i as i64)),
+            Ok(())
+        );
+    }
+}
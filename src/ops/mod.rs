@@ -2,15 +2,23 @@
 
 pub mod act_add;
 pub mod act_affine;
+pub mod act_none;
 pub mod act_range_add;
 pub mod act_range_set;
 pub mod act_set;
 pub mod action;
 pub mod group;
+pub mod laws;
+pub mod mapped;
 pub mod monoid;
 pub mod op_add;
 pub mod op_gcd;
 pub mod op_max;
 pub mod op_min;
 pub mod op_range_sum;
+pub mod op_unit;
 pub mod op_xor;
+pub mod product;
+pub mod reversed;
+pub mod semigroup;
+pub mod with_identity;
@@ -0,0 +1,65 @@
+//! 自明な演算
+//!
+//! 単位元のみからなる自明なモノイド(自明群)．
+//! 要素の型は`()`で，演算は常に単位元を返す．
+//!
+//! 総積を取る必要がないデータ構造を，総積なしで利用する場合に使用する．
+//!
+//! # Examples
+//!
+//! ```
+//! use reprol::ops::monoid::Monoid;
+//! use reprol::ops::op_unit::OpUnit;
+//!
+//! let m = OpUnit;
+//! assert_eq!(m.op(&(), &()), ());
+//! assert_eq!(m.id(), ());
+//! ```
+
+use crate::ops::{
+    group::{AbelianGroup, Group},
+    monoid::{CommutativeMonoid, IdempotentMonoid, Monoid},
+};
+
+/// 自明な演算
+///
+/// 単位元`()`のみからなるモノイド．
+#[derive(Default, Clone, Copy)]
+pub struct OpUnit;
+
+impl Monoid for OpUnit {
+    type Element = ();
+
+    #[inline]
+    fn op(&self, _: &Self::Element, _: &Self::Element) -> Self::Element {}
+
+    #[inline]
+    fn id(&self) -> Self::Element {}
+}
+
+impl IdempotentMonoid for OpUnit {}
+
+impl CommutativeMonoid for OpUnit {}
+
+impl Group for OpUnit {
+    #[inline]
+    fn inv(&self, _: &Self::Element) -> Self::Element {}
+}
+
+impl AbelianGroup for OpUnit {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ops::laws::check_group,
+        utils::test_utils::ops::{assert_abelian_group, assert_idempotent_monoid},
+    };
+
+    #[test]
+    fn test_laws() {
+        assert_idempotent_monoid(&OpUnit);
+        assert_abelian_group(&OpUnit);
+        assert_eq!(check_group(&OpUnit, 1, |_| ()), Ok(()));
+    }
+}
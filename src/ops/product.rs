@@ -0,0 +1,158 @@
+//! 直積(Product)
+//!
+//! 2つの演算の組`(A, B)`を，要素ごとに演算する直積として扱う．
+//! 「最小値とその個数」「総和と最大値」のような複合的な演算を，既存の演算の組み合わせで表せる．
+//!
+//! - `(A, B)`: `A`と`B`がモノイドなら直積もモノイド．
+//!   冪等性・可換性・逆元は，両方が満たす場合に直積も満たす．
+//! - `(A, B)`: `A`が`OA`に，`B`が`OB`に作用するなら，直積は`(OA, OB)`に要素ごとに作用する．
+//!
+//! # Examples
+//!
+//! ```
+//! use reprol::ops::monoid::Monoid;
+//! use reprol::ops::op_add::OpAdd;
+//! use reprol::ops::op_max::OpMax;
+//!
+//! // 総和と最大値
+//! let m = (OpAdd::<i64>::default(), OpMax::<i64>::default());
+//! assert_eq!(m.op(&(3, 3), &(5, 5)), (8, 5));
+//! assert_eq!(m.op(&m.id(), &(4, 4)), (4, 4));
+//! ```
+
+use crate::ops::{
+    action::Action,
+    group::{AbelianGroup, Group},
+    monoid::{CommutativeMonoid, IdempotentMonoid, Monoid},
+};
+
+impl<A, B> Monoid for (A, B)
+where
+    A: Monoid,
+    B: Monoid,
+{
+    type Element = (A::Element, B::Element);
+
+    #[inline]
+    fn op(&self, lhs: &Self::Element, rhs: &Self::Element) -> Self::Element {
+        (self.0.op(&lhs.0, &rhs.0), self.1.op(&lhs.1, &rhs.1))
+    }
+
+    #[inline]
+    fn id(&self) -> Self::Element {
+        (self.0.id(), self.1.id())
+    }
+}
+
+impl<A, B> IdempotentMonoid for (A, B)
+where
+    A: IdempotentMonoid,
+    B: IdempotentMonoid,
+{
+}
+
+impl<A, B> CommutativeMonoid for (A, B)
+where
+    A: CommutativeMonoid,
+    B: CommutativeMonoid,
+{
+}
+
+impl<A, B> Group for (A, B)
+where
+    A: Group,
+    B: Group,
+{
+    #[inline]
+    fn inv(&self, x: &Self::Element) -> Self::Element {
+        (self.0.inv(&x.0), self.1.inv(&x.1))
+    }
+}
+
+impl<A, B> AbelianGroup for (A, B)
+where
+    A: AbelianGroup,
+    B: AbelianGroup,
+{
+}
+
+impl<A, B, OA, OB> Action<(OA, OB)> for (A, B)
+where
+    A: Action<OA>,
+    B: Action<OB>,
+    OA: Monoid,
+    OB: Monoid,
+{
+    #[inline]
+    fn act(&self, f: &Self::Element, x: &(OA::Element, OB::Element)) -> (OA::Element, OB::Element) {
+        (self.0.act(&f.0, &x.0), self.1.act(&f.1, &x.1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ops::{
+            act_add::ActAdd,
+            act_set::ActSet,
+            laws::{check_action, check_group, check_idempotent_monoid, check_monoid},
+            op_add::OpAdd,
+            op_max::OpMax,
+            op_min::OpMin,
+            op_range_sum::{OpRangeSum, OpRangeSumElement},
+            op_xor::OpXor,
+        },
+        utils::test_utils::ops::{
+            assert_abelian_group, assert_commutative_monoid, assert_idempotent_monoid,
+        },
+    };
+
+    #[test]
+    fn test_marker_traits() {
+        assert_idempotent_monoid(&(OpMin::<i64>::default(), OpMax::<i64>::default()));
+        assert_commutative_monoid(&(OpAdd::<i64>::default(), OpMin::<i64>::default()));
+        assert_abelian_group(&(OpAdd::<i64>::default(), OpXor::<u32>::default()));
+        assert_abelian_group(&(
+            (OpAdd::<i64>::default(), OpAdd::<i32>::default()),
+            OpXor::<u32>::default(),
+        ));
+    }
+
+    #[test]
+    fn test_laws() {
+        let m = (OpMin::<i64>::default(), OpMax::<i64>::default());
+        assert_eq!(
+            check_idempotent_monoid(&m, 6, |i| (i as i64 % 3, i as i64 / 2)),
+            Ok(())
+        );
+
+        let m = (OpAdd::<i64>::default(), OpXor::<u32>::default());
+        assert_eq!(check_group(&m, 6, |i| (i as i64 - 3, i as u32)), Ok(()));
+
+        let m = (OpRangeSum::<i64>::default(), OpMin::<i64>::default());
+        assert_eq!(
+            check_monoid(&m, 6, |i| (OpRangeSumElement::leaf(i as i64), 5 - i as i64)),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_action() {
+        let op = (OpMin::<i64>::default(), OpMax::<i64>::default());
+        let act = (ActAdd::<i64>::default(), ActSet::<i64>::default());
+        let apply = |f, x| Action::<(OpMin<i64>, OpMax<i64>)>::act(&act, f, x);
+        assert_eq!(apply(&(3, Some(7)), &(1, 2)), (4, 7));
+        assert_eq!(apply(&act.id(), &(1, 2)), (1, 2));
+        assert_eq!(
+            check_action(
+                &op,
+                &act,
+                6,
+                |i| (i as i64 - 3, i as i64 % 4),
+                |i| (i as i64 % 3, (i % 2 == 1).then_some(i as i64))
+            ),
+            Ok(())
+        );
+    }
+}
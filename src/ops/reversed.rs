@@ -0,0 +1,105 @@
+//! 双対(Reversed)
+//!
+//! 演算の左右を入れ替えたモノイド`x * y = op(y, x)`．
+//! 行列積や文字列の連結，アフィン変換の合成のような非可換な演算を，逆順に畳み込むときに用いる．
+//! `O`が可換なら元の演算と一致する．
+//! `x * inv(x) = inv(x) * x = e`は左右を入れ替えても変わらないため，`O`が群なら逆元は`O`のものをそのまま用いる．
+//!
+//! # Examples
+//!
+//! ```
+//! use reprol::ops::monoid::Monoid;
+//! use reprol::ops::reversed::Reversed;
+//!
+//! struct OpConcat;
+//!
+//! impl Monoid for OpConcat {
+//!     type Element = String;
+//!     fn op(&self, lhs: &String, rhs: &String) -> String { format!("{lhs}{rhs}") }
+//!     fn id(&self) -> String { String::new() }
+//! }
+//!
+//! let m = Reversed(OpConcat);
+//! assert_eq!(m.op(&"ab".to_string(), &"cd".to_string()), "cdab");
+//! ```
+
+use crate::ops::{
+    group::{AbelianGroup, Group},
+    monoid::{CommutativeMonoid, IdempotentMonoid, Monoid},
+};
+
+/// 双対
+///
+/// 演算`O`の左右を入れ替えたモノイド．
+#[derive(Default, Clone, Copy)]
+pub struct Reversed<O>(pub O);
+
+impl<O: Monoid> Monoid for Reversed<O> {
+    type Element = O::Element;
+
+    #[inline]
+    fn op(&self, lhs: &Self::Element, rhs: &Self::Element) -> Self::Element {
+        self.0.op(rhs, lhs)
+    }
+
+    #[inline]
+    fn id(&self) -> Self::Element {
+        self.0.id()
+    }
+}
+
+impl<O: IdempotentMonoid> IdempotentMonoid for Reversed<O> {}
+
+impl<O: CommutativeMonoid> CommutativeMonoid for Reversed<O> {}
+
+impl<O: Group> Group for Reversed<O> {
+    #[inline]
+    fn inv(&self, x: &Self::Element) -> Self::Element {
+        self.0.inv(x)
+    }
+}
+
+impl<O: AbelianGroup> AbelianGroup for Reversed<O> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ops::{
+            act_affine::{ActAffine, ActAffineElement},
+            laws::{check_group, check_idempotent_monoid, check_monoid},
+            op_add::OpAdd,
+            op_min::OpMin,
+        },
+        utils::test_utils::ops::{assert_abelian_group, assert_idempotent_monoid},
+    };
+
+    #[test]
+    fn test_marker_traits() {
+        assert_idempotent_monoid(&Reversed(OpMin::<i64>::default()));
+        assert_abelian_group(&Reversed(OpAdd::<i64>::default()));
+    }
+
+    #[test]
+    fn test_reversed() {
+        let m = Reversed(ActAffine::<i64>::default());
+        let f = ActAffineElement { a: 2, b: 3 };
+        let g = ActAffineElement { a: 5, b: 7 };
+        assert_eq!(m.op(&f, &g), m.0.op(&g, &f));
+        assert_eq!(m.op(&m.id(), &f), f);
+
+        let element = |i: usize| ActAffineElement {
+            a: i as i64 % 3 - 1,
+            b: i as i64 / 3,
+        };
+        assert_eq!(check_monoid(&m, 6, element), Ok(()));
+        assert_eq!(
+            check_idempotent_monoid(&Reversed(OpMin::<i64>::default()), 6, |i| i as i64),
+            Ok(())
+        );
+        assert_eq!(
+            check_group(&Reversed(OpAdd::<i64>::default()), 6, |i| i as i64 - 3),
+            Ok(())
+        );
+    }
+}
@@ -0,0 +1,70 @@
+//! 半群(Semigroup)
+//!
+//! 結合的な二項演算を持つ代数的構造を表すトレイト．
+//! 単位元を持たない演算を表し，[`WithIdentity`](crate::ops::with_identity::WithIdentity)でモノイドに持ち上げて用いる．
+//!
+//! - [`Semigroup`][]: 半群
+//! - [`IdempotentSemigroup`][]: 冪等性を持つ半群
+//! - [`CommutativeSemigroup`][]: 可換性を持つ半群
+//!
+//! 任意の[`Monoid`]は半群でもあり，[`IdempotentMonoid`]・[`CommutativeMonoid`]はそれぞれ
+//! [`IdempotentSemigroup`]・[`CommutativeSemigroup`]を実装する．
+//! 両方のトレイトをインポートしても呼び出しが曖昧にならないよう，演算のメソッド名は`combine`とする．
+//!
+//! # Examples
+//!
+//! ```
+//! use reprol::ops::op_max::OpMax;
+//! use reprol::ops::semigroup::Semigroup;
+//!
+//! // 左側の要素を返す(単位元を持たない)
+//! struct OpFirst;
+//!
+//! impl Semigroup for OpFirst {
+//!     type Element = i64;
+//!     fn combine(&self, lhs: &i64, _: &i64) -> i64 { *lhs }
+//! }
+//!
+//! assert_eq!(OpFirst.combine(&3, &5), 3);
+//!
+//! // モノイドは半群でもある
+//! assert_eq!(OpMax::<i64>::default().combine(&3, &5), 5);
+//! ```
+
+use crate::ops::monoid::{CommutativeMonoid, IdempotentMonoid, Monoid};
+
+/// 半群(Semigroup)
+///
+/// 結合則を満たす二項演算を持つ．
+/// 集合の任意の要素 `x`, `y`, `z` に対して `(x * y) * z = x * (y * z)` を満たす．
+pub trait Semigroup {
+    type Element;
+
+    /// 演算
+    ///
+    /// `lhs * rhs`
+    fn combine(&self, lhs: &Self::Element, rhs: &Self::Element) -> Self::Element;
+}
+
+/// 冪等半群(Idempotent Semigroup)
+///
+/// 集合の任意の要素 `x` に対して `x * x = x` を満たす半群．
+pub trait IdempotentSemigroup: Semigroup {}
+
+/// 可換半群(Commutative Semigroup)
+///
+/// 集合の任意の要素 `x`, `y` に対して `x * y = y * x` を満たす半群．
+pub trait CommutativeSemigroup: Semigroup {}
+
+impl<M: Monoid> Semigroup for M {
+    type Element = M::Element;
+
+    #[inline]
+    fn combine(&self, lhs: &Self::Element, rhs: &Self::Element) -> Self::Element {
+        self.op(lhs, rhs)
+    }
+}
+
+impl<M: IdempotentMonoid> IdempotentSemigroup for M {}
+
+impl<M: CommutativeMonoid> CommutativeSemigroup for M {}
@@ -0,0 +1,151 @@
+//! 単位元の付加(With Identity)
+//!
+//! 半群`S`に単位元`None`を付け加えてモノイドにする．
+//! 要素は`Option<S::Element>`で，`Some`同士は`S`の演算，`None`は単位元として扱う．
+//! 型の最大値のような番兵を用意しにくい演算(文字列の最大値，区間の結合など)をセグメント木などに載せるときに用いる．
+//! `S`が冪等・可換なら，持ち上げたモノイドも冪等・可換になる．
+//! `None`以外の逆元は存在しないため，[`Group`](crate::ops::group::Group)は実装しない．
+//!
+//! # Examples
+//!
+//! ```
+//! use reprol::ops::monoid::Monoid;
+//! use reprol::ops::semigroup::Semigroup;
+//! use reprol::ops::with_identity::WithIdentity;
+//!
+//! // 辞書順最大の文字列
+//! struct OpMaxString;
+//!
+//! impl Semigroup for OpMaxString {
+//!     type Element = String;
+//!     fn combine(&self, lhs: &String, rhs: &String) -> String { lhs.max(rhs).clone() }
+//! }
+//!
+//! let m = WithIdentity(OpMaxString);
+//! let x = Some("abc".to_string());
+//! let y = Some("abd".to_string());
+//! assert_eq!(m.op(&x, &y), y);
+//! assert_eq!(m.op(&m.id(), &x), x);
+//! assert_eq!(m.op(&None, &None), None);
+//! ```
+
+use crate::ops::{
+    monoid::{CommutativeMonoid, IdempotentMonoid, Monoid},
+    semigroup::{CommutativeSemigroup, IdempotentSemigroup, Semigroup},
+};
+
+/// 単位元の付加
+///
+/// 半群`S`に単位元`None`を付け加えたモノイド．
+#[derive(Default, Clone, Copy)]
+pub struct WithIdentity<S>(pub S);
+
+impl<S> Monoid for WithIdentity<S>
+where
+    S: Semigroup,
+    S::Element: Clone,
+{
+    type Element = Option<S::Element>;
+
+    #[inline]
+    fn op(&self, lhs: &Self::Element, rhs: &Self::Element) -> Self::Element {
+        match (lhs, rhs) {
+            (Some(lhs), Some(rhs)) => Some(self.0.combine(lhs, rhs)),
+            (Some(x), None) | (None, Some(x)) => Some(x.clone()),
+            (None, None) => None,
+        }
+    }
+
+    #[inline]
+    fn id(&self) -> Self::Element {
+        None
+    }
+}
+
+impl<S> IdempotentMonoid for WithIdentity<S>
+where
+    S: IdempotentSemigroup,
+    S::Element: Clone,
+{
+}
+
+impl<S> CommutativeMonoid for WithIdentity<S>
+where
+    S: CommutativeSemigroup,
+    S::Element: Clone,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ops::{
+            laws::{check_commutative_monoid, check_idempotent_monoid},
+            op_max::OpMax,
+        },
+        utils::test_utils::ops::{assert_commutative_monoid, assert_idempotent_monoid},
+    };
+
+    /// 区間`[l, r)`を連結する(隣接していることを仮定しない)
+    struct OpHull;
+
+    impl Semigroup for OpHull {
+        type Element = (i64, i64);
+        fn combine(&self, lhs: &(i64, i64), rhs: &(i64, i64)) -> (i64, i64) {
+            (lhs.0.min(rhs.0), lhs.1.max(rhs.1))
+        }
+    }
+
+    impl IdempotentSemigroup for OpHull {}
+    impl CommutativeSemigroup for OpHull {}
+
+    /// 左側の要素を返す
+    struct OpFirst;
+
+    impl Semigroup for OpFirst {
+        type Element = i64;
+        fn combine(&self, lhs: &i64, _: &i64) -> i64 {
+            *lhs
+        }
+    }
+
+    impl IdempotentSemigroup for OpFirst {}
+
+    #[test]
+    fn test_marker_traits() {
+        assert_idempotent_monoid(&WithIdentity(OpHull));
+        assert_commutative_monoid(&WithIdentity(OpHull));
+        assert_idempotent_monoid(&WithIdentity(OpFirst));
+        assert_commutative_monoid(&WithIdentity(OpMax::<i64>::default()));
+    }
+
+    #[test]
+    fn test_laws() {
+        let element = |i: usize| (i > 0).then(|| (i as i64 % 3, i as i64 % 3 + i as i64 / 3));
+        assert_eq!(
+            check_idempotent_monoid(&WithIdentity(OpHull), 8, element),
+            Ok(())
+        );
+        assert_eq!(
+            check_commutative_monoid(&WithIdentity(OpHull), 8, element),
+            Ok(())
+        );
+
+        let m = WithIdentity(OpFirst);
+        assert_eq!(
+            check_idempotent_monoid(&m, 5, |i| i.checked_sub(1).map(|x| x as i64)),
+            Ok(())
+        );
+        assert_eq!(m.op(&None, &Some(3)), Some(3));
+        assert_eq!(m.op(&Some(2), &Some(3)), Some(2));
+
+        // 既存のモノイドも半群として持ち上げられる
+        let m = WithIdentity(OpMax::<i64>::default());
+        assert_eq!(m.op(&None, &Some(i64::MIN)), Some(i64::MIN));
+        assert_eq!(
+            check_idempotent_monoid(&m, 5, |i| (i > 0).then_some(i as i64 - 3)),
+            Ok(())
+        );
+    }
+}
@@ -1,5 +1,6 @@
 pub mod dynamic_range_query;
 pub mod dynamic_range_query_2d;
+pub mod ops;
 pub mod random;
 pub mod static_range_query;
 pub mod static_range_query_2d;
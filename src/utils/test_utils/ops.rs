@@ -0,0 +1,15 @@
+//! 演算のマーカートレイトの検査
+
+use crate::ops::{
+    group::AbelianGroup,
+    monoid::{CommutativeMonoid, IdempotentMonoid},
+};
+
+/// `op`が[`IdempotentMonoid`]を実装することをコンパイル時に確かめる．
+pub fn assert_idempotent_monoid<O: IdempotentMonoid>(_: &O) {}
+
+/// `op`が[`CommutativeMonoid`]を実装することをコンパイル時に確かめる．
+pub fn assert_commutative_monoid<O: CommutativeMonoid>(_: &O) {}
+
+/// `op`が[`AbelianGroup`]を実装することをコンパイル時に確かめる．
+pub fn assert_abelian_group<O: AbelianGroup>(_: &O) {}